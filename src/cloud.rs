@@ -16,25 +16,46 @@
 
 use std::rc::Rc;
 
+#[cfg(feature = "compute")]
+use chrono::{DateTime, TimeZone};
 #[allow(unused_imports)]
 use ipnet;
 use osauth::sync::SyncSession;
-use osauth::{AuthType, Session};
+use osauth::AuthType;
 
 #[allow(unused_imports)]
-use super::common::{FlavorRef, NetworkRef};
+use super::common::{
+    FlavorRef, IkePolicyRef, IpsecPolicyRef, MeteringLabelRef, NetworkRef, PortRef, ProjectRef,
+    RouterRef, SecurityGroupRef, SubnetPoolRef, VpnServiceRef,
+};
 #[cfg(feature = "compute")]
 use super::compute::{
-    Flavor, FlavorQuery, FlavorSummary, KeyPair, KeyPairQuery, NewKeyPair, NewServer, Server,
-    ServerQuery, ServerSummary,
+    self, Aggregate, AggregateQuery, AvailabilityZone, AvailabilityZoneQuery, ComputeService,
+    ComputeServiceQuery, Flavor, FlavorQuery, FlavorSummary, Hypervisor, HypervisorQuery,
+    HypervisorStatistics, KeyPair, KeyPairQuery, NewAggregate, NewKeyPair, NewServer, ProjectUsage,
+    Server, ServerQuery, ServerSummary,
 };
 #[cfg(feature = "image")]
 use super::image::{Image, ImageQuery};
 #[cfg(feature = "network")]
 use super::network::{
-    FloatingIp, FloatingIpQuery, Network, NetworkQuery, NewFloatingIp, NewNetwork, NewPort,
-    NewSubnet, Port, PortQuery, Subnet, SubnetQuery,
+    self, AddressScope, AddressScopeQuery, Agent, AgentQuery, EndpointGroup, EndpointGroupQuery,
+    EndpointGroupType, FirewallGroup, FirewallGroupQuery, FirewallPolicy, FirewallPolicyQuery,
+    FirewallRule, FirewallRuleAction, FirewallRuleQuery, FloatingIp, FloatingIpQuery, IkePolicy,
+    IkePolicyQuery, IpVersion, IpsecPolicy, IpsecPolicyQuery, IpsecSiteConnection,
+    IpsecSiteConnectionQuery, MeteringLabel, MeteringLabelQuery, MeteringLabelRule,
+    MeteringLabelRuleDirection, MeteringLabelRuleQuery, Network, NetworkExtension, NetworkQuery,
+    NetworkTopology, NetworkType, NewAddressScope, NewEndpointGroup, NewFirewallGroup,
+    NewFirewallPolicy, NewFirewallRule, NewFloatingIp, NewIkePolicy, NewIpsecPolicy,
+    NewIpsecSiteConnection, NewMeteringLabel, NewMeteringLabelRule, NewNetwork, NewPort,
+    NewQosPolicy, NewRbacPolicy, NewRouter, NewSecurityGroup, NewSecurityGroupRule, NewSegment,
+    NewSubnet, NewSubnetPool, NewTrunk, NewVpnService, Port, PortQuery, QosPolicy, QosPolicyQuery,
+    RbacPolicy, RbacPolicyAction, RbacPolicyObjectType, RbacPolicyQuery, Router, RouterQuery,
+    SecurityGroup, SecurityGroupQuery, SecurityGroupRule, SecurityGroupRuleDirection,
+    SecurityGroupRuleQuery, Segment, SegmentQuery, Subnet, SubnetPool, SubnetPoolQuery,
+    SubnetQuery, Trunk, TrunkQuery, VpnService, VpnServiceQuery,
 };
+use super::utils::Session;
 use super::Result;
 
 /// OpenStack cloud API.
@@ -42,7 +63,7 @@ use super::Result;
 /// Provides high-level API for working with OpenStack clouds.
 #[derive(Debug, Clone)]
 pub struct Cloud {
-    session: Rc<SyncSession>,
+    session: Rc<Session>,
 }
 
 impl Cloud {
@@ -72,7 +93,7 @@ impl Cloud {
     /// * [from_env](#method.from_env) to create a Cloud from environment variables
     pub fn new<Auth: AuthType + 'static>(auth_type: Auth) -> Cloud {
         Cloud {
-            session: Rc::new(SyncSession::new(Session::new(auth_type))),
+            session: Rc::new(osauth::Session::new(auth_type).into()),
         }
     }
 
@@ -88,7 +109,7 @@ impl Cloud {
     /// ```
     pub fn from_config<S: AsRef<str>>(cloud_name: S) -> Result<Cloud> {
         Ok(Cloud {
-            session: Rc::new(SyncSession::new(osauth::from_config(cloud_name)?)),
+            session: Rc::new(osauth::from_config(cloud_name)?.into()),
         })
     }
 
@@ -104,7 +125,7 @@ impl Cloud {
     /// ```
     pub fn from_env() -> Result<Cloud> {
         Ok(Cloud {
-            session: Rc::new(SyncSession::new(osauth::from_env()?)),
+            session: Rc::new(osauth::from_env()?.into()),
         })
     }
 
@@ -133,6 +154,241 @@ impl Cloud {
         Rc::make_mut(&mut self.session).refresh()
     }
 
+    /// Get the auto-allocated network of a project, allocating it if needed.
+    ///
+    /// This is the "get me a network" feature of the Network API: a network,
+    /// a subnet and a router connected to the default external network are
+    /// created on the first call. Use `validate_auto_allocated_topology` to
+    /// check the prerequisites without allocating anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let network = os
+    ///     .auto_allocated_topology("8a4c9b3e")
+    ///     .expect("Unable to allocate a network");
+    /// println!("Allocated network {}", network.id());
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn auto_allocated_topology<P>(&self, project: P) -> Result<Network>
+    where
+        P: Into<ProjectRef>,
+    {
+        network::auto_allocated_topology(self.session.clone(), project.into())
+    }
+
+    /// Get compute usage of all projects over a period of time.
+    ///
+    /// Requires administrative privileges.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// extern crate chrono;
+    /// extern crate openstack;
+    ///
+    /// use chrono::{Duration, Utc};
+    ///
+    /// # fn main() {
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let end = Utc::now();
+    /// let usage = os
+    ///     .compute_usage(end - Duration::days(30), end)
+    ///     .expect("Unable to get usage");
+    /// for project in usage {
+    ///     println!("{}: {} VCPU-hours", project.project_id(), project.total_vcpu_hours());
+    /// }
+    /// # }
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn compute_usage<Tz: TimeZone>(
+        &self,
+        start: DateTime<Tz>,
+        end: DateTime<Tz>,
+    ) -> Result<Vec<ProjectUsage>> {
+        compute::compute_usage(&self.session, &start, &end)
+    }
+
+    /// Create several networks in one request.
+    ///
+    /// The networks are returned in the same order as the requests. Either all
+    /// networks are created or none of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let requests = (0..4)
+    ///     .map(|idx| os.new_network().with_name(format!("net-{}", idx)))
+    ///     .collect();
+    /// let networks = os.create_networks(requests).expect("Unable to create networks");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn create_networks(&self, networks: Vec<NewNetwork>) -> Result<Vec<Network>> {
+        network::create_networks(self.session.clone(), networks)
+    }
+
+    /// Create several ports in one request.
+    ///
+    /// The ports are returned in the same order as the requests. Either all
+    /// ports are created or none of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let requests = (0..200)
+    ///     .map(|idx| os.new_port("cluster-net").with_name(format!("node-{}", idx)))
+    ///     .collect();
+    /// let ports = os.create_ports(requests).expect("Unable to create ports");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn create_ports(&self, ports: Vec<NewPort>) -> Result<Vec<Port>> {
+        network::create_ports(self.session.clone(), ports)
+    }
+
+    /// Create several subnets in one request.
+    ///
+    /// The subnets are returned in the same order as the requests. Either all
+    /// subnets are created or none of them.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let requests = vec![
+    ///     os.new_subnet("cluster-net", "192.168.1.0/24".parse().unwrap()),
+    ///     os.new_subnet("cluster-net", "fd00::/64".parse().unwrap()),
+    /// ];
+    /// let subnets = os.create_subnets(requests).expect("Unable to create subnets");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn create_subnets(&self, subnets: Vec<NewSubnet>) -> Result<Vec<Subnet>> {
+        network::create_subnets(self.session.clone(), subnets)
+    }
+
+    /// Delete the auto-allocated topology of a project.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// os.delete_auto_allocated_topology("8a4c9b3e")
+    ///     .expect("Unable to delete the topology");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn delete_auto_allocated_topology<P>(&self, project: P) -> Result<()>
+    where
+        P: Into<ProjectRef>,
+    {
+        network::delete_auto_allocated_topology(&self.session, project.into())
+    }
+
+    /// Build a query against address scope list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_address_scopes(&self) -> AddressScopeQuery {
+        AddressScopeQuery::new(self.session.clone())
+    }
+
+    /// Build a query against network agent list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let dead_agents = os
+    ///     .find_agents()
+    ///     .with_alive(false)
+    ///     .all()
+    ///     .expect("Unable to list agents");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn find_agents(&self) -> AgentQuery {
+        AgentQuery::new(self.session.clone())
+    }
+
+    /// Build a query against host aggregate list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "compute")]
+    pub fn find_aggregates(&self) -> AggregateQuery {
+        AggregateQuery::new(self.session.clone())
+    }
+
+    /// Build a query against availability zone list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "compute")]
+    pub fn find_availability_zones(&self) -> AvailabilityZoneQuery {
+        AvailabilityZoneQuery::new(self.session.clone())
+    }
+
+    /// Build a query against compute service list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "compute")]
+    pub fn find_compute_services(&self) -> ComputeServiceQuery {
+        ComputeServiceQuery::new(self.session.clone())
+    }
+
+    /// Build a query against VPN endpoint group list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_endpoint_groups(&self) -> EndpointGroupQuery {
+        EndpointGroupQuery::new(self.session.clone())
+    }
+
+    /// Build a query against firewall group list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_firewall_groups(&self) -> FirewallGroupQuery {
+        FirewallGroupQuery::new(self.session.clone())
+    }
+
+    /// Build a query against firewall policy list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_firewall_policies(&self) -> FirewallPolicyQuery {
+        FirewallPolicyQuery::new(self.session.clone())
+    }
+
+    /// Build a query against firewall rule list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_firewall_rules(&self) -> FirewallRuleQuery {
+        FirewallRuleQuery::new(self.session.clone())
+    }
+
     /// Build a query against flavor list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -151,6 +407,24 @@ impl Cloud {
         FloatingIpQuery::new(self.session.clone())
     }
 
+    /// Build a query against hypervisor list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "compute")]
+    pub fn find_hypervisors(&self) -> HypervisorQuery {
+        HypervisorQuery::new(self.session.clone())
+    }
+
+    /// Build a query against IKE policy list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_ike_policies(&self) -> IkePolicyQuery {
+        IkePolicyQuery::new(self.session.clone())
+    }
+
     /// Build a query against image list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -160,6 +434,24 @@ impl Cloud {
         ImageQuery::new(self.session.clone())
     }
 
+    /// Build a query against IPsec policy list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_ipsec_policies(&self) -> IpsecPolicyQuery {
+        IpsecPolicyQuery::new(self.session.clone())
+    }
+
+    /// Build a query against IPsec site connection list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_ipsec_site_connections(&self) -> IpsecSiteConnectionQuery {
+        IpsecSiteConnectionQuery::new(self.session.clone())
+    }
+
     /// Build a query against key pairs list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -169,6 +461,24 @@ impl Cloud {
         KeyPairQuery::new(self.session.clone())
     }
 
+    /// Build a query against metering label rule list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_metering_label_rules(&self) -> MeteringLabelRuleQuery {
+        MeteringLabelRuleQuery::new(self.session.clone())
+    }
+
+    /// Build a query against metering label list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_metering_labels(&self) -> MeteringLabelQuery {
+        MeteringLabelQuery::new(self.session.clone())
+    }
+
     /// Build a query against network list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -187,6 +497,60 @@ impl Cloud {
         PortQuery::new(self.session.clone())
     }
 
+    /// Build a query against QoS policy list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_qos_policies(&self) -> QosPolicyQuery {
+        QosPolicyQuery::new(self.session.clone())
+    }
+
+    /// Build a query against RBAC policy list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_rbac_policies(&self) -> RbacPolicyQuery {
+        RbacPolicyQuery::new(self.session.clone())
+    }
+
+    /// Build a query against router list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_routers(&self) -> RouterQuery {
+        RouterQuery::new(self.session.clone())
+    }
+
+    /// Build a query against security group rule list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_security_group_rules(&self) -> SecurityGroupRuleQuery {
+        SecurityGroupRuleQuery::new(self.session.clone())
+    }
+
+    /// Build a query against security group list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_security_groups(&self) -> SecurityGroupQuery {
+        SecurityGroupQuery::new(self.session.clone())
+    }
+
+    /// Build a query against network segment list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_segments(&self) -> SegmentQuery {
+        SegmentQuery::new(self.session.clone())
+    }
+
     /// Build a query against server list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -210,6 +574,15 @@ impl Cloud {
         ServerQuery::new(self.session.clone())
     }
 
+    /// Build a query against subnet pool list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_subnet_pools(&self) -> SubnetPoolQuery {
+        SubnetPoolQuery::new(self.session.clone())
+    }
+
     /// Build a query against subnet list.
     ///
     /// The returned object is a builder that should be used to construct
@@ -219,7 +592,25 @@ impl Cloud {
         SubnetQuery::new(self.session.clone())
     }
 
-    /// Find a flavor by its name or ID.
+    /// Build a query against trunk list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_trunks(&self) -> TrunkQuery {
+        TrunkQuery::new(self.session.clone())
+    }
+
+    /// Build a query against VPN service list.
+    ///
+    /// The returned object is a builder that should be used to construct
+    /// the query.
+    #[cfg(feature = "network")]
+    pub fn find_vpn_services(&self) -> VpnServiceQuery {
+        VpnServiceQuery::new(self.session.clone())
+    }
+
+    /// Find an address scope by its name or ID.
     ///
     /// # Example
     ///
@@ -227,14 +618,15 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_flavor("m1.medium").expect("Unable to get a flavor");
+    /// let scope = os.get_address_scope("public-v4")
+    ///     .expect("Unable to get an address scope");
     /// ```
-    #[cfg(feature = "compute")]
-    pub fn get_flavor<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Flavor> {
-        Flavor::load(self.session.clone(), id_or_name)
+    #[cfg(feature = "network")]
+    pub fn get_address_scope<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<AddressScope> {
+        AddressScope::load(self.session.clone(), id_or_name)
     }
 
-    /// Find a floating IP by its ID.
+    /// Get a network agent by its ID.
     ///
     /// # Example
     ///
@@ -242,15 +634,19 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_floating_ip("031e08c7-2ca7-4c0b-9923-030c8d946ba4")
-    ///     .expect("Unable to get a floating IP");
+    /// let agent = os
+    ///     .get_agent("8a4c9b3e-5b4e-4a0c-9b6e-0fd0e3c6c2a1")
+    ///     .expect("Unable to get an agent");
+    /// for network in agent.dhcp_networks().all().expect("Unable to list networks") {
+    ///     println!("{} is hosted on {}", network.id(), agent.host());
+    /// }
     /// ```
     #[cfg(feature = "network")]
-    pub fn get_floating_ip<Id: AsRef<str>>(&self, id: Id) -> Result<FloatingIp> {
-        FloatingIp::load(self.session.clone(), id)
+    pub fn get_agent<Id: AsRef<str>>(&self, id: Id) -> Result<Agent> {
+        Agent::load(self.session.clone(), id)
     }
 
-    /// Find an image by its name or ID.
+    /// Find a host aggregate by its ID, UUID or name.
     ///
     /// # Example
     ///
@@ -258,14 +654,14 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_image("centos7").expect("Unable to get a image");
+    /// let aggregate = os.get_aggregate("gpu-hosts").expect("Unable to get an aggregate");
     /// ```
-    #[cfg(feature = "image")]
-    pub fn get_image<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Image> {
-        Image::new(self.session.clone(), id_or_name)
+    #[cfg(feature = "compute")]
+    pub fn get_aggregate<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Aggregate> {
+        Aggregate::load(self.session.clone(), id_or_name)
     }
 
-    /// Find a key pair by its name or ID.
+    /// Find a VPN endpoint group by its name or ID.
     ///
     /// # Example
     ///
@@ -273,14 +669,238 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_keypair("default").expect("Unable to get a key pair");
+    /// let group = os.get_endpoint_group("local-subnets")
+    ///     .expect("Unable to get a VPN endpoint group");
     /// ```
-    #[cfg(feature = "compute")]
-    pub fn get_keypair<Id: AsRef<str>>(&self, name: Id) -> Result<KeyPair> {
-        KeyPair::new(self.session.clone(), name)
+    #[cfg(feature = "network")]
+    pub fn get_endpoint_group<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<EndpointGroup> {
+        EndpointGroup::load(self.session.clone(), id_or_name)
     }
 
-    /// Find an network by its name or ID.
+    /// Find a firewall group by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let group = os.get_firewall_group("web")
+    ///     .expect("Unable to get a firewall group");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_firewall_group<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<FirewallGroup> {
+        FirewallGroup::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a firewall policy by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os.get_firewall_policy("web-ingress")
+    ///     .expect("Unable to get a firewall policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_firewall_policy<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<FirewallPolicy> {
+        FirewallPolicy::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a firewall rule by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let rule = os.get_firewall_rule("allow-http")
+    ///     .expect("Unable to get a firewall rule");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_firewall_rule<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<FirewallRule> {
+        FirewallRule::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a flavor by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_flavor("m1.medium").expect("Unable to get a flavor");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn get_flavor<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Flavor> {
+        Flavor::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a floating IP by its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_floating_ip("031e08c7-2ca7-4c0b-9923-030c8d946ba4")
+    ///     .expect("Unable to get a floating IP");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_floating_ip<Id: AsRef<str>>(&self, id: Id) -> Result<FloatingIp> {
+        FloatingIp::load(self.session.clone(), id)
+    }
+
+    /// Find a hypervisor by its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let hypervisor = os.get_hypervisor("1").expect("Unable to get a hypervisor");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn get_hypervisor<Id: AsRef<str>>(&self, id: Id) -> Result<Hypervisor> {
+        Hypervisor::load(self.session.clone(), id)
+    }
+
+    /// Get statistics aggregated over all hypervisors.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let stats = os.hypervisor_statistics().expect("Unable to get statistics");
+    /// println!("{} of {} VCPUs used", stats.vcpus_used, stats.vcpus);
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn hypervisor_statistics(&self) -> Result<HypervisorStatistics> {
+        compute::hypervisor_statistics(&self.session)
+    }
+
+    /// Find an IKE policy by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os.get_ike_policy("ike-default")
+    ///     .expect("Unable to get an IKE policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_ike_policy<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<IkePolicy> {
+        IkePolicy::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find an image by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_image("centos7").expect("Unable to get a image");
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn get_image<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Image> {
+        Image::new(self.session.clone(), id_or_name)
+    }
+
+    /// Find an IPsec policy by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os.get_ipsec_policy("ipsec-default")
+    ///     .expect("Unable to get an IPsec policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_ipsec_policy<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<IpsecPolicy> {
+        IpsecPolicy::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find an IPsec site connection by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let connection = os.get_ipsec_site_connection("to-branch-office")
+    ///     .expect("Unable to get an IPsec site connection");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_ipsec_site_connection<Id: AsRef<str>>(
+        &self,
+        id_or_name: Id,
+    ) -> Result<IpsecSiteConnection> {
+        IpsecSiteConnection::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a key pair by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_keypair("default").expect("Unable to get a key pair");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn get_keypair<Id: AsRef<str>>(&self, name: Id) -> Result<KeyPair> {
+        KeyPair::new(self.session.clone(), name)
+    }
+
+    /// Find a metering label by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let label = os.get_metering_label("egress")
+    ///     .expect("Unable to get a metering label");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_metering_label<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<MeteringLabel> {
+        MeteringLabel::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a metering label rule by its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let rule = os.get_metering_label_rule("5c2b0b8e-54a4-4e07-a8f3-3a3a4a4d1c2e")
+    ///     .expect("Unable to get a metering label rule");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_metering_label_rule<Id: AsRef<str>>(&self, id: Id) -> Result<MeteringLabelRule> {
+        MeteringLabelRule::load(self.session.clone(), id)
+    }
+
+    /// Find an network by its name or ID.
     ///
     /// # Example
     ///
@@ -291,11 +911,794 @@ impl Cloud {
     /// let server = os.get_network("centos7").expect("Unable to get a network");
     /// ```
     #[cfg(feature = "network")]
-    pub fn get_network<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Network> {
-        Network::load(self.session.clone(), id_or_name)
+    pub fn get_network<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Network> {
+        Network::load(self.session.clone(), id_or_name)
+    }
+
+    /// List extensions supported by the Network API.
+    ///
+    /// Only extensions known to this crate are returned. The result is cached,
+    /// so this call is cheap after the first invocation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let extensions = os.network_extensions().expect("Unable to list extensions");
+    /// if extensions.contains(&openstack::network::NetworkExtension::Trunk) {
+    ///     println!("Trunk ports are supported");
+    /// }
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn network_extensions(&self) -> Result<Vec<NetworkExtension>> {
+        network::network_extensions(&self.session)
+    }
+
+    /// Build the network topology of a project.
+    ///
+    /// The result is a graph of servers, ports, subnets, networks, routers
+    /// and floating IPs of the project, which can be exported in the Graphviz
+    /// DOT or JSON formats. Server names are only resolved when the `compute`
+    /// feature is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let topology = os
+    ///     .network_topology("8a4c9b3e")
+    ///     .expect("Unable to build the network topology");
+    /// println!("{}", topology.to_dot());
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn network_topology<P>(&self, project: P) -> Result<NetworkTopology>
+    where
+        P: Into<ProjectRef>,
+    {
+        network::network_topology(self.session.clone(), project.into())
+    }
+
+    /// Find an port by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_port("4d9c1710-fa02-49f9-8218-291024ef4140")
+    ///     .expect("Unable to get a port");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_port<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Port> {
+        Port::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a QoS policy by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os.get_qos_policy("bw-limiter")
+    ///     .expect("Unable to get a QoS policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_qos_policy<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<QosPolicy> {
+        QosPolicy::load(self.session.clone(), id_or_name)
+    }
+
+    /// Get an RBAC policy by its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os.get_rbac_policy("8a0c6a8c-1b5a-4c4e-b4a1-9e5f4a1b9d3c")
+    ///     .expect("Unable to get an RBAC policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_rbac_policy<Id: AsRef<str>>(&self, id: Id) -> Result<RbacPolicy> {
+        RbacPolicy::load(self.session.clone(), id)
+    }
+
+    /// Find a router by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let router = os.get_router("router1").expect("Unable to get a router");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_router<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Router> {
+        Router::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a security group by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let group = os.get_security_group("default")
+    ///     .expect("Unable to get a security group");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_security_group<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<SecurityGroup> {
+        SecurityGroup::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a security group rule by its ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let rule = os.get_security_group_rule("ab4c3f2e-7a24-4c6b-a8a0-3f1b0b4e2a11")
+    ///     .expect("Unable to get a security group rule");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_security_group_rule<Id: AsRef<str>>(&self, id: Id) -> Result<SecurityGroupRule> {
+        SecurityGroupRule::load(self.session.clone(), id)
+    }
+
+    /// Find a network segment by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let segment = os.get_segment("rack1-segment")
+    ///     .expect("Unable to get a segment");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_segment<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Segment> {
+        Segment::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a server by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_server("8a1c355b-2e1e-440a-8aa8-f272df72bc32")
+    ///     .expect("Unable to get a server");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn get_server<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Server> {
+        Server::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find an subnet by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server = os.get_subnet("private-subnet")
+    ///     .expect("Unable to get a subnet");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_subnet<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Subnet> {
+        Subnet::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a subnet pool by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let pool = os.get_subnet_pool("shared-pool")
+    ///     .expect("Unable to get a subnet pool");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_subnet_pool<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<SubnetPool> {
+        SubnetPool::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a trunk by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let trunk = os.get_trunk("trunk1").expect("Unable to get a trunk");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_trunk<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Trunk> {
+        Trunk::load(self.session.clone(), id_or_name)
+    }
+
+    /// Find a VPN service by its name or ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let service = os.get_vpn_service("vpn1")
+    ///     .expect("Unable to get a VPN service");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn get_vpn_service<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<VpnService> {
+        VpnService::load(self.session.clone(), id_or_name)
+    }
+
+    /// List all address scopes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let scope_list = os.list_address_scopes()
+    ///     .expect("Unable to fetch address scopes");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_address_scopes(&self) -> Result<Vec<AddressScope>> {
+        self.find_address_scopes().all()
+    }
+
+    /// List all host aggregates.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let aggregate_list = os.list_aggregates().expect("Unable to fetch aggregates");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_aggregates(&self) -> Result<Vec<Aggregate>> {
+        self.find_aggregates().all()
+    }
+
+    /// List all availability zones.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let az_list = os.list_availability_zones()
+    ///     .expect("Unable to fetch availability zones");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_availability_zones(&self) -> Result<Vec<AvailabilityZone>> {
+        self.find_availability_zones().all()
+    }
+
+    /// List all compute services.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let service_list = os.list_compute_services()
+    ///     .expect("Unable to fetch compute services");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_compute_services(&self) -> Result<Vec<ComputeService>> {
+        self.find_compute_services().all()
+    }
+
+    /// List all flavors.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_flavors](#method.find_flavors) call to limit the number of
+    /// flavors to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_flavors().expect("Unable to fetch flavors");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_flavors(&self) -> Result<Vec<FlavorSummary>> {
+        self.find_flavors().all()
+    }
+
+    /// List all floating IPs
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_floating_ips](#method.find_floating_ips) call to limit the number of
+    /// networks to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_floating_ips().expect("Unable to fetch floating IPs");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_floating_ips(&self) -> Result<Vec<FloatingIp>> {
+        self.find_floating_ips().all()
+    }
+
+    /// List all hypervisors.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_hypervisors](#method.find_hypervisors) call to limit the number of
+    /// hypervisors to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let hypervisor_list = os.list_hypervisors().expect("Unable to fetch hypervisors");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_hypervisors(&self) -> Result<Vec<Hypervisor>> {
+        self.find_hypervisors().all()
+    }
+
+    /// List all images.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_images](#method.find_images) call to limit the number of
+    /// images to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_images().expect("Unable to fetch images");
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn list_images(&self) -> Result<Vec<Image>> {
+        self.find_images().all()
+    }
+
+    /// List all key pairs.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let result = os.list_keypairs().expect("Unable to fetch key pairs");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_keypairs(&self) -> Result<Vec<KeyPair>> {
+        self.find_keypairs().all()
+    }
+
+    /// List all networks.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_networks](#method.find_networks) call to limit the number of
+    /// networks to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_networks().expect("Unable to fetch networks");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_networks(&self) -> Result<Vec<Network>> {
+        self.find_networks().all()
+    }
+
+    /// List all ports.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_ports](#method.find_ports) call to limit the number of
+    /// ports to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_ports().expect("Unable to fetch ports");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_ports(&self) -> Result<Vec<Port>> {
+        self.find_ports().all()
+    }
+
+    /// List all QoS policies.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_qos_policies](#method.find_qos_policies) call to limit the
+    /// number of QoS policies to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy_list = os.list_qos_policies()
+    ///     .expect("Unable to fetch QoS policies");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_qos_policies(&self) -> Result<Vec<QosPolicy>> {
+        self.find_qos_policies().all()
+    }
+
+    /// List all routers.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_routers](#method.find_routers) call to limit the number of
+    /// routers to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let router_list = os.list_routers().expect("Unable to fetch routers");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_routers(&self) -> Result<Vec<Router>> {
+        self.find_routers().all()
+    }
+
+    /// List all security group rules.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_security_group_rules](#method.find_security_group_rules) call
+    /// to limit the number of rules to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let rule_list = os.list_security_group_rules()
+    ///     .expect("Unable to fetch security group rules");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_security_group_rules(&self) -> Result<Vec<SecurityGroupRule>> {
+        self.find_security_group_rules().all()
+    }
+
+    /// List all security groups.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_security_groups](#method.find_security_groups) call to limit
+    /// the number of security groups to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let group_list = os.list_security_groups()
+    ///     .expect("Unable to fetch security groups");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_security_groups(&self) -> Result<Vec<SecurityGroup>> {
+        self.find_security_groups().all()
+    }
+
+    /// List all network segments.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let segment_list = os.list_segments().expect("Unable to fetch segments");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_segments(&self) -> Result<Vec<Segment>> {
+        self.find_segments().all()
+    }
+
+    /// List all servers.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_servers](#method.find_servers) call to limit the number of
+    /// servers to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_servers().expect("Unable to fetch servers");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn list_servers(&self) -> Result<Vec<ServerSummary>> {
+        self.find_servers().all()
+    }
+
+    /// List all subnet pools.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let pool_list = os.list_subnet_pools()
+    ///     .expect("Unable to fetch subnet pools");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_subnet_pools(&self) -> Result<Vec<SubnetPool>> {
+        self.find_subnet_pools().all()
+    }
+
+    /// List all subnets.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_subnets](#method.find_subnets) call to limit the number of
+    /// subnets to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let server_list = os.list_subnets().expect("Unable to fetch subnets");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_subnets(&self) -> Result<Vec<Subnet>> {
+        self.find_subnets().all()
+    }
+
+    /// List all trunks.
+    ///
+    /// This call can yield a lot of results, use the
+    /// [find_trunks](#method.find_trunks) call to limit the number of
+    /// trunks to receive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let trunk_list = os.list_trunks().expect("Unable to fetch trunks");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn list_trunks(&self) -> Result<Vec<Trunk>> {
+        self.find_trunks().all()
+    }
+
+    /// Prepare a new address scope for creation.
+    ///
+    /// This call returns a `NewAddressScope` object, which is a builder to
+    /// populate address scope fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    /// use openstack::network::IpVersion;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let scope = os
+    ///     .new_address_scope("public-v4", IpVersion::V4)
+    ///     .with_shared(true)
+    ///     .create()
+    ///     .expect("Unable to create an address scope");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_address_scope<S>(&self, name: S, ip_version: IpVersion) -> NewAddressScope
+    where
+        S: Into<String>,
+    {
+        NewAddressScope::new(self.session.clone(), name.into(), ip_version)
+    }
+
+    /// Prepare a new host aggregate for creation.
+    ///
+    /// This call returns a `NewAggregate` object, which is a builder to populate
+    /// aggregate fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let mut aggregate = os.new_aggregate("gpu-hosts")
+    ///     .with_availability_zone("gpu")
+    ///     .create().expect("Unable to create an aggregate");
+    /// aggregate.add_host("compute-1").expect("Unable to add a host");
+    /// aggregate.set_metadata("hardware", "gpu").expect("Unable to set metadata");
+    /// ```
+    #[cfg(feature = "compute")]
+    pub fn new_aggregate<S>(&self, name: S) -> NewAggregate
+    where
+        S: Into<String>,
+    {
+        NewAggregate::new(self.session.clone(), name.into())
+    }
+
+    /// Prepare a new VPN endpoint group for creation.
+    ///
+    /// This call returns a `NewEndpointGroup` object, which is a builder
+    /// to populate endpoint group fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    /// use openstack::network::EndpointGroupType;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let group = os
+    ///     .new_endpoint_group(EndpointGroupType::Cidr)
+    ///     .with_name("branch-office")
+    ///     .with_endpoint("10.1.0.0/24")
+    ///     .create()
+    ///     .expect("Unable to create an endpoint group");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_endpoint_group(&self, endpoint_type: EndpointGroupType) -> NewEndpointGroup {
+        NewEndpointGroup::new(self.session.clone(), endpoint_type)
+    }
+
+    /// Prepare a new firewall group for creation.
+    ///
+    /// This call returns a `NewFirewallGroup` object, which is a builder
+    /// to populate firewall group fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let group = os
+    ///     .new_firewall_group()
+    ///     .with_name("web")
+    ///     .with_ingress_firewall_policy("web-ingress")
+    ///     .with_port("router-port")
+    ///     .create()
+    ///     .expect("Unable to create a firewall group");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_firewall_group(&self) -> NewFirewallGroup {
+        NewFirewallGroup::new(self.session.clone())
+    }
+
+    /// Prepare a new firewall policy for creation.
+    ///
+    /// This call returns a `NewFirewallPolicy` object, which is a builder
+    /// to populate firewall policy fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os
+    ///     .new_firewall_policy()
+    ///     .with_name("web-ingress")
+    ///     .with_firewall_rule("allow-http")
+    ///     .create()
+    ///     .expect("Unable to create a firewall policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_firewall_policy(&self) -> NewFirewallPolicy {
+        NewFirewallPolicy::new(self.session.clone())
+    }
+
+    /// Prepare a new firewall rule for creation.
+    ///
+    /// This call returns a `NewFirewallRule` object, which is a builder
+    /// to populate firewall rule fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    /// use openstack::network::FirewallRuleAction;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let rule = os
+    ///     .new_firewall_rule(FirewallRuleAction::Allow)
+    ///     .with_name("allow-http")
+    ///     .with_protocol("tcp")
+    ///     .with_destination_port("80")
+    ///     .create()
+    ///     .expect("Unable to create a firewall rule");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_firewall_rule(&self, action: FirewallRuleAction) -> NewFirewallRule {
+        NewFirewallRule::new(self.session.clone(), action)
+    }
+
+    /// Prepare a new floating IP for creation.
+    ///
+    /// This call returns a `NewFloatingIp` object, which is a builder
+    /// to populate floating IP fields.
+    #[cfg(feature = "network")]
+    pub fn new_floating_ip<N>(&self, floating_network: N) -> NewFloatingIp
+    where
+        N: Into<NetworkRef>,
+    {
+        NewFloatingIp::new(self.session.clone(), floating_network.into())
+    }
+
+    /// Prepare a new IKE policy for creation.
+    ///
+    /// This call returns a `NewIkePolicy` object, which is a builder
+    /// to populate IKE policy fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    /// use openstack::network::IkeVersion;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let policy = os
+    ///     .new_ike_policy()
+    ///     .with_name("ike-v2")
+    ///     .with_ike_version(IkeVersion::V2)
+    ///     .with_encryption_algorithm("aes-256")
+    ///     .create()
+    ///     .expect("Unable to create an IKE policy");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_ike_policy(&self) -> NewIkePolicy {
+        NewIkePolicy::new(self.session.clone())
     }
 
-    /// Find an port by its name or ID.
+    /// Prepare a new IPsec policy for creation.
+    ///
+    /// This call returns a `NewIpsecPolicy` object, which is a builder
+    /// to populate IPsec policy fields.
     ///
     /// # Example
     ///
@@ -303,31 +1706,88 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_port("4d9c1710-fa02-49f9-8218-291024ef4140")
-    ///     .expect("Unable to get a port");
+    /// let policy = os
+    ///     .new_ipsec_policy()
+    ///     .with_name("ipsec-aes256")
+    ///     .with_encryption_algorithm("aes-256")
+    ///     .create()
+    ///     .expect("Unable to create an IPsec policy");
     /// ```
     #[cfg(feature = "network")]
-    pub fn get_port<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Port> {
-        Port::load(self.session.clone(), id_or_name)
+    pub fn new_ipsec_policy(&self) -> NewIpsecPolicy {
+        NewIpsecPolicy::new(self.session.clone())
     }
 
-    /// Find a server by its name or ID.
+    /// Prepare a new IPsec site connection for creation.
+    ///
+    /// This call returns a `NewIpsecSiteConnection` object, which is a builder
+    /// to populate connection fields. Either peer CIDRs or both local and
+    /// peer endpoint groups must be provided.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use openstack;
+    /// extern crate openstack;
+    /// extern crate waiter;
+    ///
+    /// use waiter::Waiter;
     ///
+    /// # fn main() {
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_server("8a1c355b-2e1e-440a-8aa8-f272df72bc32")
-    ///     .expect("Unable to get a server");
+    /// let mut connection = os
+    ///     .new_ipsec_site_connection("vpn1", "ike-v2", "ipsec-aes256", "203.0.113.10", "secret")
+    ///     .with_local_endpoint_group("local-subnets")
+    ///     .with_peer_endpoint_group("branch-office")
+    ///     .create()
+    ///     .expect("Unable to create an IPsec site connection");
+    /// connection
+    ///     .wait_for_active()
+    ///     .wait()
+    ///     .expect("Connection did not become active");
+    /// # }
     /// ```
+    #[cfg(feature = "network")]
+    pub fn new_ipsec_site_connection<S, K, I, A, P>(
+        &self,
+        vpn_service: S,
+        ike_policy: K,
+        ipsec_policy: I,
+        peer_address: A,
+        psk: P,
+    ) -> NewIpsecSiteConnection
+    where
+        S: Into<VpnServiceRef>,
+        K: Into<IkePolicyRef>,
+        I: Into<IpsecPolicyRef>,
+        A: Into<String>,
+        P: Into<String>,
+    {
+        NewIpsecSiteConnection::new(
+            self.session.clone(),
+            vpn_service.into(),
+            ike_policy.into(),
+            ipsec_policy.into(),
+            peer_address.into(),
+            psk.into(),
+        )
+    }
+
+    /// Prepare a new key pair for creation.
+    ///
+    /// This call returns a `NewKeyPair` object, which is a builder to populate
+    /// key pair fields.
     #[cfg(feature = "compute")]
-    pub fn get_server<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Server> {
-        Server::load(self.session.clone(), id_or_name)
+    pub fn new_keypair<S>(&self, name: S) -> NewKeyPair
+    where
+        S: Into<String>,
+    {
+        NewKeyPair::new(self.session.clone(), name.into())
     }
 
-    /// Find an subnet by its name or ID.
+    /// Prepare a new metering label for creation.
+    ///
+    /// This call returns a `NewMeteringLabel` object, which is a builder
+    /// to populate metering label fields.
     ///
     /// # Example
     ///
@@ -335,72 +1795,143 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server = os.get_subnet("private-subnet")
-    ///     .expect("Unable to get a subnet");
+    /// let label = os
+    ///     .new_metering_label("egress")
+    ///     .with_description("Billable egress traffic")
+    ///     .create()
+    ///     .expect("Unable to create a metering label");
     /// ```
     #[cfg(feature = "network")]
-    pub fn get_subnet<Id: AsRef<str>>(&self, id_or_name: Id) -> Result<Subnet> {
-        Subnet::load(self.session.clone(), id_or_name)
+    pub fn new_metering_label<S: Into<String>>(&self, name: S) -> NewMeteringLabel {
+        NewMeteringLabel::new(self.session.clone(), name.into())
     }
 
-    /// List all flavors.
+    /// Prepare a new metering label rule for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_flavors](#method.find_flavors) call to limit the number of
-    /// flavors to receive.
+    /// This call returns a `NewMeteringLabelRule` object, which is a builder
+    /// to populate metering label rule fields.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use openstack;
+    /// use openstack::network::MeteringLabelRuleDirection;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_flavors().expect("Unable to fetch flavors");
+    /// let rule = os
+    ///     .new_metering_label_rule("egress", MeteringLabelRuleDirection::Egress)
+    ///     .with_destination_ip_prefix("0.0.0.0/0".parse().unwrap())
+    ///     .create()
+    ///     .expect("Unable to create a metering label rule");
     /// ```
-    #[cfg(feature = "compute")]
-    pub fn list_flavors(&self) -> Result<Vec<FlavorSummary>> {
-        self.find_flavors().all()
+    #[cfg(feature = "network")]
+    pub fn new_metering_label_rule<L>(
+        &self,
+        metering_label: L,
+        direction: MeteringLabelRuleDirection,
+    ) -> NewMeteringLabelRule
+    where
+        L: Into<MeteringLabelRef>,
+    {
+        NewMeteringLabelRule::new(self.session.clone(), metering_label.into(), direction)
     }
 
-    /// List all floating IPs
+    /// Prepare a new network for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_floating_ips](#method.find_floating_ips) call to limit the number of
-    /// networks to receive.
+    /// This call returns a `NewNetwork` object, which is a builder to populate
+    /// network fields.
+    #[cfg(feature = "network")]
+    pub fn new_network(&self) -> NewNetwork {
+        NewNetwork::new(self.session.clone())
+    }
+
+    /// Prepare a new port for creation.
+    ///
+    /// This call returns a `NewPort` object, which is a builder to populate
+    /// port fields.
+    #[cfg(feature = "network")]
+    pub fn new_port<N>(&self, network: N) -> NewPort
+    where
+        N: Into<NetworkRef>,
+    {
+        NewPort::new(self.session.clone(), network.into())
+    }
+
+    /// Prepare a new QoS policy for creation.
+    ///
+    /// This call returns a `NewQosPolicy` object, which is a builder to
+    /// populate QoS policy fields.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use openstack;
+    /// use openstack::network::QosBandwidthLimitRule;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_floating_ips().expect("Unable to fetch floating IPs");
+    /// let mut policy = os
+    ///     .new_qos_policy("bw-limiter")
+    ///     .create()
+    ///     .expect("Unable to create a QoS policy");
+    /// policy
+    ///     .add_rule(QosBandwidthLimitRule::new(10000))
+    ///     .expect("Unable to add a QoS rule");
     /// ```
     #[cfg(feature = "network")]
-    pub fn list_floating_ips(&self) -> Result<Vec<FloatingIp>> {
-        self.find_floating_ips().all()
+    pub fn new_qos_policy<S>(&self, name: S) -> NewQosPolicy
+    where
+        S: Into<String>,
+    {
+        NewQosPolicy::new(self.session.clone(), name.into())
     }
 
-    /// List all images.
+    /// Prepare a new RBAC policy for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_images](#method.find_images) call to limit the number of
-    /// images to receive.
+    /// This call returns a `NewRbacPolicy` object, which is a builder to
+    /// populate RBAC policy fields. See also `Network::share_with`.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use openstack;
+    /// use openstack::network::{RbacPolicyAction, RbacPolicyObjectType};
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_images().expect("Unable to fetch images");
+    /// let policy = os
+    ///     .new_rbac_policy(
+    ///         RbacPolicyObjectType::QosPolicy,
+    ///         "c9f1f0b8-5a3e-4e4b-9c51-3d5e3f0e6b2a",
+    ///         RbacPolicyAction::AccessAsShared,
+    ///         "2c4d9a8e4e8e4e8e8e8e8e8e8e8e8e8e",
+    ///     )
+    ///     .create()
+    ///     .expect("Unable to create an RBAC policy");
     /// ```
-    #[cfg(feature = "image")]
-    pub fn list_images(&self) -> Result<Vec<Image>> {
-        self.find_images().all()
+    #[cfg(feature = "network")]
+    pub fn new_rbac_policy<S, P>(
+        &self,
+        object_type: RbacPolicyObjectType,
+        object_id: S,
+        action: RbacPolicyAction,
+        target_project: P,
+    ) -> NewRbacPolicy
+    where
+        S: Into<String>,
+        P: Into<ProjectRef>,
+    {
+        NewRbacPolicy::new(
+            self.session.clone(),
+            object_type,
+            object_id.into(),
+            action,
+            target_project.into(),
+        )
     }
 
-    /// List all key pairs.
+    /// Prepare a new router for creation.
+    ///
+    /// This call returns a `NewRouter` object, which is a builder to populate
+    /// router fields.
     ///
     /// # Example
     ///
@@ -408,18 +1939,25 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let result = os.list_keypairs().expect("Unable to fetch key pairs");
+    /// let mut router = os
+    ///     .new_router()
+    ///     .with_name("router1")
+    ///     .with_external_network("public")
+    ///     .create()
+    ///     .expect("Unable to create a router");
+    /// router
+    ///     .add_interface(openstack::network::RouterInterface::Subnet("private-subnet".into()))
+    ///     .expect("Unable to add an interface");
     /// ```
-    #[cfg(feature = "compute")]
-    pub fn list_keypairs(&self) -> Result<Vec<KeyPair>> {
-        self.find_keypairs().all()
+    #[cfg(feature = "network")]
+    pub fn new_router(&self) -> NewRouter {
+        NewRouter::new(self.session.clone())
     }
 
-    /// List all networks.
+    /// Prepare a new security group for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_networks](#method.find_networks) call to limit the number of
-    /// networks to receive.
+    /// This call returns a `NewSecurityGroup` object, which is a builder to
+    /// populate security group fields.
     ///
     /// # Example
     ///
@@ -427,160 +1965,286 @@ impl Cloud {
     /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_networks().expect("Unable to fetch networks");
+    /// let group = os
+    ///     .new_security_group("web")
+    ///     .with_description("Allow HTTP traffic")
+    ///     .create()
+    ///     .expect("Unable to create a security group");
     /// ```
     #[cfg(feature = "network")]
-    pub fn list_networks(&self) -> Result<Vec<Network>> {
-        self.find_networks().all()
+    pub fn new_security_group<S>(&self, name: S) -> NewSecurityGroup
+    where
+        S: Into<String>,
+    {
+        NewSecurityGroup::new(self.session.clone(), name.into())
     }
 
-    /// List all ports.
+    /// Prepare a new security group rule for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_ports](#method.find_ports) call to limit the number of
-    /// ports to receive.
+    /// This call returns a `NewSecurityGroupRule` object, which is a builder
+    /// to populate security group rule fields.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use openstack;
+    /// use openstack::network::SecurityGroupRuleDirection;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_ports().expect("Unable to fetch ports");
+    /// let rule = os
+    ///     .new_security_group_rule("web", SecurityGroupRuleDirection::Ingress)
+    ///     .with_protocol("tcp")
+    ///     .with_port(80)
+    ///     .with_remote_ip_prefix("0.0.0.0/0".parse().unwrap())
+    ///     .create()
+    ///     .expect("Unable to create a security group rule");
     /// ```
     #[cfg(feature = "network")]
-    pub fn list_ports(&self) -> Result<Vec<Port>> {
-        self.find_ports().all()
+    pub fn new_security_group_rule<G>(
+        &self,
+        security_group: G,
+        direction: SecurityGroupRuleDirection,
+    ) -> NewSecurityGroupRule
+    where
+        G: Into<SecurityGroupRef>,
+    {
+        NewSecurityGroupRule::new(self.session.clone(), security_group.into(), direction)
     }
 
-    /// List all servers.
+    /// Prepare a new network segment for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_servers](#method.find_servers) call to limit the number of
-    /// servers to receive.
+    /// This call returns a `NewSegment` object, which is a builder to
+    /// populate segment fields.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// use openstack;
+    /// use openstack::network::NetworkType;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_servers().expect("Unable to fetch servers");
+    /// let segment = os
+    ///     .new_segment("routed-net", NetworkType::Vlan)
+    ///     .with_physical_network("rack1")
+    ///     .with_segmentation_id(2016)
+    ///     .create()
+    ///     .expect("Unable to create a segment");
     /// ```
+    #[cfg(feature = "network")]
+    pub fn new_segment<N, S>(&self, network: N, network_type: S) -> NewSegment
+    where
+        N: Into<NetworkRef>,
+        S: Into<NetworkType>,
+    {
+        NewSegment::new(self.session.clone(), network.into(), network_type.into())
+    }
+
+    /// Prepare a new server for creation.
+    ///
+    /// This call returns a `NewServer` object, which is a builder to populate
+    /// server fields.
     #[cfg(feature = "compute")]
-    pub fn list_servers(&self) -> Result<Vec<ServerSummary>> {
-        self.find_servers().all()
+    pub fn new_server<S, F>(&self, name: S, flavor: F) -> NewServer
+    where
+        S: Into<String>,
+        F: Into<FlavorRef>,
+    {
+        NewServer::new(self.session.clone(), name.into(), flavor.into())
     }
 
-    /// List all subnets.
+    /// Prepare a new subnet for creation.
     ///
-    /// This call can yield a lot of results, use the
-    /// [find_subnets](#method.find_subnets) call to limit the number of
-    /// subnets to receive.
+    /// This call returns a `NewSubnet` object, which is a builder to populate
+    /// subnet fields.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use openstack;
+    /// extern crate ipnet;
+    /// extern crate openstack;
+    /// use std::net;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let server_list = os.list_subnets().expect("Unable to fetch subnets");
+    /// let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
+    ///     .unwrap().into();
+    /// let new_subnet = os.new_subnet("private-net", cidr)
+    ///     .with_name("private-subnet")
+    ///     .create().expect("Unable to create subnet");
     /// ```
     #[cfg(feature = "network")]
-    pub fn list_subnets(&self) -> Result<Vec<Subnet>> {
-        self.find_subnets().all()
+    pub fn new_subnet<N>(&self, network: N, cidr: ipnet::IpNet) -> NewSubnet
+    where
+        N: Into<NetworkRef>,
+    {
+        NewSubnet::new(self.session.clone(), network.into(), cidr)
     }
 
-    /// Prepare a new floating IP for creation.
+    /// Prepare a new subnet allocated from a subnet pool for creation.
     ///
-    /// This call returns a `NewFloatingIp` object, which is a builder
-    /// to populate floating IP fields.
+    /// This call returns a `NewSubnet` object, which is a builder to populate
+    /// subnet fields. Unless a prefix length is provided, the default one of
+    /// the subnet pool is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let new_subnet = os.new_subnet_from_pool("private-net", "shared-pool")
+    ///     .with_prefix_length(26)
+    ///     .create().expect("Unable to create subnet");
+    /// ```
     #[cfg(feature = "network")]
-    pub fn new_floating_ip<N>(&self, floating_network: N) -> NewFloatingIp
+    pub fn new_subnet_from_pool<N, P>(&self, network: N, subnet_pool: P) -> NewSubnet
     where
         N: Into<NetworkRef>,
+        P: Into<SubnetPoolRef>,
     {
-        NewFloatingIp::new(self.session.clone(), floating_network.into())
+        NewSubnet::new_from_pool(self.session.clone(), network.into(), subnet_pool.into())
     }
 
-    /// Prepare a new key pair for creation.
+    /// Prepare a new subnet pool for creation.
     ///
-    /// This call returns a `NewKeyPair` object, which is a builder to populate
-    /// key pair fields.
-    #[cfg(feature = "compute")]
-    pub fn new_keypair<S>(&self, name: S) -> NewKeyPair
+    /// This call returns a `NewSubnetPool` object, which is a builder to
+    /// populate subnet pool fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// extern crate ipnet;
+    /// extern crate openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let pool = os
+    ///     .new_subnet_pool("shared-pool")
+    ///     .with_prefix("10.0.0.0/16".parse().unwrap())
+    ///     .with_default_prefix_length(24)
+    ///     .create()
+    ///     .expect("Unable to create a subnet pool");
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn new_subnet_pool<S>(&self, name: S) -> NewSubnetPool
     where
         S: Into<String>,
     {
-        NewKeyPair::new(self.session.clone(), name.into())
+        NewSubnetPool::new(self.session.clone(), name.into())
     }
 
-    /// Prepare a new network for creation.
+    /// Prepare a new trunk for creation.
     ///
-    /// This call returns a `NewNetwork` object, which is a builder to populate
-    /// network fields.
+    /// This call returns a `NewTrunk` object, which is a builder to populate
+    /// trunk fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    /// use openstack::network::SegmentationType;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let trunk = os
+    ///     .new_trunk("parent-port")
+    ///     .with_name("trunk1")
+    ///     .with_sub_port("vlan100-port", SegmentationType::Vlan, 100)
+    ///     .create()
+    ///     .expect("Unable to create a trunk");
+    /// ```
     #[cfg(feature = "network")]
-    pub fn new_network(&self) -> NewNetwork {
-        NewNetwork::new(self.session.clone())
+    pub fn new_trunk<P>(&self, port: P) -> NewTrunk
+    where
+        P: Into<PortRef>,
+    {
+        NewTrunk::new(self.session.clone(), port.into())
     }
 
-    /// Prepare a new port for creation.
+    /// Prepare a new VPN service for creation.
     ///
-    /// This call returns a `NewPort` object, which is a builder to populate
-    /// port fields.
+    /// This call returns a `NewVpnService` object, which is a builder
+    /// to populate VPN service fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use openstack;
+    ///
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let service = os
+    ///     .new_vpn_service("router1")
+    ///     .with_name("vpn1")
+    ///     .create()
+    ///     .expect("Unable to create a VPN service");
+    /// ```
     #[cfg(feature = "network")]
-    pub fn new_port<N>(&self, network: N) -> NewPort
+    pub fn new_vpn_service<R>(&self, router: R) -> NewVpnService
     where
-        N: Into<NetworkRef>,
+        R: Into<RouterRef>,
     {
-        NewPort::new(self.session.clone(), network.into())
+        NewVpnService::new(self.session.clone(), router.into())
     }
 
-    /// Prepare a new server for creation.
+    /// Get compute usage of a project over a period of time.
     ///
-    /// This call returns a `NewServer` object, which is a builder to populate
-    /// server fields.
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// extern crate chrono;
+    /// extern crate openstack;
+    ///
+    /// use chrono::{Duration, Utc};
+    ///
+    /// # fn main() {
+    /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+    /// let end = Utc::now();
+    /// let usage = os
+    ///     .project_usage("8a4c9b3e", end - Duration::days(30), end)
+    ///     .expect("Unable to get usage");
+    /// for server in usage.server_usages() {
+    ///     println!("{}: {} VCPU-hours", server.name(), server.vcpu_hours());
+    /// }
+    /// # }
+    /// ```
     #[cfg(feature = "compute")]
-    pub fn new_server<S, F>(&self, name: S, flavor: F) -> NewServer
+    pub fn project_usage<P, Tz>(
+        &self,
+        project: P,
+        start: DateTime<Tz>,
+        end: DateTime<Tz>,
+    ) -> Result<ProjectUsage>
     where
-        S: Into<String>,
-        F: Into<FlavorRef>,
+        P: Into<ProjectRef>,
+        Tz: TimeZone,
     {
-        NewServer::new(self.session.clone(), name.into(), flavor.into())
+        compute::project_usage(&self.session, project.into(), &start, &end)
     }
 
-    /// Prepare a new subnet for creation.
+    /// Check that the auto-allocated topology can be created for a project.
     ///
-    /// This call returns a `NewSubnet` object, which is a builder to populate
-    /// subnet fields.
+    /// Fails if the prerequisites (e.g. a default external network or
+    /// a default subnet pool) are missing.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// extern crate ipnet;
-    /// extern crate openstack;
-    /// use std::net;
+    /// use openstack;
     ///
     /// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
-    /// let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
-    ///     .unwrap().into();
-    /// let new_subnet = os.new_subnet("private-net", cidr)
-    ///     .with_name("private-subnet")
-    ///     .create().expect("Unable to create subnet");
+    /// os.validate_auto_allocated_topology("8a4c9b3e")
+    ///     .expect("Auto-allocation is not possible");
     /// ```
     #[cfg(feature = "network")]
-    pub fn new_subnet<N>(&self, network: N, cidr: ipnet::IpNet) -> NewSubnet
+    pub fn validate_auto_allocated_topology<P>(&self, project: P) -> Result<()>
     where
-        N: Into<NetworkRef>,
+        P: Into<ProjectRef>,
     {
-        NewSubnet::new(self.session.clone(), network.into(), cidr)
+        network::validate_auto_allocated_topology(&self.session, project.into())
     }
 }
 
-impl From<Session> for Cloud {
-    fn from(value: Session) -> Cloud {
+impl From<osauth::Session> for Cloud {
+    fn from(value: osauth::Session) -> Cloud {
         Cloud {
-            session: Rc::new(SyncSession::new(value)),
+            session: Rc::new(value.into()),
         }
     }
 }
@@ -588,7 +2252,7 @@ impl From<Session> for Cloud {
 impl From<SyncSession> for Cloud {
     fn from(value: SyncSession) -> Cloud {
         Cloud {
-            session: Rc::new(value),
+            session: Rc::new(value.into()),
         }
     }
 }
@@ -14,17 +14,23 @@
 
 //! Types and traits shared by all API parts.
 
+mod partial;
 pub(crate) mod protocol;
 mod resourceiterator;
+mod tags;
 mod types;
 mod waiter;
 
 pub use osauth::ApiVersion;
 
+pub use self::partial::{PartialQuery, PartialResource, ProjectableQuery};
 pub use self::resourceiterator::{ResourceIterator, ResourceQuery};
+pub use self::tags::Taggable;
 pub(crate) use self::types::IntoVerified;
 pub use self::types::{
-    FlavorRef, ImageRef, KeyPairRef, NetworkRef, PortRef, ProjectRef, Refresh, RouterRef,
-    SnapshotRef, SubnetRef, UserRef, VolumeRef,
+    AddressScopeRef, EndpointGroupRef, FirewallPolicyRef, FirewallRuleRef, FlavorRef, IkePolicyRef,
+    ImageRef, IpsecPolicyRef, KeyPairRef, MeteringLabelRef, NetworkRef, PortRef, ProjectRef,
+    QosPolicyRef, Refresh, RouterRef, SecurityGroupRef, SegmentRef, SnapshotRef, SubnetPoolRef,
+    SubnetRef, TrunkRef, UserRef, VolumeRef, VpnServiceRef,
 };
 pub use self::waiter::DeletionWaiter;
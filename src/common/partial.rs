@@ -0,0 +1,233 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Queries returning only some fields of resources.
+
+use std::collections::HashMap;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::super::{Error, Result};
+use super::{ResourceIterator, ResourceQuery};
+
+/// A query that can return only some fields of resources.
+///
+/// This is a low-level trait that should not be used directly.
+pub trait ProjectableQuery: ResourceQuery {
+    /// Get a chunk of resources with only the given fields.
+    fn fetch_partial_chunk(
+        &self,
+        fields: &[String],
+        limit: Option<usize>,
+        marker: Option<String>,
+    ) -> Result<Vec<PartialResource>>;
+}
+
+/// A partial view of a resource, containing only the requested fields.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PartialResource {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(flatten)]
+    fields: HashMap<String, Value>,
+}
+
+/// A query returning partial views of resources.
+///
+/// Created by calling `with_fields` on a query.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use openstack;
+///
+/// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+/// let ports = os
+///     .find_ports()
+///     .with_fields(&["name", "status"])
+///     .all()
+///     .expect("Unable to list ports");
+/// for port in ports {
+///     println!("{} {:?}", port.id(), port.field("status"));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PartialQuery<Q> {
+    inner: Q,
+    fields: Vec<String>,
+    limit: Option<usize>,
+}
+
+impl PartialResource {
+    /// Unique ID.
+    #[inline]
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Resource name (if requested and available).
+    #[inline]
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Value of a requested field other than `id` and `name`.
+    #[inline]
+    pub fn field<S: AsRef<str>>(&self, name: S) -> Option<&Value> {
+        self.fields.get(name.as_ref())
+    }
+
+    /// All requested fields other than `id` and `name`.
+    #[inline]
+    pub fn fields(&self) -> &HashMap<String, Value> {
+        &self.fields
+    }
+
+    /// Drop fields that were not requested.
+    ///
+    /// Used for services that do not support projection on the server side.
+    #[allow(dead_code)] // unused without the image feature
+    pub(crate) fn retain_fields(&mut self, fields: &[String]) {
+        if !fields.iter().any(|field| field == "name") {
+            self.name = None;
+        }
+        self.fields.retain(|key, _| fields.contains(key));
+    }
+}
+
+impl<Q> PartialQuery<Q>
+where
+    Q: ProjectableQuery,
+{
+    #[allow(dead_code)] // unused with --no-default-features
+    pub(crate) fn new<S: AsRef<str>>(inner: Q, fields: &[S]) -> PartialQuery<Q> {
+        let mut fields: Vec<String> = fields.iter().map(|x| String::from(x.as_ref())).collect();
+        // The ID is required for pagination.
+        if !fields.iter().any(|field| field == "id") {
+            fields.insert(0, String::from("id"));
+        }
+        PartialQuery {
+            inner,
+            fields,
+            limit: None,
+        }
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<PartialQuery<Q>> {
+        debug!("Fetching partial resources with fields {:?}", self.fields);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<PartialResource>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<PartialResource> {
+        debug!(
+            "Fetching one partial resource with fields {:?}",
+            self.fields
+        );
+        if self.inner.can_paginate()? {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.limit = Some(2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl<Q> ResourceQuery for PartialQuery<Q>
+where
+    Q: ProjectableQuery,
+{
+    type Item = PartialResource;
+
+    const DEFAULT_LIMIT: usize = Q::DEFAULT_LIMIT;
+
+    fn can_paginate(&self) -> Result<bool> {
+        if self.limit.is_some() {
+            Ok(false)
+        } else {
+            self.inner.can_paginate()
+        }
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id.clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        self.inner
+            .fetch_partial_chunk(&self.fields, limit.or(self.limit), marker)
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        self.inner.validate()
+    }
+}
+
+impl<Q> IntoFallibleIterator for PartialQuery<Q>
+where
+    Q: ProjectableQuery,
+{
+    type Item = PartialResource;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<PartialQuery<Q>>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json;
+
+    use super::PartialResource;
+
+    #[test]
+    fn test_partial_resource() {
+        let mut resource: PartialResource = serde_json::from_str(
+            r#"{"id": "abcd", "name": "port", "status": "ACTIVE", "mac_address": "fa:16:3e"}"#,
+        )
+        .unwrap();
+        assert_eq!(resource.id(), "abcd");
+        assert_eq!(resource.name().as_ref().unwrap(), "port");
+        assert_eq!(resource.field("status").unwrap(), "ACTIVE");
+        assert!(resource.field("id").is_none());
+
+        resource.retain_fields(&["id".to_string(), "status".to_string()]);
+        assert!(resource.name().is_none());
+        assert_eq!(resource.fields().len(), 1);
+    }
+}
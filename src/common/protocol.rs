@@ -19,10 +19,12 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone};
 use eui48::MacAddress;
 use reqwest::Url;
 use serde::de::Error as DeserError;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct KeyValue {
@@ -51,6 +53,68 @@ where
     Ok(value.into_iter().map(|kv| (kv.key, kv.value)).collect())
 }
 
+/// Deserialize a value, treating `null` as the default.
+pub fn deser_null_as_default<'de, D, T>(des: D) -> ::std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    let value: Option<T> = Deserialize::deserialize(des)?;
+    Ok(value.unwrap_or_default())
+}
+
+/// Deserialize an ID that may be either a string or an integer.
+pub fn deser_string_or_number<'de, D>(des: D) -> ::std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Value::deserialize(des)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(DeserError::custom(format!(
+            "Expected a string or a number, got {}",
+            other
+        ))),
+    }
+}
+
+fn parse_datetime_assume_utc(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value).ok().or_else(|| {
+        value
+            .parse::<NaiveDateTime>()
+            .ok()
+            .map(|naive| FixedOffset::east_opt(0).unwrap().from_utc_datetime(&naive))
+    })
+}
+
+/// Deserialize a date and time, assuming UTC if the time zone is missing.
+pub fn deser_datetime_assume_utc<'de, D>(
+    des: D,
+) -> ::std::result::Result<DateTime<FixedOffset>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: String = Deserialize::deserialize(des)?;
+    parse_datetime_assume_utc(&value)
+        .ok_or_else(|| DeserError::custom(format!("Invalid date and time: {}", value)))
+}
+
+/// Deserialize an optional date and time, assuming UTC if the time zone is missing.
+pub fn deser_opt_datetime_assume_utc<'de, D>(
+    des: D,
+) -> ::std::result::Result<Option<DateTime<FixedOffset>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Deserialize::deserialize(des)?;
+    match value {
+        Some(ref s) if !s.is_empty() => parse_datetime_assume_utc(s)
+            .map(Some)
+            .ok_or_else(|| DeserError::custom(format!("Invalid date and time: {}", s))),
+        _ => Ok(None),
+    }
+}
+
 /// Serialize a MAC address in its HEX format.
 #[allow(clippy::trivially_copy_pass_by_ref)]
 pub fn ser_mac<S>(value: &MacAddress, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
@@ -71,3 +135,44 @@ where
 {
     value.map(|m| m.to_hex_string()).serialize(serializer)
 }
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+    use serde_json;
+
+    use chrono::{DateTime, FixedOffset};
+
+    #[derive(Debug, Deserialize)]
+    struct Test {
+        #[serde(deserialize_with = "super::deser_string_or_number")]
+        id: String,
+        #[serde(deserialize_with = "super::deser_opt_datetime_assume_utc", default)]
+        updated_at: Option<DateTime<FixedOffset>>,
+    }
+
+    #[test]
+    fn test_deser_string_or_number() {
+        let t: Test = serde_json::from_str(r#"{"id": 42}"#).unwrap();
+        assert_eq!(t.id, "42");
+        let t: Test = serde_json::from_str(r#"{"id": "abcd"}"#).unwrap();
+        assert_eq!(t.id, "abcd");
+        assert!(serde_json::from_str::<Test>(r#"{"id": null}"#).is_err());
+    }
+
+    #[test]
+    fn test_deser_datetime_assume_utc() {
+        let t: Test =
+            serde_json::from_str(r#"{"id": 1, "updated_at": "2012-10-29T13:42:02.000000"}"#)
+                .unwrap();
+        let expected = DateTime::parse_from_rfc3339("2012-10-29T13:42:02Z").unwrap();
+        assert_eq!(t.updated_at, Some(expected));
+        let t: Test =
+            serde_json::from_str(r#"{"id": 1, "updated_at": "2012-10-29T15:42:02+02:00"}"#)
+                .unwrap();
+        assert_eq!(t.updated_at, Some(expected));
+        let t: Test = serde_json::from_str(r#"{"id": 1, "updated_at": null}"#).unwrap();
+        assert!(t.updated_at.is_none());
+        assert!(serde_json::from_str::<Test>(r#"{"id": 1, "updated_at": "yesterday"}"#).is_err());
+    }
+}
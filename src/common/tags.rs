@@ -0,0 +1,66 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tags management.
+
+use super::super::Result;
+
+/// A resource that supports tags.
+///
+/// Implemented by servers (compute API version 2.26 or newer is required) and by floating IPs,
+/// networks, ports, routers and subnets.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use openstack;
+/// use openstack::common::Taggable;
+///
+/// let os = openstack::Cloud::from_env().expect("Unable to authenticate");
+/// let mut network = os.get_network("private-net").expect("Unable to get a network");
+/// network.add_tag("production").expect("Unable to add a tag");
+/// let tagged = os
+///     .find_networks()
+///     .with_tags(vec!["production"])
+///     .all()
+///     .expect("Unable to fetch networks");
+/// ```
+pub trait Taggable {
+    /// Tags of the resource.
+    ///
+    /// Use [refresh_tags](#tymethod.refresh_tags) to fetch the current list.
+    fn tags(&self) -> &Vec<String>;
+
+    /// Add a tag to the resource.
+    ///
+    /// Adding a tag that already exists is not an error.
+    fn add_tag<S: Into<String>>(&mut self, tag: S) -> Result<()>;
+
+    /// Remove all tags from the resource.
+    fn clear_tags(&mut self) -> Result<()>;
+
+    /// Fetch the current tags of the resource.
+    fn refresh_tags(&mut self) -> Result<()>;
+
+    /// Remove a tag from the resource.
+    ///
+    /// Fails with `ResourceNotFound` if the tag does not exist.
+    fn remove_tag<S: AsRef<str>>(&mut self, tag: S) -> Result<()>;
+
+    /// Replace all tags of the resource.
+    fn replace_tags<I>(&mut self, tags: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>;
+}
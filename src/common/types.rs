@@ -14,7 +14,7 @@
 
 //! Types and traits shared between services.
 
-use super::super::session::Session;
+use super::super::utils::Session;
 use super::super::Result;
 
 /// Trait representing something that can be refreshed.
@@ -115,7 +115,7 @@ macro_rules! opaque_resource_type {
         #[cfg(not(feature = $service))]
         #[allow(dead_code)]
         impl $crate::common::IntoVerified for $name {
-            fn into_verified(self, _session: &$crate::session::Session)
+            fn into_verified(self, _session: &$crate::utils::Session)
                     -> $crate::Result<$name> {
                 Ok(self)
             }
@@ -123,26 +123,52 @@ macro_rules! opaque_resource_type {
     )
 }
 
+opaque_resource_type!(#[doc = "An ID of an `AddressScope`"] AddressScopeRef ? "network");
+
+opaque_resource_type!(#[doc = "An ID of an `EndpointGroup`"] EndpointGroupRef ? "network");
+
+opaque_resource_type!(#[doc = "An ID of a `FirewallPolicy`"] FirewallPolicyRef ? "network");
+
+opaque_resource_type!(#[doc = "An ID of a `FirewallRule`"] FirewallRuleRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `Flavor`"] FlavorRef ? "compute");
 
+opaque_resource_type!(#[doc = "An ID of an `IkePolicy`"] IkePolicyRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of an `Image`"] ImageRef ? "image");
 
+opaque_resource_type!(#[doc = "An ID of an `IpsecPolicy`"] IpsecPolicyRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `KeyPair`"] KeyPairRef ? "compute");
 
+opaque_resource_type!(#[doc = "An ID of a `MeteringLabel`"] MeteringLabelRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `Network`"] NetworkRef ? "network");
 
 opaque_resource_type!(#[doc = "An ID of a `Project`"] ProjectRef ? "identity");
 
 opaque_resource_type!(#[doc = "An ID of a `Port`"] PortRef ? "network");
 
+opaque_resource_type!(#[doc = "An ID of a `QosPolicy`"] QosPolicyRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `Router`"] RouterRef ? "network");
 
+opaque_resource_type!(#[doc = "An ID of a `SecurityGroup`"] SecurityGroupRef ? "network");
+
+opaque_resource_type!(#[doc = "An ID of a `Segment`"] SegmentRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `Snapshot`"] SnapshotRef ? "volume");
 
 opaque_resource_type!(#[doc = "An ID of a `Subnet`"] SubnetRef ? "network");
 
+opaque_resource_type!(#[doc = "An ID of a `SubnetPool`"] SubnetPoolRef ? "network");
+
+opaque_resource_type!(#[doc = "An ID of a `Trunk`"] TrunkRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `User`"] UserRef ? "identity");
 
+opaque_resource_type!(#[doc = "An ID of a `VpnService`"] VpnServiceRef ? "network");
+
 opaque_resource_type!(#[doc = "An ID of a `Volume`"] VolumeRef ? "volume");
 
 #[cfg(test)]
@@ -0,0 +1,396 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Host aggregates management via Compute API.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{ImageRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery};
+use super::super::utils::Session;
+use super::super::{Error, Result};
+use super::{api, protocol};
+
+/// Structure representing a host aggregate.
+#[derive(Clone, Debug)]
+pub struct Aggregate {
+    session: Rc<Session>,
+    inner: protocol::Aggregate,
+    dirty: HashSet<&'static str>,
+}
+
+/// A query to aggregate list.
+#[derive(Clone, Debug)]
+pub struct AggregateQuery {
+    session: Rc<Session>,
+}
+
+/// A request to create an aggregate.
+#[derive(Clone, Debug)]
+pub struct NewAggregate {
+    session: Rc<Session>,
+    inner: protocol::AggregateCreate,
+}
+
+impl Aggregate {
+    /// Create an aggregate object.
+    fn new(session: Rc<Session>, inner: protocol::Aggregate) -> Aggregate {
+        Aggregate {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an Aggregate object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Aggregate> {
+        let inner = api::get_aggregate(&session, id)?;
+        Ok(Aggregate::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Availability zone of the aggregate (if any)."]
+        availability_zone: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the availability zone."]
+        set_availability_zone, with_availability_zone -> availability_zone: optional String
+    }
+
+    /// Remove the aggregate from its availability zone.
+    #[allow(unused_results)]
+    pub fn clear_availability_zone(&mut self) {
+        self.inner.availability_zone = None;
+        self.dirty.insert("availability_zone");
+    }
+
+    transparent_property! {
+        #[doc = "Creation date and time."]
+        created_at: DateTime<FixedOffset>
+    }
+
+    transparent_property! {
+        #[doc = "Hosts in the aggregate."]
+        hosts: ref Vec<String>
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Metadata of the aggregate."]
+        metadata: ref HashMap<String, String>
+    }
+
+    transparent_property! {
+        #[doc = "Aggregate name."]
+        name: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name
+    }
+
+    transparent_property! {
+        #[doc = "Last update date and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "UUID of the aggregate (requires API version 2.41)."]
+        uuid: ref Option<String>
+    }
+
+    /// Add a host to the aggregate.
+    pub fn add_host<S: Into<String>>(&mut self, host: S) -> Result<()> {
+        self.run_action(protocol::AggregateAction::AddHost { host: host.into() })
+    }
+
+    /// Request caching the given images on all hosts of the aggregate.
+    ///
+    /// Requires API version 2.81.
+    pub fn cache_images<I>(&self, images: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<ImageRef>,
+    {
+        let mut ids = Vec::new();
+        for image in images {
+            ids.push(image.into().into_verified(&self.session)?.into());
+        }
+        api::cache_images_on_aggregate(&self.session, &self.inner.id, ids)
+    }
+
+    /// Delete the aggregate.
+    pub fn delete(self) -> Result<()> {
+        api::delete_aggregate(&self.session, &self.inner.id)
+    }
+
+    /// Whether the aggregate is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Remove a host from the aggregate.
+    pub fn remove_host<S: Into<String>>(&mut self, host: S) -> Result<()> {
+        self.run_action(protocol::AggregateAction::RemoveHost { host: host.into() })
+    }
+
+    /// Save the changes to the aggregate.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::AggregateUpdate::default();
+        save_fields! {
+            self -> update: availability_zone name
+        };
+        let inner = api::update_aggregate(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Set a metadata item on the aggregate.
+    pub fn set_metadata<K, V>(&mut self, key: K, value: V) -> Result<()>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.update_metadata(Some((key.into(), Some(value.into()))))
+    }
+
+    /// Remove a metadata item from the aggregate.
+    pub fn unset_metadata<K: Into<String>>(&mut self, key: K) -> Result<()> {
+        self.update_metadata(Some((key.into(), None)))
+    }
+
+    /// Update several metadata items at once.
+    ///
+    /// Items with `None` values are removed from the metadata.
+    pub fn update_metadata<I>(&mut self, metadata: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (String, Option<String>)>,
+    {
+        self.run_action(protocol::AggregateAction::SetMetadata {
+            metadata: metadata.into_iter().collect(),
+        })
+    }
+
+    fn run_action(&mut self, action: protocol::AggregateAction) -> Result<()> {
+        let mut inner = api::aggregate_action(&self.session, &self.inner.id, action)?;
+        // NOTE: actions do not touch the updatable fields, keep local changes.
+        if self.dirty.contains("availability_zone") {
+            inner.availability_zone = self.inner.availability_zone.take();
+        }
+        if self.dirty.contains("name") {
+            inner.name = self.inner.name.clone();
+        }
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for Aggregate {
+    /// Refresh the aggregate.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_aggregate_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl AggregateQuery {
+    pub(crate) fn new(session: Rc<Session>) -> AggregateQuery {
+        AggregateQuery { session }
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<AggregateQuery> {
+        debug!("Fetching aggregates");
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Aggregate>> {
+        self.into_iter().collect()
+    }
+}
+
+impl ResourceQuery for AggregateQuery {
+    type Item = Aggregate;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        // The aggregates API does not support pagination.
+        Ok(false)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(
+        &self,
+        _limit: Option<usize>,
+        _marker: Option<String>,
+    ) -> Result<Vec<Self::Item>> {
+        Ok(api::list_aggregates(&self.session)?
+            .into_iter()
+            .map(|item| Aggregate::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+impl NewAggregate {
+    /// Start creating an aggregate.
+    pub(crate) fn new(session: Rc<Session>, name: String) -> NewAggregate {
+        NewAggregate {
+            session,
+            inner: protocol::AggregateCreate {
+                availability_zone: None,
+                name,
+            },
+        }
+    }
+
+    /// Request creation of an aggregate.
+    pub fn create(self) -> Result<Aggregate> {
+        let inner = api::create_aggregate(&self.session, self.inner)?;
+        Ok(Aggregate::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set availability zone of the aggregate."]
+        set_availability_zone, with_availability_zone -> availability_zone: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set name of the aggregate."]
+        set_name, with_name -> name
+    }
+}
+
+impl IntoFallibleIterator for AggregateQuery {
+    type Item = Aggregate;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<AggregateQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::utils::test::mock_session;
+    use super::super::super::ErrorKind;
+    use super::Aggregate;
+
+    fn aggregate(id: u64, name: &str, uuid: &str) -> Value {
+        json!({
+            "availability_zone": null,
+            "created_at": "2019-10-01T12:00:00.000000",
+            "hosts": [],
+            "id": id,
+            "metadata": {},
+            "name": name,
+            "updated_at": null,
+            "uuid": uuid,
+        })
+    }
+
+    fn aggregates_handler(path: &str) -> (u16, Value) {
+        match path {
+            "/v2.1/os-aggregates" => (
+                200,
+                json!({ "aggregates": [
+                    aggregate(1, "first", "3a6b0f4e-11e7-4b6f-8ab0-2c8a3d1c0f01"),
+                    aggregate(2, "42", "3a6b0f4e-11e7-4b6f-8ab0-2c8a3d1c0f02"),
+                ] }),
+            ),
+            "/v2.1/os-aggregates/1" => (
+                200,
+                json!({ "aggregate": aggregate(1, "first", "3a6b0f4e-11e7-4b6f-8ab0-2c8a3d1c0f01") }),
+            ),
+            _ => (404, Value::Null),
+        }
+    }
+
+    #[test]
+    fn test_get_aggregate_by_numeric_id() {
+        let (session, requests) = mock_session("2.41", |r| aggregates_handler(&r.path));
+        let aggregate = Aggregate::load(Rc::new(session), "1").unwrap();
+        assert_eq!(aggregate.id(), "1");
+        assert_eq!(aggregate.name(), "first");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/v2.1/os-aggregates/1");
+    }
+
+    #[test]
+    fn test_get_aggregate_numeric_name_fallback() {
+        let (session, requests) = mock_session("2.41", |r| aggregates_handler(&r.path));
+        let aggregate = Aggregate::load(Rc::new(session), "42").unwrap();
+        assert_eq!(aggregate.id(), "2");
+        assert_eq!(aggregate.name(), "42");
+
+        let paths: Vec<_> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.path.clone())
+            .collect();
+        assert_eq!(paths, vec!["/v2.1/os-aggregates/42", "/v2.1/os-aggregates"]);
+    }
+
+    #[test]
+    fn test_get_aggregate_by_name_or_uuid() {
+        let (session, requests) = mock_session("2.41", |r| aggregates_handler(&r.path));
+        let session = Rc::new(session);
+        let aggregate = Aggregate::load(session.clone(), "first").unwrap();
+        assert_eq!(aggregate.id(), "1");
+        let aggregate =
+            Aggregate::load(session.clone(), "3a6b0f4e-11e7-4b6f-8ab0-2c8a3d1c0f02").unwrap();
+        assert_eq!(aggregate.name(), "42");
+        let err = Aggregate::load(session, "missing").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResourceNotFound);
+
+        // Non-numeric identifiers are never fetched directly.
+        assert!(requests
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|r| r.path == "/v2.1/os-aggregates"));
+    }
+}
@@ -19,20 +19,28 @@ use std::fmt::Debug;
 
 use osauth::services::COMPUTE;
 use osproto::common::{IdAndName, Ref};
+use reqwest::Url;
 use serde::Serialize;
 use serde_json;
 
 use super::super::common::ApiVersion;
-use super::super::session::Session;
-use super::super::utils::{self, ResultExt};
-use super::super::Result;
+use super::super::utils::{self, Query, ResultExt, Session};
+use super::super::{Error, ErrorKind, Result};
 use super::protocol::*;
 
 const API_VERSION_KEYPAIR_TYPE: ApiVersion = ApiVersion(2, 2);
+const API_VERSION_SERVICE_FORCE_DOWN: ApiVersion = ApiVersion(2, 11);
 const API_VERSION_SERVER_DESCRIPTION: ApiVersion = ApiVersion(2, 19);
+const API_VERSION_SERVER_TAGS: ApiVersion = ApiVersion(2, 26);
+const API_VERSION_HYPERVISOR_PAGINATION: ApiVersion = ApiVersion(2, 33);
 const API_VERSION_KEYPAIR_PAGINATION: ApiVersion = ApiVersion(2, 35);
+const API_VERSION_NETWORK_ALLOCATION: ApiVersion = ApiVersion(2, 37);
+const API_VERSION_USAGE_PAGINATION: ApiVersion = ApiVersion(2, 40);
+const API_VERSION_AGGREGATE_UUID: ApiVersion = ApiVersion(2, 41);
+const API_VERSION_UUID_IDS: ApiVersion = ApiVersion(2, 53);
 const API_VERSION_FLAVOR_DESCRIPTION: ApiVersion = ApiVersion(2, 55);
 const API_VERSION_FLAVOR_EXTRA_SPECS: ApiVersion = ApiVersion(2, 61);
+const API_VERSION_AGGREGATE_IMAGES: ApiVersion = ApiVersion(2, 81);
 
 fn flavor_api_version(session: &Session) -> Result<Option<ApiVersion>> {
     session.pick_api_version(
@@ -44,6 +52,123 @@ fn flavor_api_version(session: &Session) -> Result<Option<ApiVersion>> {
     )
 }
 
+fn hypervisor_api_version(session: &Session) -> Result<Option<ApiVersion>> {
+    session.pick_api_version(
+        COMPUTE,
+        vec![API_VERSION_HYPERVISOR_PAGINATION, API_VERSION_UUID_IDS],
+    )
+}
+
+fn server_api_version(session: &Session) -> Result<Option<ApiVersion>> {
+    session.pick_api_version(
+        COMPUTE,
+        vec![API_VERSION_SERVER_DESCRIPTION, API_VERSION_SERVER_TAGS],
+    )
+}
+
+fn server_create_api_version(
+    session: &Session,
+    request: &ServerCreate,
+) -> Result<Option<ApiVersion>> {
+    match request.networks {
+        ServerNetworks::Explicit(..) => Ok(None),
+        ServerNetworks::Allocation(..) => {
+            if session.supports_api_version(COMPUTE, API_VERSION_NETWORK_ALLOCATION)? {
+                Ok(Some(API_VERSION_NETWORK_ALLOCATION))
+            } else {
+                Err(Error::new(
+                    ErrorKind::IncompatibleApiVersion,
+                    "Automatic network allocation requires compute API version 2.37",
+                ))
+            }
+        }
+    }
+}
+
+fn server_tags_api_version(session: &Session) -> Result<Option<ApiVersion>> {
+    if supports_server_tags(session)? {
+        Ok(Some(API_VERSION_SERVER_TAGS))
+    } else {
+        Err(Error::new(
+            ErrorKind::IncompatibleApiVersion,
+            "Server tags require compute API version 2.26",
+        ))
+    }
+}
+
+fn usage_api_version(session: &Session) -> Result<Option<ApiVersion>> {
+    session.pick_api_version(COMPUTE, Some(API_VERSION_USAGE_PAGINATION))
+}
+
+fn aggregate_api_version(session: &Session) -> Result<Option<ApiVersion>> {
+    session.pick_api_version(COMPUTE, Some(API_VERSION_AGGREGATE_UUID))
+}
+
+/// Run an action on an aggregate.
+pub fn aggregate_action<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    action: AggregateAction,
+) -> Result<Aggregate> {
+    debug!("Running {:?} on aggregate {}", action, id.as_ref());
+    let version = aggregate_api_version(session)?;
+    let root: AggregateRoot = session.post_json(
+        COMPUTE,
+        &["os-aggregates", id.as_ref(), "action"],
+        action,
+        version,
+    )?;
+    debug!("Updated aggregate {:?}", root.aggregate);
+    Ok(root.aggregate)
+}
+
+/// Request caching images on all hosts of an aggregate.
+pub fn cache_images_on_aggregate<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    images: Vec<String>,
+) -> Result<()> {
+    debug!("Caching images {:?} on aggregate {}", images, id.as_ref());
+    let body = AggregateCacheImagesRoot {
+        cache: images.into_iter().map(|id| IdOnly { id }).collect(),
+    };
+    let _ = session.post(
+        COMPUTE,
+        &["os-aggregates", id.as_ref(), "images"],
+        body,
+        Some(API_VERSION_AGGREGATE_IMAGES),
+    )?;
+    debug!("Requested image caching on aggregate {}", id.as_ref());
+    Ok(())
+}
+
+/// Create an aggregate.
+pub fn create_aggregate(session: &Session, request: AggregateCreate) -> Result<Aggregate> {
+    debug!("Creating an aggregate with {:?}", request);
+    let version = aggregate_api_version(session)?;
+    let body = AggregateCreateRoot { aggregate: request };
+    let root: AggregateRoot = session.post_json(COMPUTE, &["os-aggregates"], body, version)?;
+    debug!("Created aggregate {:?}", root.aggregate);
+    Ok(root.aggregate)
+}
+
+/// Add a tag to a server.
+pub fn add_server_tag<S1, S2>(session: &Session, id: S1, tag: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    trace!("Adding tag {} to server {}", tag.as_ref(), id.as_ref());
+    let version = server_tags_api_version(session)?;
+    let _ = session.put_empty(
+        COMPUTE,
+        &["servers", id.as_ref(), "tags", tag.as_ref()],
+        version,
+    )?;
+    debug!("Added tag {} to server {}", tag.as_ref(), id.as_ref());
+    Ok(())
+}
+
 /// Create a key pair.
 pub fn create_keypair(session: &Session, request: KeyPairCreate) -> Result<KeyPair> {
     let version = if request.key_type.is_some() {
@@ -62,12 +187,21 @@ pub fn create_keypair(session: &Session, request: KeyPairCreate) -> Result<KeyPa
 /// Create a server.
 pub fn create_server(session: &Session, request: ServerCreate) -> Result<Ref> {
     debug!("Creating a server with {:?}", request);
+    let version = server_create_api_version(session, &request)?;
     let body = ServerCreateRoot { server: request };
-    let root: CreatedServerRoot = session.post_json(COMPUTE, &["servers"], body, None)?;
+    let root: CreatedServerRoot = session.post_json(COMPUTE, &["servers"], body, version)?;
     trace!("Requested creation of server {:?}", root.server);
     Ok(root.server)
 }
 
+/// Delete an aggregate.
+pub fn delete_aggregate<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting aggregate {}", id.as_ref());
+    let _ = session.delete(COMPUTE, &["os-aggregates", id.as_ref()], None)?;
+    debug!("Aggregate {} was deleted", id.as_ref());
+    Ok(())
+}
+
 /// Delete a key pair.
 pub fn delete_keypair<S: AsRef<str>>(session: &Session, name: S) -> Result<()> {
     debug!("Deleting key pair {}", name.as_ref());
@@ -84,6 +218,175 @@ pub fn delete_server<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     Ok(())
 }
 
+/// Clear the encrypted password of a server.
+pub fn delete_server_password<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    trace!("Clearing password of server {}", id.as_ref());
+    let _ = session.delete(
+        COMPUTE,
+        &["servers", id.as_ref(), "os-server-password"],
+        None,
+    )?;
+    debug!("Successfully cleared password of server {}", id.as_ref());
+    Ok(())
+}
+
+/// Delete all tags of a server.
+pub fn delete_server_tags<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    trace!("Deleting all tags of server {}", id.as_ref());
+    let version = server_tags_api_version(session)?;
+    let _ = session.delete(COMPUTE, &["servers", id.as_ref(), "tags"], version)?;
+    debug!("Deleted all tags of server {}", id.as_ref());
+    Ok(())
+}
+
+/// Delete a tag of a server.
+pub fn delete_server_tag<S1, S2>(session: &Session, id: S1, tag: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    trace!("Deleting tag {} of server {}", tag.as_ref(), id.as_ref());
+    let version = server_tags_api_version(session)?;
+    let _ = session.delete(
+        COMPUTE,
+        &["servers", id.as_ref(), "tags", tag.as_ref()],
+        version,
+    )?;
+    debug!("Deleted tag {} of server {}", tag.as_ref(), id.as_ref());
+    Ok(())
+}
+
+/// Get an aggregate.
+///
+/// Aggregates are fetched by ID if the argument is an integer, otherwise (or
+/// if no aggregate has such ID) they are looked up by name or UUID.
+pub fn get_aggregate<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Aggregate> {
+    let s = id_or_name.as_ref();
+    if s.parse::<u64>().is_ok() {
+        get_aggregate_by_id(session, s).if_not_found_then(|| get_aggregate_by_name(session, s))
+    } else {
+        get_aggregate_by_name(session, s)
+    }
+}
+
+/// Get an aggregate by its ID.
+pub fn get_aggregate_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Aggregate> {
+    trace!("Get compute aggregate by ID {}", id.as_ref());
+    let version = aggregate_api_version(session)?;
+    let root: AggregateRoot =
+        session.get_json(COMPUTE, &["os-aggregates", id.as_ref()], version)?;
+    trace!("Received {:?}", root.aggregate);
+    Ok(root.aggregate)
+}
+
+/// Get an aggregate by its name or UUID.
+pub fn get_aggregate_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Aggregate> {
+    trace!("Get compute aggregate by name or UUID {}", name.as_ref());
+    let result = utils::one(
+        list_aggregates(session)?.into_iter().filter(|item| {
+            item.name == name.as_ref() || item.uuid.as_deref() == Some(name.as_ref())
+        }),
+        "Aggregate with given name or ID not found",
+        "Too many aggregates found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a hypervisor by its ID.
+pub fn get_hypervisor<S: AsRef<str>>(session: &Session, id: S) -> Result<Hypervisor> {
+    trace!("Get compute hypervisor by ID {}", id.as_ref());
+    let version = hypervisor_api_version(session)?;
+    let root: HypervisorRoot =
+        session.get_json(COMPUTE, &["os-hypervisors", id.as_ref()], version)?;
+    trace!("Received {:?}", root.hypervisor);
+    Ok(root.hypervisor)
+}
+
+/// Get statistics over all hypervisors.
+pub fn get_hypervisor_statistics(session: &Session) -> Result<HypervisorStatistics> {
+    trace!("Get compute hypervisor statistics");
+    let root: HypervisorStatisticsRoot =
+        session.get_json(COMPUTE, &["os-hypervisors", "statistics"], None)?;
+    trace!("Received {:?}", root.hypervisor_statistics);
+    Ok(root.hypervisor_statistics)
+}
+
+/// Get usage of a project.
+///
+/// Returns `None` if the project has no usage in the given period, as well as
+/// a marker for the next page (if any).
+pub fn get_project_usage<S: AsRef<str>>(
+    session: &Session,
+    project: S,
+    query: &Query,
+) -> Result<(Option<ProjectUsage>, Option<String>)> {
+    trace!(
+        "Get compute usage for project {} with {:?}",
+        project.as_ref(),
+        query
+    );
+    let ver = usage_api_version(session)?;
+    let root: ProjectUsageRoot = session.get_json_query(
+        COMPUTE,
+        &["os-simple-tenant-usage", project.as_ref()],
+        query,
+        ver,
+    )?;
+    let is_empty = root
+        .tenant_usage
+        .as_object()
+        .map(|obj| obj.is_empty())
+        .unwrap_or(false);
+    let result = if is_empty {
+        None
+    } else {
+        Some(serde_json::from_value(root.tenant_usage).map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidResponse,
+                format!("Cannot parse project usage: {}", err),
+            )
+        })?)
+    };
+    trace!("Received usage: {:?}", result);
+    Ok((result, next_marker(&root.tenant_usage_links)))
+}
+
+/// Get servers running on a hypervisor.
+pub fn get_hypervisor_servers<S1, S2>(
+    session: &Session,
+    id: S1,
+    hostname: S2,
+) -> Result<Vec<HypervisorServer>>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    trace!("Get servers running on compute hypervisor {}", id.as_ref());
+    let result = if session.supports_api_version(COMPUTE, API_VERSION_UUID_IDS)? {
+        let root: HypervisorRoot = session.get_json_query(
+            COMPUTE,
+            &["os-hypervisors", id.as_ref()],
+            &[("with_servers", true)],
+            Some(API_VERSION_UUID_IDS),
+        )?;
+        root.hypervisor.servers
+    } else {
+        let root: HypervisorsRoot = session.get_json(
+            COMPUTE,
+            &["os-hypervisors", hostname.as_ref(), "servers"],
+            None,
+        )?;
+        root.hypervisors
+            .into_iter()
+            .filter(|item| item.id == id.as_ref())
+            .flat_map(|item| item.servers)
+            .collect()
+    };
+    trace!("Received servers: {:?}", result);
+    Ok(result)
+}
+
 /// Get a flavor by its ID.
 pub fn get_extra_specs_by_flavor_id<S: AsRef<str>>(
     session: &Session,
@@ -143,7 +446,7 @@ pub fn get_server<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Ser
 /// Get a server by its ID.
 pub fn get_server_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Server> {
     trace!("Get compute server with ID {}", id.as_ref());
-    let version = session.pick_api_version(COMPUTE, Some(API_VERSION_SERVER_DESCRIPTION))?;
+    let version = server_api_version(session)?;
     let root: ServerRoot = session.get_json(COMPUTE, &["servers", id.as_ref()], version)?;
     trace!("Received {:?}", root.server);
     Ok(root.server)
@@ -164,6 +467,22 @@ pub fn get_server_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<S
     .and_then(|item| get_server_by_id(session, item.id))
 }
 
+/// Get the encrypted password of a server.
+pub fn get_server_password<S: AsRef<str>>(session: &Session, id: S) -> Result<Option<String>> {
+    trace!("Get password of server {}", id.as_ref());
+    let root: ServerPasswordRoot = session.get_json(
+        COMPUTE,
+        &["servers", id.as_ref(), "os-server-password"],
+        None,
+    )?;
+    trace!(
+        "Received password of server {} (present: {})",
+        id.as_ref(),
+        root.password.is_some()
+    );
+    Ok(root.password)
+}
+
 /// List flavors.
 pub fn list_flavors<Q: Serialize + Sync + Debug>(
     session: &Session,
@@ -188,6 +507,74 @@ pub fn list_flavors_detail<Q: Serialize + Sync + Debug>(
     Ok(root.flavors)
 }
 
+/// List aggregates.
+pub fn list_aggregates(session: &Session) -> Result<Vec<Aggregate>> {
+    trace!("Listing compute aggregates");
+    let version = aggregate_api_version(session)?;
+    let root: AggregatesRoot = session.get_json(COMPUTE, &["os-aggregates"], version)?;
+    trace!("Received aggregates: {:?}", root.aggregates);
+    Ok(root.aggregates)
+}
+
+/// List availability zones.
+pub fn list_availability_zones(session: &Session, detailed: bool) -> Result<Vec<AvailabilityZone>> {
+    trace!(
+        "Listing compute availability zones (detailed: {})",
+        detailed
+    );
+    let root: AvailabilityZonesRoot = if detailed {
+        session.get_json(COMPUTE, &["os-availability-zone", "detail"], None)?
+    } else {
+        session.get_json(COMPUTE, &["os-availability-zone"], None)?
+    };
+    trace!("Received availability zones: {:?}", root.availability_zones);
+    Ok(root.availability_zones)
+}
+
+/// List compute services.
+pub fn list_compute_services<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<ComputeService>> {
+    trace!("Listing compute services with {:?}", query);
+    let version = session.pick_api_version(
+        COMPUTE,
+        vec![API_VERSION_SERVICE_FORCE_DOWN, API_VERSION_UUID_IDS],
+    )?;
+    let root: ComputeServicesRoot =
+        session.get_json_query(COMPUTE, &["os-services"], query, version)?;
+    trace!("Received compute services: {:?}", root.services);
+    Ok(root.services)
+}
+
+/// List hypervisors with details.
+pub fn list_hypervisors_detail<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Hypervisor>> {
+    trace!("Listing compute hypervisors with {:?}", query);
+    let version = hypervisor_api_version(session)?;
+    let root: HypervisorsRoot =
+        session.get_json_query(COMPUTE, &["os-hypervisors", "detail"], query, version)?;
+    trace!("Received hypervisors: {:?}", root.hypervisors);
+    Ok(root.hypervisors)
+}
+
+/// List usage of all projects.
+///
+/// Also returns a marker for the next page (if any).
+pub fn list_project_usages(
+    session: &Session,
+    query: &Query,
+) -> Result<(Vec<ProjectUsage>, Option<String>)> {
+    trace!("Listing compute usage with {:?}", query);
+    let ver = usage_api_version(session)?;
+    let root: ProjectUsagesRoot =
+        session.get_json_query(COMPUTE, &["os-simple-tenant-usage"], query, ver)?;
+    trace!("Received usage: {:?}", root.tenant_usages);
+    Ok((root.tenant_usages, next_marker(&root.tenant_usages_links)))
+}
+
 /// List key pairs.
 pub fn list_keypairs<Q: Serialize + Sync + Debug>(
     session: &Session,
@@ -214,7 +601,8 @@ pub fn list_servers<Q: Serialize + Sync + Debug>(
     query: &Q,
 ) -> Result<Vec<IdAndName>> {
     trace!("Listing compute servers with {:?}", query);
-    let root: ServersRoot = session.get_json_query(COMPUTE, &["servers"], query, None)?;
+    let version = server_api_version(session)?;
+    let root: ServersRoot = session.get_json_query(COMPUTE, &["servers"], query, version)?;
     trace!("Received servers: {:?}", root.servers);
     Ok(root.servers)
 }
@@ -225,13 +613,22 @@ pub fn list_servers_detail<Q: Serialize + Sync + Debug>(
     query: &Q,
 ) -> Result<Vec<Server>> {
     trace!("Listing compute servers with {:?}", query);
-    let version = session.pick_api_version(COMPUTE, Some(API_VERSION_SERVER_DESCRIPTION))?;
+    let version = server_api_version(session)?;
     let root: ServersDetailRoot =
         session.get_json_query(COMPUTE, &["servers", "detail"], query, version)?;
     trace!("Received servers: {:?}", root.servers);
     Ok(root.servers)
 }
 
+/// List tags of a server.
+pub fn list_server_tags<S: AsRef<str>>(session: &Session, id: S) -> Result<Vec<String>> {
+    trace!("Listing tags of server {}", id.as_ref());
+    let version = server_tags_api_version(session)?;
+    let root: ServerTags = session.get_json(COMPUTE, &["servers", id.as_ref(), "tags"], version)?;
+    trace!("Server {} has tags {:?}", id.as_ref(), root.tags);
+    Ok(root.tags)
+}
+
 /// Run an action while providing some arguments.
 pub fn server_action_with_args<S1, S2, Q>(
     session: &Session,
@@ -270,6 +667,132 @@ where
     server_action_with_args(session, id, action, serde_json::Value::Null)
 }
 
+/// Replace all tags of a server.
+pub fn replace_server_tags<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    tags: Vec<String>,
+) -> Result<Vec<String>> {
+    trace!("Replacing tags of server {} with {:?}", id.as_ref(), tags);
+    let version = server_tags_api_version(session)?;
+    let body = ServerTags { tags };
+    let root: ServerTags =
+        session.put_json(COMPUTE, &["servers", id.as_ref(), "tags"], body, version)?;
+    debug!("Server {} now has tags {:?}", id.as_ref(), root.tags);
+    Ok(root.tags)
+}
+
+/// Update an aggregate.
+pub fn update_aggregate<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: AggregateUpdate,
+) -> Result<Aggregate> {
+    debug!("Updating aggregate {} with {:?}", id.as_ref(), update);
+    let version = aggregate_api_version(session)?;
+    let body = AggregateUpdateRoot { aggregate: update };
+    let root: AggregateRoot =
+        session.put_json(COMPUTE, &["os-aggregates", id.as_ref()], body, version)?;
+    debug!("Updated aggregate {:?}", root.aggregate);
+    Ok(root.aggregate)
+}
+
+/// Update a compute service.
+///
+/// Uses the modern API when possible, falls back to the legacy actions otherwise
+/// (one call for forcing down and one for changing the status).
+pub fn update_compute_service(
+    session: &Session,
+    service: &ComputeService,
+    update: ComputeServiceUpdate,
+) -> Result<ComputeService> {
+    debug!("Updating compute service {} with {:?}", service.id, update);
+    if session.supports_api_version(COMPUTE, API_VERSION_UUID_IDS)? {
+        let root: ComputeServiceRoot = session.put_json(
+            COMPUTE,
+            &["os-services", &service.id],
+            update,
+            Some(API_VERSION_UUID_IDS),
+        )?;
+        debug!("Updated compute service {:?}", root.service);
+        return Ok(root.service);
+    }
+
+    // Each legacy action changes only one aspect of the service.
+    if update.forced_down.is_some() {
+        let body = LegacyComputeServiceUpdate {
+            binary: service.binary.clone(),
+            disabled_reason: None,
+            forced_down: update.forced_down,
+            host: service.host.clone(),
+        };
+        let _ = session.put(
+            COMPUTE,
+            &["os-services", "force-down"],
+            body,
+            Some(API_VERSION_SERVICE_FORCE_DOWN),
+        )?;
+        debug!("Ran force-down on compute service {}", service.id);
+    }
+    if update.status.is_some() || update.forced_down.is_none() {
+        let action = match update.status {
+            Some(ServiceStatus::Disabled) if update.disabled_reason.is_some() => {
+                "disable-log-reason"
+            }
+            Some(ServiceStatus::Disabled) => "disable",
+            _ => "enable",
+        };
+        let body = LegacyComputeServiceUpdate {
+            binary: service.binary.clone(),
+            disabled_reason: update.disabled_reason,
+            forced_down: None,
+            host: service.host.clone(),
+        };
+        let _ = session.put(COMPUTE, &["os-services", action], body, None)?;
+        debug!("Ran {} on compute service {}", action, service.id);
+    }
+    // Legacy actions return only a part of the service, so re-fetch it.
+    let query = [
+        ("host", service.host.as_str()),
+        ("binary", service.binary.as_str()),
+    ];
+    utils::one(
+        list_compute_services(session, &query)?,
+        "Compute service disappeared after update",
+        "Too many compute services found with the same host and binary",
+    )
+}
+
+fn next_marker(links: &[Link]) -> Option<String> {
+    links
+        .iter()
+        .find(|link| link.rel == "next")
+        .and_then(|link| Url::parse(&link.href).ok())
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "marker")
+                .map(|(_, value)| value.into_owned())
+        })
+}
+
+/// Whether server tags are supported.
+#[inline]
+pub fn supports_server_tags(session: &Session) -> Result<bool> {
+    session.supports_api_version(COMPUTE, API_VERSION_SERVER_TAGS)
+}
+
+/// Whether hypervisor pagination is supported.
+#[inline]
+pub fn supports_hypervisor_pagination(session: &Session) -> Result<bool> {
+    session.supports_api_version(COMPUTE, API_VERSION_HYPERVISOR_PAGINATION)
+}
+
+/// Whether filtering hypervisors by a host name pattern is supported.
+#[inline]
+pub fn supports_hypervisor_hostname_pattern(session: &Session) -> Result<bool> {
+    session.supports_api_version(COMPUTE, API_VERSION_UUID_IDS)
+}
+
 /// Whether key pair pagination is supported.
 #[inline]
 pub fn supports_keypair_pagination(session: &Session) -> Result<bool> {
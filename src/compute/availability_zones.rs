@@ -0,0 +1,146 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Availability zones via Compute API.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{ResourceIterator, ResourceQuery};
+use super::super::utils::Session;
+use super::super::{Error, Result};
+use super::{api, protocol};
+
+/// Structure representing an availability zone.
+#[derive(Clone, Debug)]
+pub struct AvailabilityZone {
+    name: String,
+    available: bool,
+    hosts: HashMap<String, HashMap<String, protocol::AvailabilityZoneService>>,
+}
+
+/// A query to availability zone list.
+#[derive(Clone, Debug)]
+pub struct AvailabilityZoneQuery {
+    session: Rc<Session>,
+    with_hosts: bool,
+}
+
+impl AvailabilityZone {
+    fn new(inner: protocol::AvailabilityZone) -> AvailabilityZone {
+        AvailabilityZone {
+            name: inner.name,
+            available: inner.state.available,
+            hosts: inner.hosts.unwrap_or_default(),
+        }
+    }
+
+    /// Whether the availability zone is available.
+    #[inline]
+    pub fn available(&self) -> bool {
+        self.available
+    }
+
+    /// Hosts in the availability zone with their services.
+    ///
+    /// Only populated for queries created with `with_hosts`.
+    #[inline]
+    pub fn hosts(&self) -> &HashMap<String, HashMap<String, protocol::AvailabilityZoneService>> {
+        &self.hosts
+    }
+
+    /// Availability zone name.
+    #[inline]
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+}
+
+impl AvailabilityZoneQuery {
+    pub(crate) fn new(session: Rc<Session>) -> AvailabilityZoneQuery {
+        AvailabilityZoneQuery {
+            session,
+            with_hosts: false,
+        }
+    }
+
+    /// Include hosts and their services in the results (admin-only).
+    pub fn with_hosts(mut self) -> Self {
+        self.with_hosts = true;
+        self
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<AvailabilityZoneQuery> {
+        debug!(
+            "Fetching availability zones (with hosts: {})",
+            self.with_hosts
+        );
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<AvailabilityZone>> {
+        self.into_iter().collect()
+    }
+}
+
+impl ResourceQuery for AvailabilityZoneQuery {
+    type Item = AvailabilityZone;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        // The availability zones API does not support pagination.
+        Ok(false)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.name().clone()
+    }
+
+    fn fetch_chunk(
+        &self,
+        _limit: Option<usize>,
+        _marker: Option<String>,
+    ) -> Result<Vec<Self::Item>> {
+        Ok(
+            api::list_availability_zones(&self.session, self.with_hosts)?
+                .into_iter()
+                .map(AvailabilityZone::new)
+                .collect(),
+        )
+    }
+}
+
+impl IntoFallibleIterator for AvailabilityZoneQuery {
+    type Item = AvailabilityZone;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<AvailabilityZoneQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
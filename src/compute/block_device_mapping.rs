@@ -15,7 +15,7 @@
 //! Block device mapping for the Compute API.
 
 use super::super::common;
-use super::super::session::Session;
+use super::super::utils::Session;
 use super::super::Result;
 
 use serde::ser::{Serialize, SerializeStruct, Serializer};
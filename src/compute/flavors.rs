@@ -21,8 +21,7 @@ use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 use osproto::common::IdAndName;
 
 use super::super::common::{FlavorRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery};
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::utils::{Query, Session};
 use super::super::{Error, Result};
 use super::{api, protocol};
 
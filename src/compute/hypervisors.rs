@@ -0,0 +1,371 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hypervisor inventory via Compute API.
+
+use std::net::IpAddr;
+use std::rc::Rc;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use serde_json;
+
+use super::super::common::{Refresh, ResourceIterator, ResourceQuery};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result};
+use super::{api, protocol, ServerSummary};
+
+/// Structure representing a hypervisor.
+#[derive(Clone, Debug)]
+pub struct Hypervisor {
+    session: Rc<Session>,
+    inner: protocol::Hypervisor,
+}
+
+/// A query to hypervisor list.
+#[derive(Clone, Debug)]
+pub struct HypervisorQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+impl Hypervisor {
+    /// Create a hypervisor object.
+    fn new(session: Rc<Session>, inner: protocol::Hypervisor) -> Hypervisor {
+        Hypervisor { session, inner }
+    }
+
+    /// Load a Hypervisor object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Hypervisor> {
+        let inner = api::get_hypervisor(&session, id)?;
+        Ok(Hypervisor::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "CPU information (the format depends on the hypervisor)."]
+        cpu_info: ref Option<serde_json::Value>
+    }
+
+    transparent_property! {
+        #[doc = "Number of tasks the hypervisor is currently busy with (if available)."]
+        current_workload: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "Actual free disk space in GiB (if available)."]
+        disk_available_least: Option<i64>
+    }
+
+    transparent_property! {
+        #[doc = "Free disk space in GiB (if available)."]
+        free_disk_gb: Option<i64>
+    }
+
+    transparent_property! {
+        #[doc = "Free RAM in MiB (if available)."]
+        free_ram_mb: Option<i64>
+    }
+
+    transparent_property! {
+        #[doc = "IP address of the hypervisor's host (if available)."]
+        host_ip: Option<IpAddr>
+    }
+
+    transparent_property! {
+        #[doc = "Host name of the hypervisor."]
+        hypervisor_hostname: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Hypervisor type (if available)."]
+        hypervisor_type: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Hypervisor version (if available)."]
+        hypervisor_version: Option<u64>
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Total disk space in GiB (if available)."]
+        local_gb: Option<u64>
+    }
+
+    transparent_property! {
+        #[doc = "Used disk space in GiB (if available)."]
+        local_gb_used: Option<u64>
+    }
+
+    transparent_property! {
+        #[doc = "Total RAM in MiB (if available)."]
+        memory_mb: Option<u64>
+    }
+
+    transparent_property! {
+        #[doc = "Used RAM in MiB (if available)."]
+        memory_mb_used: Option<u64>
+    }
+
+    transparent_property! {
+        #[doc = "Number of running servers (if available)."]
+        running_vms: Option<u32>
+    }
+
+    /// Servers running on this hypervisor.
+    ///
+    /// Always issues a new request to the Compute API.
+    pub fn servers(&self) -> Result<Vec<ServerSummary>> {
+        Ok(api::get_hypervisor_servers(
+            &self.session,
+            &self.inner.id,
+            &self.inner.hypervisor_hostname,
+        )?
+        .into_iter()
+        .map(|item| ServerSummary::new(self.session.clone(), item.uuid, item.name))
+        .collect())
+    }
+
+    transparent_property! {
+        #[doc = "Compute service backing this hypervisor (if available)."]
+        service: ref Option<protocol::HypervisorService>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the hypervisor is up or down."]
+        state: protocol::ServiceState
+    }
+
+    transparent_property! {
+        #[doc = "Whether the hypervisor is enabled or disabled."]
+        status: protocol::ServiceStatus
+    }
+
+    transparent_property! {
+        #[doc = "Total number of VCPUs (if available)."]
+        vcpus: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "Number of used VCPUs (if available)."]
+        vcpus_used: Option<u32>
+    }
+}
+
+/// Get statistics aggregated over all hypervisors.
+pub(crate) fn hypervisor_statistics(session: &Session) -> Result<protocol::HypervisorStatistics> {
+    api::get_hypervisor_statistics(session)
+}
+
+impl Refresh for Hypervisor {
+    /// Refresh the hypervisor.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_hypervisor(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+}
+
+impl HypervisorQuery {
+    pub(crate) fn new(session: Rc<Session>) -> HypervisorQuery {
+        HypervisorQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Filter by a host name pattern (requires API version 2.53).
+    ///
+    /// Using this disables automatic pagination. The query fails with
+    /// `IncompatibleApiVersion` if the API version 2.53 is not available.
+    pub fn set_hostname_pattern<T: Into<String>>(&mut self, value: T) {
+        self.can_paginate = false;
+        self.query.push_str("hypervisor_hostname_pattern", value);
+    }
+
+    /// Filter by a host name pattern (requires API version 2.53).
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_hostname_pattern<T: Into<String>>(mut self, value: T) -> Self {
+        self.set_hostname_pattern(value);
+        self
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<HypervisorQuery> {
+        debug!("Fetching hypervisors with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Hypervisor>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<Hypervisor> {
+        debug!("Fetching one hypervisor with {:?}", self.query);
+        if self.can_paginate && api::supports_hypervisor_pagination(&self.session)? {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for HypervisorQuery {
+    type Item = Hypervisor;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        if self.can_paginate {
+            api::supports_hypervisor_pagination(&self.session)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_hypervisors_detail(&self.session, &query)?
+            .into_iter()
+            .map(|item| Hypervisor::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        let uses_pattern = self
+            .query
+            .0
+            .iter()
+            .any(|(key, _)| key == "hypervisor_hostname_pattern");
+        if uses_pattern && !api::supports_hypervisor_hostname_pattern(&self.session)? {
+            Err(Error::new(
+                ErrorKind::IncompatibleApiVersion,
+                "Filtering by a host name pattern requires compute API version 2.53",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl IntoFallibleIterator for HypervisorQuery {
+    type Item = Hypervisor;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<HypervisorQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::json;
+
+    use super::super::super::utils::test::{fake_session, mock_session};
+    use super::super::super::ErrorKind;
+    use super::HypervisorQuery;
+
+    #[test]
+    fn test_hostname_pattern_disables_pagination() {
+        let query = HypervisorQuery::new(Rc::new(fake_session()));
+        assert!(query.can_paginate);
+        let query = query.with_hostname_pattern("compute-");
+        assert!(!query.can_paginate);
+        assert_eq!(
+            query.query.0,
+            vec![(
+                "hypervisor_hostname_pattern".to_string(),
+                "compute-".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_hostname_pattern_requires_microversion() {
+        let (session, requests) = mock_session("2.52", |_| (200, json!({"hypervisors": []})));
+        let err = HypervisorQuery::new(Rc::new(session))
+            .with_hostname_pattern("compute-")
+            .all()
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_hostname_pattern() {
+        let (session, requests) = mock_session("2.53", |_| (200, json!({"hypervisors": []})));
+        let result = HypervisorQuery::new(Rc::new(session))
+            .with_hostname_pattern("compute-")
+            .all()
+            .unwrap();
+        assert!(result.is_empty());
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].path,
+            "/v2.1/os-hypervisors/detail?hypervisor_hostname_pattern=compute-"
+        );
+        assert_eq!(
+            requests[0].header("x-openstack-nova-api-version"),
+            Some("2.53")
+        );
+    }
+}
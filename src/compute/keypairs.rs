@@ -19,8 +19,7 @@ use std::rc::Rc;
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 
 use super::super::common::{IntoVerified, KeyPairRef, Refresh, ResourceIterator, ResourceQuery};
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::utils::{Query, Session};
 use super::super::{Error, ErrorKind, Result};
 use super::{api, protocol};
 
@@ -14,21 +14,38 @@
 
 //! Compute API implementation bits.
 
+mod aggregates;
 mod api;
+mod availability_zones;
 mod block_device_mapping;
 mod flavors;
+mod hypervisors;
 mod keypairs;
+#[cfg(feature = "windows-password")]
+mod password;
 mod protocol;
 mod servers;
+mod services;
+mod usage;
 
+pub use self::aggregates::{Aggregate, AggregateQuery, NewAggregate};
+pub use self::availability_zones::{AvailabilityZone, AvailabilityZoneQuery};
 pub use self::block_device_mapping::{BlockDevice, BlockDeviceDestinationType, BlockDeviceSource};
 pub use self::flavors::{DetailedFlavorQuery, Flavor, FlavorQuery, FlavorSummary};
+pub(crate) use self::hypervisors::hypervisor_statistics;
+pub use self::hypervisors::{Hypervisor, HypervisorQuery};
 pub use self::keypairs::{KeyPair, KeyPairQuery, NewKeyPair};
+#[cfg(feature = "windows-password")]
+pub use self::password::decrypt_password;
 pub use self::protocol::{
-    AddressType, KeyPairType, RebootType, ServerAddress, ServerFlavor, ServerPowerState,
-    ServerSortKey, ServerStatus,
+    AddressType, AvailabilityZoneService, HypervisorService, HypervisorStatistics, KeyPairType,
+    RebootType, ServerAddress, ServerFlavor, ServerNetworkAllocation, ServerPowerState,
+    ServerSortKey, ServerStatus, ServiceState, ServiceStatus,
 };
 pub use self::servers::{
     DetailedServerQuery, NewServer, Server, ServerCreationWaiter, ServerNIC, ServerQuery,
     ServerStatusWaiter, ServerSummary,
 };
+pub use self::services::{ComputeService, ComputeServiceQuery};
+pub(crate) use self::usage::{compute_usage, project_usage};
+pub use self::usage::{ProjectUsage, ServerUsage};
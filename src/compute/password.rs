@@ -0,0 +1,93 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decryption of server passwords.
+
+use base64;
+use openssl::rsa::{Padding, Rsa};
+
+use super::super::{Error, ErrorKind, Result};
+
+/// Decrypt a server password with the private key of its key pair.
+///
+/// The encrypted password is expected in the base64-encoded form returned by
+/// `Server::encrypted_password`, the private key - in the PEM format returned
+/// by `NewKeyPair::generate`.
+///
+/// Requires the `windows-password` feature.
+pub fn decrypt_password<S1, S2>(encrypted: S1, private_key: S2) -> Result<String>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    let data: String = encrypted
+        .as_ref()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let data = base64::decode(&data).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Encrypted password is not valid base64: {}", err),
+        )
+    })?;
+
+    let key = Rsa::private_key_from_pem(private_key.as_ref().as_bytes()).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Cannot load the private key: {}", err),
+        )
+    })?;
+
+    let mut buffer = vec![0; key.size() as usize];
+    let size = key
+        .private_decrypt(&data, &mut buffer, Padding::PKCS1)
+        .map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Cannot decrypt the password: {}", err),
+            )
+        })?;
+    buffer.truncate(size);
+
+    String::from_utf8(buffer).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("Decrypted password is not valid UTF-8: {}", err),
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use base64;
+    use openssl::rsa::{Padding, Rsa};
+
+    use super::decrypt_password;
+
+    #[test]
+    fn test_decrypt_password() {
+        let key = Rsa::generate(2048).unwrap();
+        let mut buffer = vec![0; key.size() as usize];
+        let size = key
+            .public_encrypt(b"s3cr3t", &mut buffer, Padding::PKCS1)
+            .unwrap();
+        let encrypted = base64::encode(&buffer[..size]);
+        let pem = String::from_utf8(key.private_key_to_pem().unwrap()).unwrap();
+
+        assert_eq!(decrypt_password(&encrypted, &pem).unwrap(), "s3cr3t");
+        assert!(decrypt_password("not base64!", &pem).is_err());
+        assert!(decrypt_password(&encrypted, "not a key").is_err());
+    }
+}
@@ -24,6 +24,7 @@ use chrono::{DateTime, FixedOffset};
 use osproto::common::{empty_as_default, IdAndName, Ref};
 use serde::{Deserialize, Serialize};
 
+use super::super::common;
 use super::BlockDevice;
 
 protocol_enum! {
@@ -106,6 +107,14 @@ protocol_enum! {
     }
 }
 
+protocol_enum! {
+    #[doc = "Automatic network allocation for a new server."]
+    enum ServerNetworkAllocation {
+        Auto = "auto",
+        None = "none"
+    }
+}
+
 protocol_enum! {
     #[doc = "Type of a server address."]
     enum AddressType {
@@ -122,6 +131,22 @@ protocol_enum! {
     }
 }
 
+protocol_enum! {
+    #[doc = "State of a compute service or a hypervisor."]
+    enum ServiceState {
+        Up = "up",
+        Down = "down"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Administrative status of a compute service or a hypervisor."]
+    enum ServiceStatus {
+        Enabled = "enabled",
+        Disabled = "disabled"
+    }
+}
+
 /// Address of a server.
 #[derive(Clone, Debug, Deserialize)]
 pub struct ServerAddress {
@@ -188,6 +213,8 @@ pub struct Server {
     pub status: ServerStatus,
     #[serde(rename = "OS-EXT-STS:power_state", default)]
     pub power_state: ServerPowerState,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub tenant_id: String,
     #[serde(rename = "updated")]
     pub updated_at: DateTime<FixedOffset>,
@@ -199,6 +226,11 @@ pub struct ServersRoot {
     pub servers: Vec<IdAndName>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ServerTags {
+    pub tags: Vec<String>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct ServersDetailRoot {
     pub servers: Vec<Server>,
@@ -217,6 +249,13 @@ pub enum ServerNetwork {
     FixedIp { fixed_ip: Ipv4Addr },
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum ServerNetworks {
+    Explicit(Vec<ServerNetwork>),
+    Allocation(ServerNetworkAllocation),
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ServerCreate {
     #[serde(
@@ -232,7 +271,14 @@ pub struct ServerCreate {
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
     pub name: String,
-    pub networks: Vec<ServerNetwork>,
+    pub networks: ServerNetworks,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub security_groups: Vec<ServerSecurityGroup>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ServerSecurityGroup {
+    pub name: String,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -328,6 +374,352 @@ pub struct KeyPairsRoot {
     pub keypairs: Vec<KeyPairRoot>,
 }
 
+/// A compute service backing a hypervisor.
+#[derive(Clone, Debug, Deserialize)]
+pub struct HypervisorService {
+    /// Reason the service was disabled (if any).
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub disabled_reason: Option<String>,
+    /// Host name of the service.
+    pub host: String,
+    /// Service ID.
+    #[serde(deserialize_with = "common::protocol::deser_string_or_number")]
+    pub id: String,
+}
+
+/// A server running on a hypervisor.
+#[derive(Clone, Debug, Deserialize)]
+pub struct HypervisorServer {
+    pub name: String,
+    pub uuid: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Hypervisor {
+    #[serde(default)]
+    pub cpu_info: Option<serde_json::Value>,
+    #[serde(default)]
+    pub current_workload: Option<u32>,
+    #[serde(default)]
+    pub disk_available_least: Option<i64>,
+    #[serde(default)]
+    pub free_disk_gb: Option<i64>,
+    #[serde(default)]
+    pub free_ram_mb: Option<i64>,
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub host_ip: Option<IpAddr>,
+    pub hypervisor_hostname: String,
+    #[serde(default)]
+    pub hypervisor_type: Option<String>,
+    #[serde(default)]
+    pub hypervisor_version: Option<u64>,
+    #[serde(deserialize_with = "common::protocol::deser_string_or_number")]
+    pub id: String,
+    #[serde(default)]
+    pub local_gb: Option<u64>,
+    #[serde(default)]
+    pub local_gb_used: Option<u64>,
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+    #[serde(default)]
+    pub memory_mb_used: Option<u64>,
+    #[serde(default)]
+    pub running_vms: Option<u32>,
+    #[serde(default)]
+    pub servers: Vec<HypervisorServer>,
+    #[serde(default)]
+    pub service: Option<HypervisorService>,
+    pub state: ServiceState,
+    pub status: ServiceStatus,
+    #[serde(default)]
+    pub vcpus: Option<u32>,
+    #[serde(default)]
+    pub vcpus_used: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HypervisorRoot {
+    pub hypervisor: Hypervisor,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HypervisorsRoot {
+    pub hypervisors: Vec<Hypervisor>,
+}
+
+/// Statistics aggregated over all hypervisors.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct HypervisorStatistics {
+    /// Number of hypervisors.
+    pub count: u32,
+    /// Number of tasks the hypervisors are currently busy with.
+    pub current_workload: u32,
+    /// Actual free disk space in GiB.
+    pub disk_available_least: i64,
+    /// Free disk space in GiB.
+    pub free_disk_gb: i64,
+    /// Free RAM in MiB.
+    pub free_ram_mb: i64,
+    /// Total disk space in GiB.
+    pub local_gb: u64,
+    /// Used disk space in GiB.
+    pub local_gb_used: u64,
+    /// Total RAM in MiB.
+    pub memory_mb: u64,
+    /// Used RAM in MiB.
+    pub memory_mb_used: u64,
+    /// Number of running servers.
+    pub running_vms: u32,
+    /// Total number of VCPUs.
+    pub vcpus: u32,
+    /// Number of used VCPUs.
+    pub vcpus_used: u32,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct HypervisorStatisticsRoot {
+    pub hypervisor_statistics: HypervisorStatistics,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComputeService {
+    pub binary: String,
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub disabled_reason: Option<String>,
+    #[serde(default)]
+    pub forced_down: bool,
+    pub host: String,
+    #[serde(deserialize_with = "common::protocol::deser_string_or_number")]
+    pub id: String,
+    pub state: ServiceState,
+    pub status: ServiceStatus,
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+    pub zone: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComputeServiceRoot {
+    pub service: ComputeService,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ComputeServicesRoot {
+    pub services: Vec<ComputeService>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ComputeServiceUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forced_down: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ServiceStatus>,
+}
+
+/// Legacy (before API version 2.53) compute service update.
+#[derive(Clone, Debug, Serialize)]
+pub struct LegacyComputeServiceUpdate {
+    pub binary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forced_down: Option<bool>,
+    pub host: String,
+}
+
+/// State of an availability zone.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct AvailabilityZoneState {
+    pub available: bool,
+}
+
+/// State of a service in an availability zone.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct AvailabilityZoneService {
+    /// Whether the service is enabled.
+    pub active: bool,
+    /// Whether the service is up.
+    pub available: bool,
+    /// Last time the service reported its state.
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AvailabilityZone {
+    #[serde(default)]
+    pub hosts: Option<HashMap<String, HashMap<String, AvailabilityZoneService>>>,
+    #[serde(rename = "zoneName")]
+    pub name: String,
+    #[serde(rename = "zoneState")]
+    pub state: AvailabilityZoneState,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AvailabilityZonesRoot {
+    #[serde(rename = "availabilityZoneInfo")]
+    pub availability_zones: Vec<AvailabilityZone>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Aggregate {
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub availability_zone: Option<String>,
+    #[serde(deserialize_with = "common::protocol::deser_datetime_assume_utc")]
+    pub created_at: DateTime<FixedOffset>,
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    #[serde(deserialize_with = "common::protocol::deser_string_or_number")]
+    pub id: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    pub name: String,
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    pub uuid: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AggregateRoot {
+    pub aggregate: Aggregate,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AggregatesRoot {
+    pub aggregates: Vec<Aggregate>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AggregateCreate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability_zone: Option<String>,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AggregateCreateRoot {
+    pub aggregate: AggregateCreate,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct AggregateUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability_zone: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AggregateUpdateRoot {
+    pub aggregate: AggregateUpdate,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub enum AggregateAction {
+    #[serde(rename = "add_host")]
+    AddHost { host: String },
+    #[serde(rename = "remove_host")]
+    RemoveHost { host: String },
+    #[serde(rename = "set_metadata")]
+    SetMetadata {
+        metadata: HashMap<String, Option<String>>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AggregateCacheImagesRoot {
+    pub cache: Vec<IdOnly>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IdOnly {
+    pub id: String,
+}
+
+/// Usage of a single server over a period of time.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ServerUsage {
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub ended_at: Option<DateTime<FixedOffset>>,
+    pub flavor: String,
+    pub hours: f64,
+    #[serde(rename = "instance_id")]
+    pub id: String,
+    #[serde(rename = "local_gb")]
+    pub disk_gb: u32,
+    pub memory_mb: u32,
+    pub name: String,
+    #[serde(rename = "tenant_id")]
+    pub project_id: String,
+    #[serde(deserialize_with = "common::protocol::deser_datetime_assume_utc")]
+    pub started_at: DateTime<FixedOffset>,
+    pub state: String,
+    pub uptime: u64,
+    pub vcpus: u32,
+}
+
+/// Usage of a project over a period of time.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectUsage {
+    #[serde(rename = "tenant_id")]
+    pub project_id: String,
+    #[serde(default)]
+    pub server_usages: Vec<ServerUsage>,
+    #[serde(deserialize_with = "common::protocol::deser_datetime_assume_utc")]
+    pub start: DateTime<FixedOffset>,
+    #[serde(deserialize_with = "common::protocol::deser_datetime_assume_utc")]
+    pub stop: DateTime<FixedOffset>,
+    pub total_hours: f64,
+    #[serde(rename = "total_local_gb_usage")]
+    pub total_disk_gb_hours: f64,
+    #[serde(rename = "total_memory_mb_usage")]
+    pub total_memory_mb_hours: f64,
+    #[serde(rename = "total_vcpus_usage")]
+    pub total_vcpu_hours: f64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ServerPasswordRoot {
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub password: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Link {
+    pub href: String,
+    pub rel: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectUsageRoot {
+    // NOTE: an empty object is returned for projects without usage.
+    pub tenant_usage: serde_json::Value,
+    #[serde(default)]
+    pub tenant_usage_links: Vec<Link>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProjectUsagesRoot {
+    pub tenant_usages: Vec<ProjectUsage>,
+    #[serde(default)]
+    pub tenant_usages_links: Vec<Link>,
+}
+
 impl Default for ServerStatus {
     fn default() -> ServerStatus {
         ServerStatus::Unknown
@@ -26,16 +26,17 @@ use waiter::{Waiter, WaiterCurrentState};
 
 use super::super::common::{
     DeletionWaiter, FlavorRef, ImageRef, IntoVerified, KeyPairRef, NetworkRef, PortRef, ProjectRef,
-    Refresh, ResourceIterator, ResourceQuery, UserRef, VolumeRef,
+    Refresh, ResourceIterator, ResourceQuery, SecurityGroupRef, Taggable, UserRef, VolumeRef,
 };
 #[cfg(feature = "image")]
 use super::super::image::Image;
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::utils::{Query, Session};
 use super::super::{Error, ErrorKind, Result, Sort};
 use super::{api, protocol, BlockDevice, KeyPair};
 
 /// A query to server list.
+///
+/// Filtering by tags requires compute API version 2.26.
 #[derive(Clone, Debug)]
 pub struct ServerQuery {
     session: Rc<Session>,
@@ -94,7 +95,9 @@ pub struct NewServer {
     metadata: HashMap<String, String>,
     name: String,
     nics: Vec<ServerNIC>,
+    network_allocation: Option<protocol::ServerNetworkAllocation>,
     block_devices: Vec<BlockDevice>,
+    security_groups: Vec<SecurityGroupRef>,
 }
 
 /// Waiter for server to be created.
@@ -227,6 +230,14 @@ impl Server {
         }
     }
 
+    /// Fetch the encrypted password of the server (if it was posted).
+    ///
+    /// The password is encrypted with the public key of the server's key
+    /// pair and encoded with base64.
+    pub fn encrypted_password(&self) -> Result<Option<String>> {
+        api::get_server_password(&self.session, &self.inner.id)
+    }
+
     /// Fetch the key pair used for the server.
     pub fn key_pair(&self) -> Result<KeyPair> {
         match self.inner.key_pair_name {
@@ -268,6 +279,26 @@ impl Server {
         updated_at: DateTime<FixedOffset>
     }
 
+    /// Clear the encrypted password of the server.
+    ///
+    /// This does not change the password on the server itself.
+    pub fn clear_password(&self) -> Result<()> {
+        api::delete_server_password(&self.session, &self.inner.id)
+    }
+
+    /// Decrypt the password of the server with the given private key.
+    ///
+    /// Returns `None` if the server has not posted its password yet.
+    ///
+    /// Requires the `windows-password` feature.
+    #[cfg(feature = "windows-password")]
+    pub fn decrypt_password<S: AsRef<str>>(&self, private_key: S) -> Result<Option<String>> {
+        match self.encrypted_password()? {
+            Some(encrypted) => super::decrypt_password(encrypted, private_key).map(Some),
+            None => Ok(None),
+        }
+    }
+
     /// Delete the server.
     pub fn delete(self) -> Result<DeletionWaiter<Server>> {
         api::delete_server(&self.session, &self.inner.id)?;
@@ -311,6 +342,49 @@ impl Server {
     }
 }
 
+/// Server tags require compute API version 2.26.
+impl Taggable for Server {
+    fn tags(&self) -> &Vec<String> {
+        &self.inner.tags
+    }
+
+    fn add_tag<S: Into<String>>(&mut self, tag: S) -> Result<()> {
+        let tag = tag.into();
+        api::add_server_tag(&self.session, &self.inner.id, &tag)?;
+        if !self.inner.tags.contains(&tag) {
+            self.inner.tags.push(tag);
+        }
+        Ok(())
+    }
+
+    fn clear_tags(&mut self) -> Result<()> {
+        api::delete_server_tags(&self.session, &self.inner.id)?;
+        self.inner.tags.clear();
+        Ok(())
+    }
+
+    fn refresh_tags(&mut self) -> Result<()> {
+        self.inner.tags = api::list_server_tags(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+
+    fn remove_tag<S: AsRef<str>>(&mut self, tag: S) -> Result<()> {
+        api::delete_server_tag(&self.session, &self.inner.id, tag.as_ref())?;
+        self.inner.tags.retain(|item| item != tag.as_ref());
+        Ok(())
+    }
+
+    fn replace_tags<I>(&mut self, tags: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let tags = tags.into_iter().map(Into::into).collect();
+        self.inner.tags = api::replace_server_tags(&self.session, &self.inner.id, tags)?;
+        Ok(())
+    }
+}
+
 impl<'server> Waiter<(), Error> for ServerStatusWaiter<'server> {
     fn default_wait_timeout(&self) -> Option<Duration> {
         // TODO(dtantsur): vary depending on target?
@@ -366,6 +440,14 @@ impl<'server> WaiterCurrentState<Server> for ServerStatusWaiter<'server> {
 }
 
 impl ServerSummary {
+    /// Create a server summary from an ID and a name.
+    pub(crate) fn new(session: Rc<Session>, id: String, name: String) -> ServerSummary {
+        ServerSummary {
+            session,
+            inner: IdAndName { id, name },
+        }
+    }
+
     transparent_property! {
         #[doc = "Server unique ID."]
         id: ref String
@@ -483,6 +565,8 @@ impl ServerQuery {
         set_user, with_user -> user_id: UserRef
     }
 
+    tag_query_filters!();
+
     /// Convert this query into a detailed query.
     #[inline]
     pub fn detailed(self) -> DetailedServerQuery {
@@ -545,12 +629,23 @@ impl ResourceQuery for ServerQuery {
         let query = self.query.with_marker_and_limit(limit, marker);
         Ok(api::list_servers(&self.session, &query)?
             .into_iter()
-            .map(|srv| ServerSummary {
-                session: self.session.clone(),
-                inner: srv,
-            })
+            .map(|srv| ServerSummary::new(self.session.clone(), srv.id, srv.name))
             .collect())
     }
+
+    fn validate(&mut self) -> Result<()> {
+        let uses_tags = self.query.0.iter().any(|(key, _)| {
+            key == "tags" || key == "tags-any" || key == "not-tags" || key == "not-tags-any"
+        });
+        if uses_tags && !api::supports_server_tags(&self.session)? {
+            Err(Error::new(
+                ErrorKind::IncompatibleApiVersion,
+                "Filtering by tags requires compute API version 2.26",
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl DetailedServerQuery {
@@ -590,6 +685,10 @@ impl ResourceQuery for DetailedServerQuery {
         }
         Ok(result)
     }
+
+    fn validate(&mut self) -> Result<()> {
+        self.inner.validate()
+    }
 }
 
 impl From<DetailedServerQuery> for ServerQuery {
@@ -634,12 +733,34 @@ impl NewServer {
             metadata: HashMap::new(),
             name,
             nics: Vec::new(),
+            network_allocation: None,
             block_devices: Vec::new(),
+            security_groups: Vec::new(),
         }
     }
 
     /// Request creation of the server.
     pub fn create(self) -> Result<ServerCreationWaiter> {
+        let mut security_groups = Vec::with_capacity(self.security_groups.len());
+        for group in self.security_groups {
+            security_groups.push(protocol::ServerSecurityGroup {
+                name: group.into_verified(&self.session)?.into(),
+            });
+        }
+
+        let networks = match self.network_allocation {
+            Some(allocation) => {
+                if !self.nics.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Explicit NICs cannot be combined with automatic network allocation",
+                    ));
+                }
+                protocol::ServerNetworks::Allocation(allocation)
+            }
+            None => protocol::ServerNetworks::Explicit(convert_networks(&self.session, self.nics)?),
+        };
+
         let request = protocol::ServerCreate {
             block_devices: self.block_devices.into_verified(&self.session)?,
             flavorRef: self.flavor.into_verified(&self.session)?.into(),
@@ -653,7 +774,8 @@ impl NewServer {
             },
             metadata: self.metadata,
             name: self.name,
-            networks: convert_networks(&self.session, self.nics)?,
+            networks,
+            security_groups,
         };
 
         let server_ref = api::create_server(&self.session, request)?;
@@ -686,6 +808,17 @@ impl NewServer {
         self.nics.push(ServerNIC::WithPort(port.into()));
     }
 
+    /// Add a security group to the new server.
+    ///
+    /// The default security group is used if none are provided.
+    #[inline]
+    pub fn add_security_group<G>(&mut self, group: G)
+    where
+        G: Into<SecurityGroupRef>,
+    {
+        self.security_groups.push(group.into());
+    }
+
     /// Metadata assigned to this server.
     #[inline]
     pub fn metadata(&mut self) -> &mut HashMap<String, String> {
@@ -720,6 +853,14 @@ impl NewServer {
         self.keypair = Some(keypair.into());
     }
 
+    /// Use automatic network allocation instead of explicit NICs.
+    ///
+    /// Requires compute API version 2.37. With `Auto` a network is picked or
+    /// allocated by the cloud, with `None` the server has no networking.
+    pub fn set_network_allocation(&mut self, allocation: protocol::ServerNetworkAllocation) {
+        self.network_allocation = Some(allocation);
+    }
+
     /// Add a block device to attach to the server.
     #[inline]
     pub fn with_block_device(mut self, block_device: BlockDevice) -> Self {
@@ -783,6 +924,19 @@ impl NewServer {
         self
     }
 
+    /// Use automatic network allocation instead of explicit NICs.
+    ///
+    /// Requires compute API version 2.37. With `Auto` a network is picked or
+    /// allocated by the cloud, with `None` the server has no networking.
+    #[inline]
+    pub fn with_network_allocation(
+        mut self,
+        allocation: protocol::ServerNetworkAllocation,
+    ) -> NewServer {
+        self.set_network_allocation(allocation);
+        self
+    }
+
     /// Create a volume to boot from from an image.
     #[inline]
     pub fn with_new_boot_volume<I>(self, image: I, size_gib: u32) -> Self
@@ -801,6 +955,18 @@ impl NewServer {
         self.add_port(port);
         self
     }
+
+    /// Add a security group to the new server.
+    ///
+    /// The default security group is used if none are provided.
+    #[inline]
+    pub fn with_security_group<G>(mut self, group: G) -> NewServer
+    where
+        G: Into<SecurityGroupRef>,
+    {
+        self.add_security_group(group);
+        self
+    }
 }
 
 impl Waiter<Server, Error> for ServerCreationWaiter {
@@ -877,3 +1043,34 @@ impl IntoFallibleIterator for DetailedServerQuery {
         self.into_iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::super::super::common::ResourceQuery;
+    use super::super::super::utils::test::versioned_session;
+    use super::super::super::ErrorKind;
+    use super::ServerQuery;
+
+    #[test]
+    fn test_tags_require_microversion() {
+        let mut query = ServerQuery::new(Rc::new(versioned_session("2.25")));
+        query.validate().unwrap();
+        let mut query = query.without_tags_any(vec!["a", "b"]);
+        let err = query.validate().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+
+        let mut query = ServerQuery::new(Rc::new(versioned_session("2.26")))
+            .with_tags(vec!["a"])
+            .without_tags(vec!["b", "c"]);
+        query.validate().unwrap();
+        assert_eq!(
+            query.query.0,
+            vec![
+                ("tags".to_string(), "a".to_string()),
+                ("not-tags".to_string(), "b,c".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compute services management via Compute API.
+
+use std::rc::Rc;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{ResourceIterator, ResourceQuery};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result};
+use super::{api, protocol};
+
+/// Structure representing a compute service (e.g. `nova-compute`).
+#[derive(Clone, Debug)]
+pub struct ComputeService {
+    session: Rc<Session>,
+    inner: protocol::ComputeService,
+}
+
+/// A query to compute service list.
+#[derive(Clone, Debug)]
+pub struct ComputeServiceQuery {
+    session: Rc<Session>,
+    query: Query,
+}
+
+impl ComputeService {
+    transparent_property! {
+        #[doc = "Name of the service binary (e.g. `nova-compute`)."]
+        binary: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Reason the service was disabled (if any)."]
+        disabled_reason: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the service was forced down."]
+        forced_down: bool
+    }
+
+    transparent_property! {
+        #[doc = "Host the service is running on."]
+        host: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Whether the service is up or down."]
+        state: protocol::ServiceState
+    }
+
+    transparent_property! {
+        #[doc = "Whether the service is enabled or disabled."]
+        status: protocol::ServiceStatus
+    }
+
+    transparent_property! {
+        #[doc = "Last time the service reported its state (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Availability zone of the service."]
+        zone: ref String
+    }
+
+    /// Disable the service.
+    pub fn disable(&mut self) -> Result<()> {
+        self.update(protocol::ComputeServiceUpdate {
+            status: Some(protocol::ServiceStatus::Disabled),
+            ..Default::default()
+        })
+    }
+
+    /// Disable the service, recording the reason.
+    pub fn disable_with_reason<S: Into<String>>(&mut self, reason: S) -> Result<()> {
+        self.update(protocol::ComputeServiceUpdate {
+            disabled_reason: Some(reason.into()),
+            status: Some(protocol::ServiceStatus::Disabled),
+            ..Default::default()
+        })
+    }
+
+    /// Enable the service.
+    pub fn enable(&mut self) -> Result<()> {
+        self.update(protocol::ComputeServiceUpdate {
+            status: Some(protocol::ServiceStatus::Enabled),
+            ..Default::default()
+        })
+    }
+
+    /// Force the service down (or clear the forced down flag).
+    ///
+    /// Requires API version 2.11.
+    pub fn set_forced_down(&mut self, forced_down: bool) -> Result<()> {
+        self.update(protocol::ComputeServiceUpdate {
+            forced_down: Some(forced_down),
+            ..Default::default()
+        })
+    }
+
+    fn update(&mut self, update: protocol::ComputeServiceUpdate) -> Result<()> {
+        self.inner = api::update_compute_service(&self.session, &self.inner, update)?;
+        Ok(())
+    }
+}
+
+impl ComputeServiceQuery {
+    pub(crate) fn new(session: Rc<Session>) -> ComputeServiceQuery {
+        ComputeServiceQuery {
+            session,
+            query: Query::new(),
+        }
+    }
+
+    query_filter! {
+        #[doc = "Filter by binary name (e.g. `nova-compute`)."]
+        set_binary, with_binary -> binary
+    }
+
+    query_filter! {
+        #[doc = "Filter by host name."]
+        set_host, with_host -> host
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<ComputeServiceQuery> {
+        debug!("Fetching compute services with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<ComputeService>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(self) -> Result<ComputeService> {
+        debug!("Fetching one compute service with {:?}", self.query);
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for ComputeServiceQuery {
+    type Item = ComputeService;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        // The services API does not support pagination.
+        Ok(false)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_compute_services(&self.session, &query)?
+            .into_iter()
+            .map(|item| ComputeService {
+                session: self.session.clone(),
+                inner: item,
+            })
+            .collect())
+    }
+}
+
+impl IntoFallibleIterator for ComputeServiceQuery {
+    type Item = ComputeService;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<ComputeServiceQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
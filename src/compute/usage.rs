@@ -0,0 +1,330 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Usage reports via Compute API.
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+
+use super::super::common::{IntoVerified, ProjectRef};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result};
+use super::{api, protocol};
+
+/// Usage of a single server over a period of time.
+#[derive(Clone, Debug)]
+pub struct ServerUsage {
+    inner: protocol::ServerUsage,
+}
+
+/// Usage of a project over a period of time.
+#[derive(Clone, Debug)]
+pub struct ProjectUsage {
+    inner: protocol::ProjectUsage,
+    server_usages: Vec<ServerUsage>,
+}
+
+impl ServerUsage {
+    transparent_property! {
+        #[doc = "Root disk size in GiB."]
+        disk_gb: u32
+    }
+
+    /// Disk usage in GiB-hours.
+    pub fn disk_gb_hours(&self) -> f64 {
+        f64::from(self.inner.disk_gb) * self.inner.hours
+    }
+
+    transparent_property! {
+        #[doc = "When the server was deleted (if it was deleted during the period)."]
+        ended_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Name of the server's flavor."]
+        flavor: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Number of hours the server was running during the period."]
+        hours: f64
+    }
+
+    transparent_property! {
+        #[doc = "Server ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "RAM size in MiB."]
+        memory_mb: u32
+    }
+
+    /// RAM usage in MiB-hours.
+    pub fn memory_mb_hours(&self) -> f64 {
+        f64::from(self.inner.memory_mb) * self.inner.hours
+    }
+
+    transparent_property! {
+        #[doc = "Server name."]
+        name: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Project the server belongs to."]
+        project_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "When the server was created."]
+        started_at: DateTime<FixedOffset>
+    }
+
+    transparent_property! {
+        #[doc = "Server state as a string (e.g. \"active\" or \"terminated\")."]
+        state: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Server uptime in seconds."]
+        uptime: u64
+    }
+
+    transparent_property! {
+        #[doc = "Number of virtual CPUs."]
+        vcpus: u32
+    }
+
+    /// VCPU usage in VCPU-hours.
+    pub fn vcpu_hours(&self) -> f64 {
+        f64::from(self.inner.vcpus) * self.inner.hours
+    }
+}
+
+impl ProjectUsage {
+    fn new(mut inner: protocol::ProjectUsage) -> ProjectUsage {
+        let server_usages = inner
+            .server_usages
+            .drain(..)
+            .map(|inner| ServerUsage { inner })
+            .collect();
+        ProjectUsage {
+            inner,
+            server_usages,
+        }
+    }
+
+    fn empty<Tz: TimeZone>(project_id: String, start: &DateTime<Tz>, end: &DateTime<Tz>) -> Self {
+        ProjectUsage::new(protocol::ProjectUsage {
+            project_id,
+            server_usages: Vec::new(),
+            start: start.with_timezone(&Utc).into(),
+            stop: end.with_timezone(&Utc).into(),
+            total_hours: 0.0,
+            total_disk_gb_hours: 0.0,
+            total_memory_mb_hours: 0.0,
+            total_vcpu_hours: 0.0,
+        })
+    }
+
+    fn merge(&mut self, other: ProjectUsage) {
+        self.server_usages.extend(other.server_usages);
+        self.inner.total_hours += other.inner.total_hours;
+        self.inner.total_disk_gb_hours += other.inner.total_disk_gb_hours;
+        self.inner.total_memory_mb_hours += other.inner.total_memory_mb_hours;
+        self.inner.total_vcpu_hours += other.inner.total_vcpu_hours;
+    }
+
+    transparent_property! {
+        #[doc = "Project ID."]
+        project_id: ref String
+    }
+
+    /// Usage of individual servers.
+    #[inline]
+    pub fn server_usages(&self) -> &Vec<ServerUsage> {
+        &self.server_usages
+    }
+
+    transparent_property! {
+        #[doc = "Beginning of the period."]
+        start: DateTime<FixedOffset>
+    }
+
+    transparent_property! {
+        #[doc = "End of the period."]
+        stop: DateTime<FixedOffset>
+    }
+
+    transparent_property! {
+        #[doc = "Total disk usage in GiB-hours."]
+        total_disk_gb_hours: f64
+    }
+
+    transparent_property! {
+        #[doc = "Total server hours."]
+        total_hours: f64
+    }
+
+    transparent_property! {
+        #[doc = "Total RAM usage in MiB-hours."]
+        total_memory_mb_hours: f64
+    }
+
+    transparent_property! {
+        #[doc = "Total VCPU usage in VCPU-hours."]
+        total_vcpu_hours: f64
+    }
+}
+
+/// Merge a page of project usages into the result.
+///
+/// Usage of one project can be split between pages.
+fn merge_usages(result: &mut Vec<ProjectUsage>, chunk: Vec<protocol::ProjectUsage>) {
+    for item in chunk {
+        let item = ProjectUsage::new(item);
+        match result
+            .iter_mut()
+            .find(|existing| existing.inner.project_id == item.inner.project_id)
+        {
+            Some(existing) => existing.merge(item),
+            None => result.push(item),
+        }
+    }
+}
+
+fn usage_query<Tz: TimeZone>(start: &DateTime<Tz>, end: &DateTime<Tz>) -> Result<Query> {
+    if end <= start {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "End of the usage period must be after its start",
+        ));
+    }
+
+    // NOTE: Nova expects naive timestamps in UTC.
+    let format = "%Y-%m-%dT%H:%M:%S%.f";
+    let mut query = Query::new();
+    query.push_str("start", start.naive_utc().format(format).to_string());
+    query.push_str("end", end.naive_utc().format(format).to_string());
+    query.push("detailed", 1);
+    Ok(query)
+}
+
+/// Get usage of all projects over a period of time.
+pub(crate) fn compute_usage<Tz: TimeZone>(
+    session: &Session,
+    start: &DateTime<Tz>,
+    end: &DateTime<Tz>,
+) -> Result<Vec<ProjectUsage>> {
+    let query = usage_query(start, end)?;
+    let mut result: Vec<ProjectUsage> = Vec::new();
+    let mut marker = None;
+    loop {
+        let (chunk, next) =
+            api::list_project_usages(session, &query.with_marker_and_limit(None, marker))?;
+        merge_usages(&mut result, chunk);
+
+        if next.is_none() {
+            return Ok(result);
+        }
+        marker = next;
+    }
+}
+
+/// Get usage of a project over a period of time.
+pub(crate) fn project_usage<Tz: TimeZone>(
+    session: &Session,
+    project: ProjectRef,
+    start: &DateTime<Tz>,
+    end: &DateTime<Tz>,
+) -> Result<ProjectUsage> {
+    let project_id: String = project.into_verified(session)?.into();
+    let query = usage_query(start, end)?;
+    let mut result: Option<ProjectUsage> = None;
+    let mut marker = None;
+    loop {
+        let (chunk, next) = api::get_project_usage(
+            session,
+            &project_id,
+            &query.with_marker_and_limit(None, marker),
+        )?;
+        if let Some(item) = chunk {
+            let item = ProjectUsage::new(item);
+            match result {
+                Some(ref mut existing) => existing.merge(item),
+                None => result = Some(item),
+            }
+        }
+
+        if next.is_none() {
+            return Ok(result.unwrap_or_else(|| ProjectUsage::empty(project_id, start, end)));
+        }
+        marker = next;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json;
+
+    use super::super::protocol;
+    use super::merge_usages;
+
+    fn usage(project_id: &str, server_id: &str, hours: f64) -> protocol::ProjectUsage {
+        serde_json::from_value(serde_json::json!({
+            "tenant_id": project_id,
+            "server_usages": [{
+                "ended_at": null,
+                "flavor": "m1.small",
+                "hours": hours,
+                "instance_id": server_id,
+                "local_gb": 10,
+                "memory_mb": 2048,
+                "name": server_id,
+                "tenant_id": project_id,
+                "started_at": "2019-01-01T00:00:00.000000",
+                "state": "active",
+                "uptime": 3600,
+                "vcpus": 2
+            }],
+            "start": "2019-01-01T00:00:00.000000",
+            "stop": "2019-01-02T00:00:00.000000",
+            "total_hours": hours,
+            "total_local_gb_usage": hours * 10.0,
+            "total_memory_mb_usage": hours * 2048.0,
+            "total_vcpus_usage": hours * 2.0
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_usages() {
+        let mut result = Vec::new();
+        merge_usages(
+            &mut result,
+            vec![usage("p1", "s1", 1.0), usage("p2", "s2", 2.0)],
+        );
+        merge_usages(&mut result, vec![usage("p1", "s3", 4.0)]);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].project_id(), "p1");
+        assert_eq!(result[0].server_usages().len(), 2);
+        assert_eq!(result[0].total_hours(), 5.0);
+        assert_eq!(result[0].total_vcpu_hours(), 10.0);
+        assert_eq!(result[0].server_usages()[1].id(), "s3");
+        assert_eq!(result[0].server_usages()[1].vcpu_hours(), 8.0);
+        assert_eq!(result[0].server_usages()[1].disk_gb_hours(), 40.0);
+        assert_eq!(result[1].project_id(), "p2");
+        assert_eq!(result[1].total_memory_mb_hours(), 4096.0);
+    }
+}
@@ -19,8 +19,8 @@ use std::fmt::Debug;
 use osauth::services::IMAGE;
 use serde::Serialize;
 
-use super::super::session::Session;
-use super::super::utils::{self, ResultExt};
+use super::super::common::PartialResource;
+use super::super::utils::{self, ResultExt, Session};
 use super::super::Result;
 use super::protocol::*;
 
@@ -62,3 +62,14 @@ pub fn list_images<Q: Serialize + Sync + Debug>(
     trace!("Received images: {:?}", root.images);
     Ok(root.images)
 }
+
+/// List images as partial resources.
+pub fn list_partial_images<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<PartialResource>> {
+    trace!("Listing partial images with {:?}", query);
+    let root: PartialImagesRoot = session.get_json_query(IMAGE, &["images"], query, None)?;
+    trace!("Received partial images: {:?}", root.images);
+    Ok(root.images)
+}
@@ -19,9 +19,11 @@ use std::rc::Rc;
 use chrono::{DateTime, FixedOffset};
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 
-use super::super::common::{ImageRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery};
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::common::{
+    ImageRef, IntoVerified, PartialQuery, PartialResource, ProjectableQuery, Refresh,
+    ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
 use super::super::{Error, Result, Sort};
 use super::{api, protocol};
 
@@ -195,6 +197,20 @@ impl ImageQuery {
         ResourceIterator::new(self)
     }
 
+    /// Request only the given fields of the images.
+    ///
+    /// Returns a query yielding lightweight `PartialResource` objects. The `id`
+    /// field is always requested. The Image API does not support projection,
+    /// so complete records are still received, but only the requested fields
+    /// are kept in memory.
+    pub fn with_fields<S: AsRef<str>>(mut self, fields: &[S]) -> PartialQuery<ImageQuery> {
+        if !self.sort.is_empty() {
+            self.query.push_str("sort", self.sort.join(","));
+            self.sort.clear();
+        }
+        PartialQuery::new(self, fields)
+    }
+
     /// Execute this request and return all results.
     ///
     /// A convenience shortcut for `self.into_iter().collect()`.
@@ -243,6 +259,22 @@ impl ResourceQuery for ImageQuery {
     }
 }
 
+impl ProjectableQuery for ImageQuery {
+    fn fetch_partial_chunk(
+        &self,
+        fields: &[String],
+        limit: Option<usize>,
+        marker: Option<String>,
+    ) -> Result<Vec<PartialResource>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        let mut result = api::list_partial_images(&self.session, &query)?;
+        for item in &mut result {
+            item.retain_fields(fields);
+        }
+        Ok(result)
+    }
+}
+
 impl IntoFallibleIterator for ImageQuery {
     type Item = Image;
 
@@ -272,3 +304,40 @@ impl IntoVerified for ImageRef {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::json;
+
+    use super::super::super::utils::test::mock_session;
+    use super::ImageQuery;
+
+    #[test]
+    fn test_with_fields() {
+        let (session, requests) = mock_session("2.1", |_| {
+            (
+                200,
+                json!({ "images": [{
+                    "id": "image-id",
+                    "name": "cirros",
+                    "size": 12345,
+                    "status": "active",
+                }] }),
+            )
+        });
+        let image = ImageQuery::new(Rc::new(session))
+            .with_fields(&["status"])
+            .one()
+            .unwrap();
+        assert_eq!(image.id(), "image-id");
+        assert!(image.name().is_none());
+        assert_eq!(image.field("status").unwrap(), "active");
+        assert_eq!(image.fields().len(), 1);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/v2.1/images?limit=2");
+    }
+}
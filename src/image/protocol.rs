@@ -126,3 +126,9 @@ pub struct Image {
 pub struct ImagesRoot {
     pub images: Vec<Image>,
 }
+
+/// A list of images as partial resources.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PartialImagesRoot {
+    pub images: Vec<common::PartialResource>,
+}
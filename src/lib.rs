@@ -146,12 +146,16 @@
     clippy::wrong_self_convention
 )]
 
+#[cfg(feature = "windows-password")]
+extern crate base64;
 extern crate chrono;
 extern crate eui48;
 extern crate fallible_iterator;
 extern crate ipnet;
 #[macro_use]
 extern crate log;
+#[cfg(feature = "windows-password")]
+extern crate openssl;
 extern crate osauth;
 extern crate reqwest;
 extern crate serde;
@@ -340,6 +344,7 @@ macro_rules! update_field {
 
     ($(#[$attr:meta])* $set_func:ident, $with_func:ident -> $name:ident) => (
         $(#[$attr])*
+        #[allow(unused_results)]
         pub fn $set_func<S: Into<String>>(&mut self, value: S)  {
             self.inner.$name = value.into();
             self.dirty.insert(stringify!($name));
@@ -453,6 +458,240 @@ macro_rules! save_fields {
     }
 }
 
+#[allow(unused_macros)]
+macro_rules! tag_query_filters {
+    () => {
+        /// Filter by tags (all of them must be present).
+        pub fn set_tags<I>(&mut self, tags: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+            self.query.push_str("tags", tags.join(","));
+        }
+
+        /// Filter by tags (all of them must be present).
+        #[inline]
+        pub fn with_tags<I>(mut self, tags: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            self.set_tags(tags);
+            self
+        }
+
+        /// Filter by tags (any of them must be present).
+        pub fn set_tags_any<I>(&mut self, tags: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+            self.query.push_str("tags-any", tags.join(","));
+        }
+
+        /// Filter by tags (any of them must be present).
+        #[inline]
+        pub fn with_tags_any<I>(mut self, tags: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            self.set_tags_any(tags);
+            self
+        }
+
+        /// Filter out by tags (resources with all of them are excluded).
+        pub fn set_without_tags<I>(&mut self, tags: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+            self.query.push_str("not-tags", tags.join(","));
+        }
+
+        /// Filter out by tags (resources with all of them are excluded).
+        #[inline]
+        pub fn without_tags<I>(mut self, tags: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            self.set_without_tags(tags);
+            self
+        }
+
+        /// Filter out by tags (resources with any of them are excluded).
+        pub fn set_without_tags_any<I>(&mut self, tags: I)
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+            self.query.push_str("not-tags-any", tags.join(","));
+        }
+
+        /// Filter out by tags (resources with any of them are excluded).
+        #[inline]
+        pub fn without_tags_any<I>(mut self, tags: I) -> Self
+        where
+            I: IntoIterator,
+            I::Item: Into<String>,
+        {
+            self.set_without_tags_any(tags);
+            self
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! taggable_resource {
+    ($type:ident -> $resource_type:expr) => {
+        impl $crate::common::Taggable for $type {
+            fn tags(&self) -> &Vec<String> {
+                &self.inner.tags
+            }
+
+            fn add_tag<S: Into<String>>(&mut self, tag: S) -> $crate::Result<()> {
+                let tag = tag.into();
+                super::api::add_tag(&self.session, $resource_type, &self.inner.id, &tag)?;
+                if !self.inner.tags.contains(&tag) {
+                    self.inner.tags.push(tag);
+                }
+                Ok(())
+            }
+
+            fn clear_tags(&mut self) -> $crate::Result<()> {
+                super::api::delete_tags(&self.session, $resource_type, &self.inner.id)?;
+                self.inner.tags.clear();
+                Ok(())
+            }
+
+            fn refresh_tags(&mut self) -> $crate::Result<()> {
+                self.inner.tags =
+                    super::api::list_tags(&self.session, $resource_type, &self.inner.id)?;
+                Ok(())
+            }
+
+            fn remove_tag<S: AsRef<str>>(&mut self, tag: S) -> $crate::Result<()> {
+                super::api::delete_tag(&self.session, $resource_type, &self.inner.id, &tag)?;
+                self.inner.tags.retain(|item| item != tag.as_ref());
+                Ok(())
+            }
+
+            fn replace_tags<I>(&mut self, tags: I) -> $crate::Result<()>
+            where
+                I: IntoIterator,
+                I::Item: Into<String>,
+            {
+                let tags = tags.into_iter().map(Into::into).collect();
+                self.inner.tags =
+                    super::api::replace_tags(&self.session, $resource_type, &self.inner.id, tags)?;
+                Ok(())
+            }
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! revisioned_resource {
+    ($type:ident -> $name:expr) => {
+        impl $type {
+            /// Save the changes if the resource has not been modified since it was fetched.
+            ///
+            /// The update is conditional on the current `revision_number`. If the
+            /// resource has been updated by someone else in the meantime, the call
+            /// fails and the changes are kept, so that they can be re-applied after
+            /// a [refresh](#method.refresh).
+            pub fn save_if_unchanged(&mut self) -> $crate::Result<()> {
+                let revision = self.inner.revision_number.ok_or_else(|| {
+                    $crate::Error::new(
+                        $crate::ErrorKind::InvalidInput,
+                        concat!("Revision number is not available for this ", $name),
+                    )
+                })?;
+                self.save_revision(Some(revision))
+            }
+        }
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! projectable_query {
+    ($type:ident: [$($path:expr),+] -> $key:expr $(, $ext:ident: $feature:expr)?) => {
+        impl $type {
+            /// Request only the given fields of the resources.
+            ///
+            /// Returns a query yielding lightweight `PartialResource` objects.
+            /// The `id` field is always requested.
+            pub fn with_fields<S: AsRef<str>>(
+                self,
+                fields: &[S],
+            ) -> $crate::common::PartialQuery<$type> {
+                $crate::common::PartialQuery::new(self, fields)
+            }
+        }
+
+        impl $crate::common::ProjectableQuery for $type {
+            fn fetch_partial_chunk(
+                &self,
+                fields: &[String],
+                limit: Option<usize>,
+                marker: Option<String>,
+            ) -> $crate::Result<Vec<$crate::common::PartialResource>> {
+                let mut query = self.query.with_marker_and_limit(limit, marker);
+                for field in fields {
+                    query.push_str("fields", field.clone());
+                }
+                $(super::api::require_extension(
+                    &self.session,
+                    super::protocol::NetworkExtension::$ext,
+                    $feature,
+                )?;)?
+                super::api::list_partial(&self.session, &[$($path),+], $key, &query)
+            }
+        }
+    };
+
+    ($type:ident: $path_func:ident() -> $key:expr $(, $ext:ident: $feature:expr)?) => {
+        impl $type {
+            /// Request only the given fields of the resources.
+            ///
+            /// Returns a query yielding lightweight `PartialResource` objects.
+            /// The `id` field is always requested.
+            pub fn with_fields<S: AsRef<str>>(
+                self,
+                fields: &[S],
+            ) -> $crate::common::PartialQuery<$type> {
+                $crate::common::PartialQuery::new(self, fields)
+            }
+        }
+
+        impl $crate::common::ProjectableQuery for $type {
+            fn fetch_partial_chunk(
+                &self,
+                fields: &[String],
+                limit: Option<usize>,
+                marker: Option<String>,
+            ) -> $crate::Result<Vec<$crate::common::PartialResource>> {
+                let mut query = self.query.with_marker_and_limit(limit, marker);
+                for field in fields {
+                    query.push_str("fields", field.clone());
+                }
+                $(super::api::require_extension(
+                    &self.session,
+                    super::protocol::NetworkExtension::$ext,
+                    $feature,
+                )?;)?
+                super::api::list_partial(&self.session, &self.$path_func(), $key, &query)
+            }
+        }
+    };
+}
+
 #[allow(unused_macros)]
 macro_rules! protocol_enum {
     {$(#[$attr:meta])* enum $name:ident: $carrier:ty {
@@ -557,6 +796,74 @@ macro_rules! protocol_enum {
             }
         }
     );
+
+    {$(#[$attr:meta])* enum $name:ident {
+        $($(#[$iattr:meta])* $item:ident = $val:expr),+;
+        $(#[$oattr:meta])* $other:ident(String)
+    }} => (
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum $name {
+            $($(#[$iattr])* $item),+,
+            $(#[$oattr])* $other(String),
+        }
+
+        impl $name {
+            fn as_ref(&self) -> &str {
+                match *self {
+                    $($name::$item => $val),+,
+                    $name::$other(ref value) => value,
+                }
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            fn from(value: &'a str) -> $name {
+                match value {
+                    $($val => $name::$item),+,
+                    other => $name::$other(String::from(other)),
+                }
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> $name {
+                match value.as_ref() {
+                    $($val => $name::$item),+,
+                    _ => $name::$other(value),
+                }
+            }
+        }
+
+        impl<'de> ::serde::de::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                    where D: ::serde::de::Deserializer<'de> {
+                Ok(String::deserialize(deserializer)?.into())
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.write_str(self.as_ref())
+            }
+        }
+
+        impl ::serde::ser::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                    where S: ::serde::ser::Serializer {
+                serializer.serialize_str(self.as_ref())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> String {
+                match value {
+                    $name::$other(value) => value,
+                    other => String::from(other.as_ref()),
+                }
+            }
+        }
+    );
 }
 
 /// Reimports of authentication bits from `osauth`.
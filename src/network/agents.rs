@@ -0,0 +1,363 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Network agents management via Network API.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use serde_json::Value;
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, NetworkRef, Refresh, ResourceIterator, ResourceQuery, RouterRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, NetworkQuery, RouterQuery};
+
+/// A query to network agent list.
+#[derive(Clone, Debug)]
+pub struct AgentQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single network agent.
+#[derive(Clone, Debug)]
+pub struct Agent {
+    session: Rc<Session>,
+    inner: protocol::Agent,
+    dirty: HashSet<&'static str>,
+}
+
+impl Agent {
+    /// Create an agent object.
+    fn new(session: Rc<Session>, inner: protocol::Agent) -> Agent {
+        Agent {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an Agent object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Agent> {
+        let inner = api::get_agent(&session, id)?;
+        Ok(Agent::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "The administrative state of the agent."]
+        admin_state_up: bool
+    }
+
+    update_field! {
+        #[doc = "Set the administrative state of the agent."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    transparent_property! {
+        #[doc = "Agent type (e.g. `DHCP agent`)."]
+        agent_type: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Whether the agent has reported its state recently."]
+        alive: bool
+    }
+
+    transparent_property! {
+        #[doc = "Availability zone of the agent (if any)."]
+        availability_zone: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Name of the agent executable."]
+        binary: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Agent-specific configuration."]
+        configurations: ref HashMap<String, Value>
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Agent description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Date and time of the last heartbeat (if available)."]
+        heartbeat_timestamp: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Host the agent runs on."]
+        host: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Date and time the agent was started (if available)."]
+        started_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Message queue topic of the agent (if any)."]
+        topic: ref Option<String>
+    }
+
+    /// Schedule a network to this DHCP agent.
+    pub fn add_dhcp_network<N>(&self, network: N) -> Result<()>
+    where
+        N: Into<NetworkRef>,
+    {
+        let network = network.into().into_verified(&self.session)?;
+        api::add_network_to_dhcp_agent(&self.session, &self.inner.id, network)
+    }
+
+    /// Remove a network from this DHCP agent.
+    pub fn remove_dhcp_network<N>(&self, network: N) -> Result<()>
+    where
+        N: Into<NetworkRef>,
+    {
+        let network = network.into().into_verified(&self.session)?;
+        api::remove_network_from_dhcp_agent(&self.session, &self.inner.id, network)
+    }
+
+    /// Networks hosted by this DHCP agent.
+    pub fn dhcp_networks(&self) -> NetworkQuery {
+        NetworkQuery::new(self.session.clone()).with_dhcp_agent(self.inner.id.clone())
+    }
+
+    /// Schedule a router to this L3 agent.
+    pub fn add_l3_router<R>(&self, router: R) -> Result<()>
+    where
+        R: Into<RouterRef>,
+    {
+        let router = router.into().into_verified(&self.session)?;
+        api::add_router_to_l3_agent(&self.session, &self.inner.id, router)
+    }
+
+    /// Remove a router from this L3 agent.
+    pub fn remove_l3_router<R>(&self, router: R) -> Result<()>
+    where
+        R: Into<RouterRef>,
+    {
+        let router = router.into().into_verified(&self.session)?;
+        api::remove_router_from_l3_agent(&self.session, &self.inner.id, router)
+    }
+
+    /// Routers hosted by this L3 agent.
+    pub fn l3_routers(&self) -> RouterQuery {
+        RouterQuery::new(self.session.clone()).with_l3_agent(self.inner.id.clone())
+    }
+
+    /// Delete the agent record.
+    pub fn delete(self) -> Result<DeletionWaiter<Agent>> {
+        api::delete_agent(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the agent is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the agent.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::AgentUpdate::default();
+        save_fields! {
+            self -> update: admin_state_up
+        };
+        save_option_fields! {
+            self -> update: description
+        };
+        let inner = api::update_agent(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for Agent {
+    /// Refresh the agent.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_agent(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl AgentQuery {
+    pub(crate) fn new(session: Rc<Session>) -> AgentQuery {
+        AgentQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::AgentSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by administrative state."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by agent type (e.g. `L3 agent`)."]
+        set_agent_type, with_agent_type -> agent_type
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the agent is alive."]
+        set_alive, with_alive -> alive: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by availability zone."]
+        set_availability_zone, with_availability_zone -> availability_zone
+    }
+
+    query_filter! {
+        #[doc = "Filter by agent executable name."]
+        set_binary, with_binary -> binary
+    }
+
+    query_filter! {
+        #[doc = "Filter by host."]
+        set_host, with_host -> host
+    }
+
+    query_filter! {
+        #[doc = "Filter by message queue topic."]
+        set_topic, with_topic -> topic
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<AgentQuery> {
+        debug!("Fetching network agents with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Agent>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<Agent> {
+        debug!("Fetching one network agent with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for AgentQuery {
+    type Item = Agent;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_agents(&self.session, &query)?
+            .into_iter()
+            .map(|item| Agent::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(AgentQuery: ["agents"] -> "agents", Agent: "Network agents");
+
+impl IntoFallibleIterator for AgentQuery {
+    type Item = Agent;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<AgentQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
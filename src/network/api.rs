@@ -14,19 +14,189 @@
 
 //! Foundation bits exposing the Network API.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use osauth::services::NETWORK;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use super::super::session::Session;
-use super::super::utils::{self, ResultExt};
-use super::super::Result;
+use super::super::common::PartialResource;
+use super::super::utils::{self, ResultExt, Session};
+use super::super::{Error, ErrorKind, Result};
 use super::protocol::*;
 
+/// Schedule a network to a DHCP agent.
+pub fn add_network_to_dhcp_agent<S1, S2>(
+    session: &Session,
+    agent_id: S1,
+    network_id: S2,
+) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    debug!(
+        "Adding network {} to DHCP agent {}",
+        network_id.as_ref(),
+        agent_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::DhcpAgentScheduler,
+        "DHCP agent scheduling",
+    )?;
+    let body = DhcpAgentNetwork {
+        network_id: network_id.as_ref().to_string(),
+    };
+    let _ = session.post(
+        NETWORK,
+        &["agents", agent_id.as_ref(), "dhcp-networks"],
+        body,
+        None,
+    )?;
+    debug!(
+        "Network {} was added to DHCP agent {}",
+        network_id.as_ref(),
+        agent_id.as_ref()
+    );
+    Ok(())
+}
+
+/// Schedule a router to an L3 agent.
+pub fn add_router_to_l3_agent<S1, S2>(session: &Session, agent_id: S1, router_id: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    debug!(
+        "Adding router {} to L3 agent {}",
+        router_id.as_ref(),
+        agent_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::L3AgentScheduler,
+        "L3 agent scheduling",
+    )?;
+    let body = L3AgentRouter {
+        router_id: router_id.as_ref().to_string(),
+    };
+    let _ = session.post(
+        NETWORK,
+        &["agents", agent_id.as_ref(), "l3-routers"],
+        body,
+        None,
+    )?;
+    debug!(
+        "Router {} was added to L3 agent {}",
+        router_id.as_ref(),
+        agent_id.as_ref()
+    );
+    Ok(())
+}
+
+/// Add a tag to a resource of the given type (e.g. `networks`).
+pub fn add_tag<S1, S2>(session: &Session, resource_type: &str, id: S1, tag: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    require_extension(session, NetworkExtension::StandardAttrTag, "Tags")?;
+    debug!(
+        "Adding tag {} to {} {}",
+        tag.as_ref(),
+        resource_type,
+        id.as_ref()
+    );
+    let _ = session.put_empty(
+        NETWORK,
+        &[resource_type, id.as_ref(), "tags", tag.as_ref()],
+        None,
+    )?;
+    debug!(
+        "Tag {} was added to {} {}",
+        tag.as_ref(),
+        resource_type,
+        id.as_ref()
+    );
+    Ok(())
+}
+
+/// Create an address scope.
+pub fn create_address_scope(session: &Session, request: AddressScope) -> Result<AddressScope> {
+    debug!("Creating a new address scope with {:?}", request);
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let body = AddressScopeRoot {
+        address_scope: request,
+    };
+    let root: AddressScopeRoot = session.post_json(NETWORK, &["address-scopes"], body, None)?;
+    debug!("Created address scope {:?}", root.address_scope);
+    Ok(root.address_scope)
+}
+
+/// Create a VPN endpoint group.
+pub fn create_endpoint_group(session: &Session, request: EndpointGroup) -> Result<EndpointGroup> {
+    debug!("Creating a new VPN endpoint group with {:?}", request);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = EndpointGroupRoot {
+        endpoint_group: request,
+    };
+    let root: EndpointGroupRoot =
+        session.post_json(NETWORK, &["vpn", "endpoint-groups"], body, None)?;
+    debug!("Created VPN endpoint group {:?}", root.endpoint_group);
+    Ok(root.endpoint_group)
+}
+
+/// Create a firewall group.
+pub fn create_firewall_group(session: &Session, request: FirewallGroup) -> Result<FirewallGroup> {
+    debug!("Creating a new firewall group with {:?}", request);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallGroupRoot {
+        firewall_group: request,
+    };
+    let root: FirewallGroupRoot =
+        session.post_json(NETWORK, &["fwaas", "firewall_groups"], body, None)?;
+    debug!("Created firewall group {:?}", root.firewall_group);
+    Ok(root.firewall_group)
+}
+
+/// Create a firewall policy.
+pub fn create_firewall_policy(
+    session: &Session,
+    request: FirewallPolicy,
+) -> Result<FirewallPolicy> {
+    debug!("Creating a new firewall policy with {:?}", request);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallPolicyRoot {
+        firewall_policy: request,
+    };
+    let root: FirewallPolicyRoot =
+        session.post_json(NETWORK, &["fwaas", "firewall_policies"], body, None)?;
+    debug!("Created firewall policy {:?}", root.firewall_policy);
+    Ok(root.firewall_policy)
+}
+
+/// Create a firewall rule.
+pub fn create_firewall_rule(session: &Session, request: FirewallRule) -> Result<FirewallRule> {
+    debug!("Creating a new firewall rule with {:?}", request);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallRuleRoot {
+        firewall_rule: request,
+    };
+    let root: FirewallRuleRoot =
+        session.post_json(NETWORK, &["fwaas", "firewall_rules"], body, None)?;
+    debug!("Created firewall rule {:?}", root.firewall_rule);
+    Ok(root.firewall_rule)
+}
+
 /// Create a floating IP.
 pub fn create_floating_ip(session: &Session, request: FloatingIp) -> Result<FloatingIp> {
     debug!("Creating a new floating IP with {:?}", request);
+    if request.dns_domain.is_some() || request.dns_name.is_some() {
+        require_extension(session, NetworkExtension::DnsIntegration, "DNS name")?;
+    }
     let body = FloatingIpRoot {
         floatingip: request,
     };
@@ -35,24 +205,270 @@ pub fn create_floating_ip(session: &Session, request: FloatingIp) -> Result<Floa
     Ok(root.floatingip)
 }
 
+/// Create an IKE policy.
+pub fn create_ike_policy(session: &Session, request: IkePolicy) -> Result<IkePolicy> {
+    debug!("Creating a new IKE policy with {:?}", request);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IkePolicyRoot { ikepolicy: request };
+    let root: IkePolicyRoot = session.post_json(NETWORK, &["vpn", "ikepolicies"], body, None)?;
+    debug!("Created IKE policy {:?}", root.ikepolicy);
+    Ok(root.ikepolicy)
+}
+
+/// Create an IPsec policy.
+pub fn create_ipsec_policy(session: &Session, request: IpsecPolicy) -> Result<IpsecPolicy> {
+    debug!("Creating a new IPsec policy with {:?}", request);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IpsecPolicyRoot {
+        ipsecpolicy: request,
+    };
+    let root: IpsecPolicyRoot =
+        session.post_json(NETWORK, &["vpn", "ipsecpolicies"], body, None)?;
+    debug!("Created IPsec policy {:?}", root.ipsecpolicy);
+    Ok(root.ipsecpolicy)
+}
+
+/// Create an IPsec site connection.
+pub fn create_ipsec_site_connection(
+    session: &Session,
+    request: IpsecSiteConnection,
+) -> Result<IpsecSiteConnection> {
+    debug!("Creating a new IPsec site connection with {:?}", request);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IpsecSiteConnectionRoot {
+        ipsec_site_connection: request,
+    };
+    let root: IpsecSiteConnectionRoot =
+        session.post_json(NETWORK, &["vpn", "ipsec-site-connections"], body, None)?;
+    debug!(
+        "Created IPsec site connection {:?}",
+        root.ipsec_site_connection
+    );
+    Ok(root.ipsec_site_connection)
+}
+
+/// Create a metering label.
+pub fn create_metering_label(session: &Session, request: MeteringLabel) -> Result<MeteringLabel> {
+    debug!("Creating a new metering label with {:?}", request);
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let body = MeteringLabelRoot {
+        metering_label: request,
+    };
+    let root: MeteringLabelRoot =
+        session.post_json(NETWORK, &["metering", "metering-labels"], body, None)?;
+    debug!("Created metering label {:?}", root.metering_label);
+    Ok(root.metering_label)
+}
+
+/// Create a metering label rule.
+pub fn create_metering_label_rule(
+    session: &Session,
+    request: MeteringLabelRule,
+) -> Result<MeteringLabelRule> {
+    debug!("Creating a new metering label rule with {:?}", request);
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let body = MeteringLabelRuleRoot {
+        metering_label_rule: request,
+    };
+    let root: MeteringLabelRuleRoot =
+        session.post_json(NETWORK, &["metering", "metering-label-rules"], body, None)?;
+    debug!("Created metering label rule {:?}", root.metering_label_rule);
+    Ok(root.metering_label_rule)
+}
+
 /// Create a network.
 pub fn create_network(session: &Session, request: Network) -> Result<Network> {
     debug!("Creating a new network with {:?}", request);
+    require_network_extensions(session, &request)?;
     let body = NetworkRoot { network: request };
     let root: NetworkRoot = session.post_json(NETWORK, &["networks"], body, None)?;
     debug!("Created network {:?}", root.network);
     Ok(root.network)
 }
 
+/// Create several networks in one request.
+pub fn create_networks(session: &Session, requests: Vec<Network>) -> Result<Vec<Network>> {
+    debug!("Creating new networks in bulk with {:?}", requests);
+    for request in &requests {
+        require_network_extensions(session, request)?;
+    }
+    let count = requests.len();
+    let body = NetworksRoot { networks: requests };
+    let root: NetworksRoot = session
+        .post_json(NETWORK, &["networks"], body, None)
+        .map_err(|err| bulk_rolled_back(err, count, "networks"))?;
+    debug!("Created networks {:?}", root.networks);
+    check_bulk_result(root.networks, count, "networks")
+}
+
+/// Create a port forwarding on a floating IP.
+pub fn create_port_forwarding<S: AsRef<str>>(
+    session: &Session,
+    floating_ip_id: S,
+    request: PortForwarding,
+) -> Result<PortForwarding> {
+    require_extension(
+        session,
+        NetworkExtension::FloatingIpPortForwarding,
+        "Port forwarding",
+    )?;
+    debug!(
+        "Creating a new port forwarding on floating IP {} with {:?}",
+        floating_ip_id.as_ref(),
+        request
+    );
+    let body = PortForwardingRoot {
+        port_forwarding: request,
+    };
+    let root: PortForwardingRoot = session.post_json(
+        NETWORK,
+        &["floatingips", floating_ip_id.as_ref(), "port_forwardings"],
+        body,
+        None,
+    )?;
+    debug!("Created port forwarding {:?}", root.port_forwarding);
+    Ok(root.port_forwarding)
+}
+
 /// Create a port.
 pub fn create_port(session: &Session, request: Port) -> Result<Port> {
     debug!("Creating a new port with {:?}", request);
+    if request.dns_domain.is_some() || request.dns_name.is_some() {
+        require_extension(session, NetworkExtension::DnsIntegration, "DNS name")?;
+    }
+    if request.port_security_enabled.is_some() {
+        require_extension(session, NetworkExtension::PortSecurity, "Port security")?;
+    }
     let body = PortRoot { port: request };
     let root: PortRoot = session.post_json(NETWORK, &["ports"], body, None)?;
     debug!("Created port {:?}", root.port);
     Ok(root.port)
 }
 
+/// Create several ports in one request.
+pub fn create_ports(session: &Session, requests: Vec<Port>) -> Result<Vec<Port>> {
+    debug!("Creating new ports in bulk with {:?}", requests);
+    if requests
+        .iter()
+        .any(|port| port.dns_domain.is_some() || port.dns_name.is_some())
+    {
+        require_extension(session, NetworkExtension::DnsIntegration, "DNS name")?;
+    }
+    if requests
+        .iter()
+        .any(|port| port.port_security_enabled.is_some())
+    {
+        require_extension(session, NetworkExtension::PortSecurity, "Port security")?;
+    }
+    let count = requests.len();
+    let body = PortsRoot { ports: requests };
+    let root: PortsRoot = session
+        .post_json(NETWORK, &["ports"], body, None)
+        .map_err(|err| bulk_rolled_back(err, count, "ports"))?;
+    debug!("Created ports {:?}", root.ports);
+    check_bulk_result(root.ports, count, "ports")
+}
+
+/// Create a QoS policy.
+pub fn create_qos_policy(session: &Session, request: QosPolicy) -> Result<QosPolicy> {
+    debug!("Creating a new QoS policy with {:?}", request);
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let body = QosPolicyRoot { policy: request };
+    let root: QosPolicyRoot = session.post_json(NETWORK, &["qos", "policies"], body, None)?;
+    debug!("Created QoS policy {:?}", root.policy);
+    Ok(root.policy)
+}
+
+/// Create a QoS rule of the given kind (e.g. `bandwidth_limit`).
+pub fn create_qos_rule<S, T>(session: &Session, policy_id: S, kind: &str, request: T) -> Result<T>
+where
+    S: AsRef<str>,
+    T: Serialize + DeserializeOwned + Debug + Send,
+{
+    debug!(
+        "Creating a new {} rule in QoS policy {} with {:?}",
+        kind,
+        policy_id.as_ref(),
+        request
+    );
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let key = format!("{}_rule", kind);
+    let mut body = HashMap::new();
+    let _ = body.insert(key.clone(), request);
+    let root: HashMap<String, T> = session.post_json(
+        NETWORK,
+        &[
+            "qos",
+            "policies",
+            policy_id.as_ref(),
+            &format!("{}_rules", kind),
+        ],
+        body,
+        None,
+    )?;
+    let result = extract_root(root, &key)?;
+    debug!("Created QoS rule {:?}", result);
+    Ok(result)
+}
+
+/// Create an RBAC policy.
+pub fn create_rbac_policy(session: &Session, request: RbacPolicy) -> Result<RbacPolicy> {
+    debug!("Creating a new RBAC policy with {:?}", request);
+    require_extension(session, NetworkExtension::Rbac, "RBAC policies")?;
+    let body = RbacPolicyRoot {
+        rbac_policy: request,
+    };
+    let root: RbacPolicyRoot = session.post_json(NETWORK, &["rbac-policies"], body, None)?;
+    debug!("Created RBAC policy {:?}", root.rbac_policy);
+    Ok(root.rbac_policy)
+}
+
+/// Create a router.
+pub fn create_router(session: &Session, request: Router) -> Result<Router> {
+    debug!("Creating a new router with {:?}", request);
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let body = RouterRoot { router: request };
+    let root: RouterRoot = session.post_json(NETWORK, &["routers"], body, None)?;
+    debug!("Created router {:?}", root.router);
+    Ok(root.router)
+}
+
+/// Create a security group.
+pub fn create_security_group(session: &Session, request: SecurityGroup) -> Result<SecurityGroup> {
+    debug!("Creating a new security group with {:?}", request);
+    let body = SecurityGroupRoot {
+        security_group: request,
+    };
+    let root: SecurityGroupRoot = session.post_json(NETWORK, &["security-groups"], body, None)?;
+    debug!("Created security group {:?}", root.security_group);
+    Ok(root.security_group)
+}
+
+/// Create a security group rule.
+pub fn create_security_group_rule(
+    session: &Session,
+    request: SecurityGroupRule,
+) -> Result<SecurityGroupRule> {
+    debug!("Creating a new security group rule with {:?}", request);
+    let body = SecurityGroupRuleRoot {
+        security_group_rule: request,
+    };
+    let root: SecurityGroupRuleRoot =
+        session.post_json(NETWORK, &["security-group-rules"], body, None)?;
+    debug!("Created security group rule {:?}", root.security_group_rule);
+    Ok(root.security_group_rule)
+}
+
+/// Create a network segment.
+pub fn create_segment(session: &Session, request: Segment) -> Result<Segment> {
+    debug!("Creating a new segment with {:?}", request);
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let body = SegmentRoot { segment: request };
+    let root: SegmentRoot = session.post_json(NETWORK, &["segments"], body, None)?;
+    debug!("Created segment {:?}", root.segment);
+    Ok(root.segment)
+}
+
 /// Create a subnet.
 pub fn create_subnet(session: &Session, request: Subnet) -> Result<Subnet> {
     debug!("Creating a new subnet with {:?}", request);
@@ -62,6 +478,132 @@ pub fn create_subnet(session: &Session, request: Subnet) -> Result<Subnet> {
     Ok(root.subnet)
 }
 
+/// Create a subnet pool.
+pub fn create_subnet_pool(session: &Session, request: SubnetPool) -> Result<SubnetPool> {
+    debug!("Creating a new subnet pool with {:?}", request);
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let body = SubnetPoolRoot {
+        subnetpool: request,
+    };
+    let root: SubnetPoolRoot = session.post_json(NETWORK, &["subnetpools"], body, None)?;
+    debug!("Created subnet pool {:?}", root.subnetpool);
+    Ok(root.subnetpool)
+}
+
+/// Create several subnets in one request.
+pub fn create_subnets(session: &Session, requests: Vec<Subnet>) -> Result<Vec<Subnet>> {
+    debug!("Creating new subnets in bulk with {:?}", requests);
+    let count = requests.len();
+    let body = SubnetsRoot { subnets: requests };
+    let root: SubnetsRoot = session
+        .post_json(NETWORK, &["subnets"], body, None)
+        .map_err(|err| bulk_rolled_back(err, count, "subnets"))?;
+    debug!("Created subnets {:?}", root.subnets);
+    check_bulk_result(root.subnets, count, "subnets")
+}
+
+/// Create a trunk.
+pub fn create_trunk(session: &Session, request: Trunk) -> Result<Trunk> {
+    debug!("Creating a new trunk with {:?}", request);
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let body = TrunkRoot { trunk: request };
+    let root: TrunkRoot = session.post_json(NETWORK, &["trunks"], body, None)?;
+    debug!("Created trunk {:?}", root.trunk);
+    Ok(root.trunk)
+}
+
+/// Create a VPN service.
+pub fn create_vpn_service(session: &Session, request: VpnService) -> Result<VpnService> {
+    debug!("Creating a new VPN service with {:?}", request);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = VpnServiceRoot {
+        vpnservice: request,
+    };
+    let root: VpnServiceRoot = session.post_json(NETWORK, &["vpn", "vpnservices"], body, None)?;
+    debug!("Created VPN service {:?}", root.vpnservice);
+    Ok(root.vpnservice)
+}
+
+/// Delete a network agent.
+pub fn delete_agent<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting agent {}", id.as_ref());
+    require_extension(session, NetworkExtension::Agent, "Network agents")?;
+    let _ = session.delete(NETWORK, &["agents", id.as_ref()], None)?;
+    debug!("Agent {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete an address scope.
+pub fn delete_address_scope<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting address scope {}", id.as_ref());
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let _ = session.delete(NETWORK, &["address-scopes", id.as_ref()], None)?;
+    debug!("Address scope {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete the auto-allocated topology of a project.
+pub fn delete_auto_allocated_topology<S: AsRef<str>>(
+    session: &Session,
+    project_id: S,
+) -> Result<()> {
+    debug!(
+        "Deleting auto-allocated topology of project {}",
+        project_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::AutoAllocatedTopology,
+        "Auto-allocated topology",
+    )?;
+    let _ = session.delete(
+        NETWORK,
+        &["auto-allocated-topology", project_id.as_ref()],
+        None,
+    )?;
+    debug!(
+        "Auto-allocated topology of project {} was deleted",
+        project_id.as_ref()
+    );
+    Ok(())
+}
+
+/// Delete a VPN endpoint group.
+pub fn delete_endpoint_group<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting VPN endpoint group {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["vpn", "endpoint-groups", id.as_ref()], None)?;
+    debug!("VPN endpoint group {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a firewall group.
+pub fn delete_firewall_group<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting firewall group {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["fwaas", "firewall_groups", id.as_ref()], None)?;
+    debug!("Firewall group {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a firewall policy.
+pub fn delete_firewall_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting firewall policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["fwaas", "firewall_policies", id.as_ref()], None)?;
+    debug!("Firewall policy {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a firewall rule.
+pub fn delete_firewall_rule<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting firewall rule {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["fwaas", "firewall_rules", id.as_ref()], None)?;
+    debug!("Firewall rule {} was deleted", id.as_ref());
+    Ok(())
+}
+
 /// Delete a floating IP.
 pub fn delete_floating_ip<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     debug!("Deleting floating IP {}", id.as_ref());
@@ -70,6 +612,59 @@ pub fn delete_floating_ip<S: AsRef<str>>(session: &Session, id: S) -> Result<()>
     Ok(())
 }
 
+/// Delete an IKE policy.
+pub fn delete_ike_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting IKE policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["vpn", "ikepolicies", id.as_ref()], None)?;
+    debug!("IKE policy {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete an IPsec policy.
+pub fn delete_ipsec_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting IPsec policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["vpn", "ipsecpolicies", id.as_ref()], None)?;
+    debug!("IPsec policy {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete an IPsec site connection.
+pub fn delete_ipsec_site_connection<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting IPsec site connection {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let _ = session.delete(
+        NETWORK,
+        &["vpn", "ipsec-site-connections", id.as_ref()],
+        None,
+    )?;
+    debug!("IPsec site connection {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a metering label.
+pub fn delete_metering_label<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting metering label {}", id.as_ref());
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let _ = session.delete(NETWORK, &["metering", "metering-labels", id.as_ref()], None)?;
+    debug!("Metering label {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a metering label rule.
+pub fn delete_metering_label_rule<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting metering label rule {}", id.as_ref());
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let _ = session.delete(
+        NETWORK,
+        &["metering", "metering-label-rules", id.as_ref()],
+        None,
+    )?;
+    debug!("Metering label rule {} was deleted", id.as_ref());
+    Ok(())
+}
+
 /// Delete a network.
 pub fn delete_network<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     debug!("Deleting network {}", id.as_ref());
@@ -78,6 +673,36 @@ pub fn delete_network<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     Ok(())
 }
 
+/// Delete a port forwarding from a floating IP.
+pub fn delete_port_forwarding<S1, S2>(session: &Session, floating_ip_id: S1, id: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    require_extension(
+        session,
+        NetworkExtension::FloatingIpPortForwarding,
+        "Port forwarding",
+    )?;
+    debug!(
+        "Deleting port forwarding {} from floating IP {}",
+        id.as_ref(),
+        floating_ip_id.as_ref()
+    );
+    let _ = session.delete(
+        NETWORK,
+        &[
+            "floatingips",
+            floating_ip_id.as_ref(),
+            "port_forwardings",
+            id.as_ref(),
+        ],
+        None,
+    )?;
+    debug!("Port forwarding {} was deleted", id.as_ref());
+    Ok(())
+}
+
 /// Delete a port.
 pub fn delete_port<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     debug!("Deleting port {}", id.as_ref());
@@ -86,6 +711,86 @@ pub fn delete_port<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     Ok(())
 }
 
+/// Delete a QoS policy.
+pub fn delete_qos_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting QoS policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let _ = session.delete(NETWORK, &["qos", "policies", id.as_ref()], None)?;
+    debug!("QoS policy {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a QoS rule of the given kind (e.g. `bandwidth_limit`).
+pub fn delete_qos_rule<S1, S2>(session: &Session, policy_id: S1, kind: &str, id: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    debug!(
+        "Deleting {} rule {} from QoS policy {}",
+        kind,
+        id.as_ref(),
+        policy_id.as_ref()
+    );
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let _ = session.delete(
+        NETWORK,
+        &[
+            "qos",
+            "policies",
+            policy_id.as_ref(),
+            &format!("{}_rules", kind),
+            id.as_ref(),
+        ],
+        None,
+    )?;
+    debug!("QoS rule {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete an RBAC policy.
+pub fn delete_rbac_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting RBAC policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Rbac, "RBAC policies")?;
+    let _ = session.delete(NETWORK, &["rbac-policies", id.as_ref()], None)?;
+    debug!("RBAC policy {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a router.
+pub fn delete_router<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting router {}", id.as_ref());
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let _ = session.delete(NETWORK, &["routers", id.as_ref()], None)?;
+    debug!("Router {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a security group.
+pub fn delete_security_group<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting security group {}", id.as_ref());
+    let _ = session.delete(NETWORK, &["security-groups", id.as_ref()], None)?;
+    debug!("Security group {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a security group rule.
+pub fn delete_security_group_rule<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting security group rule {}", id.as_ref());
+    let _ = session.delete(NETWORK, &["security-group-rules", id.as_ref()], None)?;
+    debug!("Security group rule {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a network segment.
+pub fn delete_segment<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting segment {}", id.as_ref());
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let _ = session.delete(NETWORK, &["segments", id.as_ref()], None)?;
+    debug!("Segment {} was deleted", id.as_ref());
+    Ok(())
+}
+
 /// Delete a subnet.
 pub fn delete_subnet<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     debug!("Deleting subnet {}", id.as_ref());
@@ -94,120 +799,1130 @@ pub fn delete_subnet<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
     Ok(())
 }
 
-/// Get a floating IP.
-pub fn get_floating_ip<S: AsRef<str>>(session: &Session, id: S) -> Result<FloatingIp> {
-    trace!("Get floating IP by ID {}", id.as_ref());
-    let root: FloatingIpRoot = session.get_json(NETWORK, &["floatingips", id.as_ref()], None)?;
-    trace!("Received {:?}", root.floatingip);
-    Ok(root.floatingip)
+/// Delete a subnet pool.
+pub fn delete_subnet_pool<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting subnet pool {}", id.as_ref());
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let _ = session.delete(NETWORK, &["subnetpools", id.as_ref()], None)?;
+    debug!("Subnet pool {} was deleted", id.as_ref());
+    Ok(())
 }
 
-/// Get a network.
-pub fn get_network<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Network> {
+/// Delete a tag from a resource of the given type.
+pub fn delete_tag<S1, S2>(session: &Session, resource_type: &str, id: S1, tag: S2) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    require_extension(session, NetworkExtension::StandardAttrTag, "Tags")?;
+    debug!(
+        "Deleting tag {} from {} {}",
+        tag.as_ref(),
+        resource_type,
+        id.as_ref()
+    );
+    let _ = session.delete(
+        NETWORK,
+        &[resource_type, id.as_ref(), "tags", tag.as_ref()],
+        None,
+    )?;
+    debug!(
+        "Tag {} was deleted from {} {}",
+        tag.as_ref(),
+        resource_type,
+        id.as_ref()
+    );
+    Ok(())
+}
+
+/// Delete all tags from a resource of the given type.
+pub fn delete_tags<S: AsRef<str>>(session: &Session, resource_type: &str, id: S) -> Result<()> {
+    require_extension(session, NetworkExtension::StandardAttrTag, "Tags")?;
+    debug!("Deleting all tags from {} {}", resource_type, id.as_ref());
+    let _ = session.delete(NETWORK, &[resource_type, id.as_ref(), "tags"], None)?;
+    debug!(
+        "All tags were deleted from {} {}",
+        resource_type,
+        id.as_ref()
+    );
+    Ok(())
+}
+
+/// Delete a trunk.
+pub fn delete_trunk<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting trunk {}", id.as_ref());
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let _ = session.delete(NETWORK, &["trunks", id.as_ref()], None)?;
+    debug!("Trunk {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Delete a VPN service.
+pub fn delete_vpn_service<S: AsRef<str>>(session: &Session, id: S) -> Result<()> {
+    debug!("Deleting VPN service {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let _ = session.delete(NETWORK, &["vpn", "vpnservices", id.as_ref()], None)?;
+    debug!("VPN service {} was deleted", id.as_ref());
+    Ok(())
+}
+
+/// Get a network agent.
+pub fn get_agent<S: AsRef<str>>(session: &Session, id: S) -> Result<Agent> {
+    trace!("Get agent {}", id.as_ref());
+    require_extension(session, NetworkExtension::Agent, "Network agents")?;
+    let root: AgentRoot = session.get_json(NETWORK, &["agents", id.as_ref()], None)?;
+    trace!("Received {:?}", root.agent);
+    Ok(root.agent)
+}
+
+/// Get an address scope.
+pub fn get_address_scope<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<AddressScope> {
     let s = id_or_name.as_ref();
-    get_network_by_id(session, s).if_not_found_then(|| get_network_by_name(session, s))
+    get_address_scope_by_id(session, s).if_not_found_then(|| get_address_scope_by_name(session, s))
 }
 
-/// Get a network by its ID.
-pub fn get_network_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Network> {
-    trace!("Get network by ID {}", id.as_ref());
-    let root: NetworkRoot = session.get_json(NETWORK, &["networks", id.as_ref()], None)?;
-    trace!("Received {:?}", root.network);
-    Ok(root.network)
+/// Get an address scope by its ID.
+pub fn get_address_scope_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<AddressScope> {
+    trace!("Get address scope by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let root: AddressScopeRoot =
+        session.get_json(NETWORK, &["address-scopes", id.as_ref()], None)?;
+    trace!("Received {:?}", root.address_scope);
+    Ok(root.address_scope)
 }
 
-/// Get a network by its name.
-pub fn get_network_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Network> {
-    trace!("Get network by name {}", name.as_ref());
-    let root: NetworksRoot =
-        session.get_json_query(NETWORK, &["networks"], &[("name", name.as_ref())], None)?;
+/// Get an address scope by its name.
+pub fn get_address_scope_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<AddressScope> {
+    trace!("Get address scope by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let root: AddressScopesRoot = session.get_json_query(
+        NETWORK,
+        &["address-scopes"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
     let result = utils::one(
-        root.networks,
-        "Network with given name or ID not found",
-        "Too many networks found with given name",
+        root.address_scopes,
+        "Address scope with given name or ID not found",
+        "Too many address scopes found with given name",
     )?;
     trace!("Received {:?}", result);
     Ok(result)
 }
 
-/// Get a port.
-pub fn get_port<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Port> {
+/// Get (allocating if needed) the auto-allocated topology of a project.
+pub fn get_auto_allocated_topology<S: AsRef<str>>(
+    session: &Session,
+    project_id: S,
+) -> Result<AutoAllocatedTopology> {
+    trace!(
+        "Get auto-allocated topology of project {}",
+        project_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::AutoAllocatedTopology,
+        "Auto-allocated topology",
+    )?;
+    let root: AutoAllocatedTopologyRoot = session.get_json(
+        NETWORK,
+        &["auto-allocated-topology", project_id.as_ref()],
+        None,
+    )?;
+    trace!("Received {:?}", root.auto_allocated_topology);
+    Ok(root.auto_allocated_topology)
+}
+
+/// Get a VPN endpoint group.
+pub fn get_endpoint_group<S: AsRef<str>>(
+    session: &Session,
+    id_or_name: S,
+) -> Result<EndpointGroup> {
     let s = id_or_name.as_ref();
-    get_port_by_id(session, s).if_not_found_then(|| get_port_by_name(session, s))
+    get_endpoint_group_by_id(session, s)
+        .if_not_found_then(|| get_endpoint_group_by_name(session, s))
 }
 
-/// Get a port by its ID.
-pub fn get_port_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Port> {
-    trace!("Get port by ID {}", id.as_ref());
-    let root: PortRoot = session.get_json(NETWORK, &["ports", id.as_ref()], None)?;
-    trace!("Received {:?}", root.port);
-    Ok(root.port)
+/// Get a VPN endpoint group by its ID.
+pub fn get_endpoint_group_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<EndpointGroup> {
+    trace!("Get VPN endpoint group by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: EndpointGroupRoot =
+        session.get_json(NETWORK, &["vpn", "endpoint-groups", id.as_ref()], None)?;
+    trace!("Received {:?}", root.endpoint_group);
+    Ok(root.endpoint_group)
 }
 
-/// Get a port by its name.
-pub fn get_port_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Port> {
-    trace!("Get port by name {}", name.as_ref());
-    let root: PortsRoot =
-        session.get_json_query(NETWORK, &["ports"], &[("name", name.as_ref())], None)?;
+/// Get a VPN endpoint group by its name.
+pub fn get_endpoint_group_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<EndpointGroup> {
+    trace!("Get VPN endpoint group by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: EndpointGroupsRoot = session.get_json_query(
+        NETWORK,
+        &["vpn", "endpoint-groups"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
     let result = utils::one(
-        root.ports,
-        "Port with given name or ID not found",
-        "Too many ports found with given name",
+        root.endpoint_groups,
+        "VPN endpoint group with given name or ID not found",
+        "Too many VPN endpoint groups found with given name",
     )?;
     trace!("Received {:?}", result);
     Ok(result)
 }
 
-/// Get a subnet.
-pub fn get_subnet<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Subnet> {
+/// Get a firewall group.
+pub fn get_firewall_group<S: AsRef<str>>(
+    session: &Session,
+    id_or_name: S,
+) -> Result<FirewallGroup> {
     let s = id_or_name.as_ref();
-    get_subnet_by_id(session, s).if_not_found_then(|| get_subnet_by_name(session, s))
+    get_firewall_group_by_id(session, s)
+        .if_not_found_then(|| get_firewall_group_by_name(session, s))
 }
 
-/// Get a subnet by its ID.
-pub fn get_subnet_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Subnet> {
-    trace!("Get subnet by ID {}", id.as_ref());
-    let root: SubnetRoot = session.get_json(NETWORK, &["subnets", id.as_ref()], None)?;
-    trace!("Received {:?}", root.subnet);
-    Ok(root.subnet)
+/// Get a firewall group by its ID.
+pub fn get_firewall_group_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<FirewallGroup> {
+    trace!("Get firewall group by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallGroupRoot =
+        session.get_json(NETWORK, &["fwaas", "firewall_groups", id.as_ref()], None)?;
+    trace!("Received {:?}", root.firewall_group);
+    Ok(root.firewall_group)
 }
 
-/// Get a subnet by its name.
-pub fn get_subnet_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Subnet> {
-    trace!("Get subnet by name {}", name.as_ref());
-    let root: SubnetsRoot =
-        session.get_json_query(NETWORK, &["subnets"], &[("name", name.as_ref())], None)?;
+/// Get a firewall group by its name.
+pub fn get_firewall_group_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<FirewallGroup> {
+    trace!("Get firewall group by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallGroupsRoot = session.get_json_query(
+        NETWORK,
+        &["fwaas", "firewall_groups"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
     let result = utils::one(
-        root.subnets,
-        "Subnet with given name or ID not found",
-        "Too many subnets found with given name",
+        root.firewall_groups,
+        "Firewall group with given name or ID not found",
+        "Too many firewall groups found with given name",
     )?;
     trace!("Received {:?}", result);
     Ok(result)
 }
 
-/// List floating IPs.
-pub fn list_floating_ips<Q: Serialize + Sync + Debug>(
+/// Get a firewall policy.
+pub fn get_firewall_policy<S: AsRef<str>>(
     session: &Session,
-    query: &Q,
-) -> Result<Vec<FloatingIp>> {
-    trace!("Listing floating IPs with {:?}", query);
-    let root: FloatingIpsRoot = session.get_json_query(NETWORK, &["floatingips"], query, None)?;
-    trace!("Received floating IPs: {:?}", root.floatingips);
-    Ok(root.floatingips)
+    id_or_name: S,
+) -> Result<FirewallPolicy> {
+    let s = id_or_name.as_ref();
+    get_firewall_policy_by_id(session, s)
+        .if_not_found_then(|| get_firewall_policy_by_name(session, s))
 }
 
-/// List networks.
-pub fn list_networks<Q: Serialize + Sync + Debug>(
+/// Get a firewall policy by its ID.
+pub fn get_firewall_policy_by_id<S: AsRef<str>>(
     session: &Session,
-    query: &Q,
-) -> Result<Vec<Network>> {
+    id: S,
+) -> Result<FirewallPolicy> {
+    trace!("Get firewall policy by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallPolicyRoot =
+        session.get_json(NETWORK, &["fwaas", "firewall_policies", id.as_ref()], None)?;
+    trace!("Received {:?}", root.firewall_policy);
+    Ok(root.firewall_policy)
+}
+
+/// Get a firewall policy by its name.
+pub fn get_firewall_policy_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<FirewallPolicy> {
+    trace!("Get firewall policy by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallPoliciesRoot = session.get_json_query(
+        NETWORK,
+        &["fwaas", "firewall_policies"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.firewall_policies,
+        "Firewall policy with given name or ID not found",
+        "Too many firewall policies found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a firewall rule.
+pub fn get_firewall_rule<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<FirewallRule> {
+    let s = id_or_name.as_ref();
+    get_firewall_rule_by_id(session, s).if_not_found_then(|| get_firewall_rule_by_name(session, s))
+}
+
+/// Get a firewall rule by its ID.
+pub fn get_firewall_rule_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<FirewallRule> {
+    trace!("Get firewall rule by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallRuleRoot =
+        session.get_json(NETWORK, &["fwaas", "firewall_rules", id.as_ref()], None)?;
+    trace!("Received {:?}", root.firewall_rule);
+    Ok(root.firewall_rule)
+}
+
+/// Get a firewall rule by its name.
+pub fn get_firewall_rule_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<FirewallRule> {
+    trace!("Get firewall rule by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallRulesRoot = session.get_json_query(
+        NETWORK,
+        &["fwaas", "firewall_rules"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.firewall_rules,
+        "Firewall rule with given name or ID not found",
+        "Too many firewall rules found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a floating IP.
+pub fn get_floating_ip<S: AsRef<str>>(session: &Session, id: S) -> Result<FloatingIp> {
+    trace!("Get floating IP by ID {}", id.as_ref());
+    let root: FloatingIpRoot = session.get_json(NETWORK, &["floatingips", id.as_ref()], None)?;
+    trace!("Received {:?}", root.floatingip);
+    Ok(root.floatingip)
+}
+
+/// Get an IKE policy.
+pub fn get_ike_policy<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<IkePolicy> {
+    let s = id_or_name.as_ref();
+    get_ike_policy_by_id(session, s).if_not_found_then(|| get_ike_policy_by_name(session, s))
+}
+
+/// Get an IKE policy by its ID.
+pub fn get_ike_policy_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<IkePolicy> {
+    trace!("Get IKE policy by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IkePolicyRoot =
+        session.get_json(NETWORK, &["vpn", "ikepolicies", id.as_ref()], None)?;
+    trace!("Received {:?}", root.ikepolicy);
+    Ok(root.ikepolicy)
+}
+
+/// Get an IKE policy by its name.
+pub fn get_ike_policy_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<IkePolicy> {
+    trace!("Get IKE policy by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IkePoliciesRoot = session.get_json_query(
+        NETWORK,
+        &["vpn", "ikepolicies"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.ikepolicies,
+        "IKE policy with given name or ID not found",
+        "Too many IKE policies found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get an IPsec policy.
+pub fn get_ipsec_policy<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<IpsecPolicy> {
+    let s = id_or_name.as_ref();
+    get_ipsec_policy_by_id(session, s).if_not_found_then(|| get_ipsec_policy_by_name(session, s))
+}
+
+/// Get an IPsec policy by its ID.
+pub fn get_ipsec_policy_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<IpsecPolicy> {
+    trace!("Get IPsec policy by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecPolicyRoot =
+        session.get_json(NETWORK, &["vpn", "ipsecpolicies", id.as_ref()], None)?;
+    trace!("Received {:?}", root.ipsecpolicy);
+    Ok(root.ipsecpolicy)
+}
+
+/// Get an IPsec policy by its name.
+pub fn get_ipsec_policy_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<IpsecPolicy> {
+    trace!("Get IPsec policy by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecPoliciesRoot = session.get_json_query(
+        NETWORK,
+        &["vpn", "ipsecpolicies"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.ipsecpolicies,
+        "IPsec policy with given name or ID not found",
+        "Too many IPsec policies found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get an IPsec site connection.
+pub fn get_ipsec_site_connection<S: AsRef<str>>(
+    session: &Session,
+    id_or_name: S,
+) -> Result<IpsecSiteConnection> {
+    let s = id_or_name.as_ref();
+    get_ipsec_site_connection_by_id(session, s)
+        .if_not_found_then(|| get_ipsec_site_connection_by_name(session, s))
+}
+
+/// Get an IPsec site connection by its ID.
+pub fn get_ipsec_site_connection_by_id<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+) -> Result<IpsecSiteConnection> {
+    trace!("Get IPsec site connection by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecSiteConnectionRoot = session.get_json(
+        NETWORK,
+        &["vpn", "ipsec-site-connections", id.as_ref()],
+        None,
+    )?;
+    trace!("Received {:?}", root.ipsec_site_connection);
+    Ok(root.ipsec_site_connection)
+}
+
+/// Get an IPsec site connection by its name.
+pub fn get_ipsec_site_connection_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<IpsecSiteConnection> {
+    trace!("Get IPsec site connection by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecSiteConnectionsRoot = session.get_json_query(
+        NETWORK,
+        &["vpn", "ipsec-site-connections"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.ipsec_site_connections,
+        "IPsec site connection with given name or ID not found",
+        "Too many IPsec site connections found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a metering label.
+pub fn get_metering_label<S: AsRef<str>>(
+    session: &Session,
+    id_or_name: S,
+) -> Result<MeteringLabel> {
+    let s = id_or_name.as_ref();
+    get_metering_label_by_id(session, s)
+        .if_not_found_then(|| get_metering_label_by_name(session, s))
+}
+
+/// Get a metering label by its ID.
+pub fn get_metering_label_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<MeteringLabel> {
+    trace!("Get metering label by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let root: MeteringLabelRoot =
+        session.get_json(NETWORK, &["metering", "metering-labels", id.as_ref()], None)?;
+    trace!("Received {:?}", root.metering_label);
+    Ok(root.metering_label)
+}
+
+/// Get a metering label by its name.
+pub fn get_metering_label_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<MeteringLabel> {
+    trace!("Get metering label by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let root: MeteringLabelsRoot = session.get_json_query(
+        NETWORK,
+        &["metering", "metering-labels"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.metering_labels,
+        "Metering label with given name or ID not found",
+        "Too many metering labels found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a metering label rule.
+pub fn get_metering_label_rule<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+) -> Result<MeteringLabelRule> {
+    trace!("Get metering label rule by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let root: MeteringLabelRuleRoot = session.get_json(
+        NETWORK,
+        &["metering", "metering-label-rules", id.as_ref()],
+        None,
+    )?;
+    trace!("Received {:?}", root.metering_label_rule);
+    Ok(root.metering_label_rule)
+}
+
+/// Get a network.
+pub fn get_network<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Network> {
+    let s = id_or_name.as_ref();
+    get_network_by_id(session, s).if_not_found_then(|| get_network_by_name(session, s))
+}
+
+/// Get a network by its ID.
+pub fn get_network_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Network> {
+    trace!("Get network by ID {}", id.as_ref());
+    let root: NetworkRoot = session.get_json(NETWORK, &["networks", id.as_ref()], None)?;
+    trace!("Received {:?}", root.network);
+    Ok(root.network)
+}
+
+/// Get a network by its name.
+pub fn get_network_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Network> {
+    trace!("Get network by name {}", name.as_ref());
+    let root: NetworksRoot =
+        session.get_json_query(NETWORK, &["networks"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.networks,
+        "Network with given name or ID not found",
+        "Too many networks found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get IP address availability of a network.
+pub fn get_network_ip_availability<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+) -> Result<NetworkIpAvailability> {
+    require_extension(
+        session,
+        NetworkExtension::NetworkIpAvailability,
+        "IP availability",
+    )?;
+    trace!("Get IP availability of network {}", id.as_ref());
+    let root: NetworkIpAvailabilityRoot =
+        session.get_json(NETWORK, &["network-ip-availabilities", id.as_ref()], None)?;
+    trace!("Received {:?}", root.network_ip_availability);
+    Ok(root.network_ip_availability)
+}
+
+/// Get a port.
+pub fn get_port<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Port> {
+    let s = id_or_name.as_ref();
+    get_port_by_id(session, s).if_not_found_then(|| get_port_by_name(session, s))
+}
+
+/// Get a port by its ID.
+pub fn get_port_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Port> {
+    trace!("Get port by ID {}", id.as_ref());
+    let root: PortRoot = session.get_json(NETWORK, &["ports", id.as_ref()], None)?;
+    trace!("Received {:?}", root.port);
+    Ok(root.port)
+}
+
+/// Get a port by its name.
+pub fn get_port_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Port> {
+    trace!("Get port by name {}", name.as_ref());
+    let root: PortsRoot =
+        session.get_json_query(NETWORK, &["ports"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.ports,
+        "Port with given name or ID not found",
+        "Too many ports found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a QoS policy.
+pub fn get_qos_policy<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<QosPolicy> {
+    let s = id_or_name.as_ref();
+    get_qos_policy_by_id(session, s).if_not_found_then(|| get_qos_policy_by_name(session, s))
+}
+
+/// Get a QoS policy by its ID.
+pub fn get_qos_policy_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<QosPolicy> {
+    trace!("Get QoS policy by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let root: QosPolicyRoot = session.get_json(NETWORK, &["qos", "policies", id.as_ref()], None)?;
+    trace!("Received {:?}", root.policy);
+    Ok(root.policy)
+}
+
+/// Get a QoS policy by its name.
+pub fn get_qos_policy_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<QosPolicy> {
+    trace!("Get QoS policy by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let root: QosPoliciesRoot = session.get_json_query(
+        NETWORK,
+        &["qos", "policies"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.policies,
+        "QoS policy with given name or ID not found",
+        "Too many QoS policies found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get an RBAC policy.
+pub fn get_rbac_policy<S: AsRef<str>>(session: &Session, id: S) -> Result<RbacPolicy> {
+    trace!("Get RBAC policy {}", id.as_ref());
+    require_extension(session, NetworkExtension::Rbac, "RBAC policies")?;
+    let root: RbacPolicyRoot = session.get_json(NETWORK, &["rbac-policies", id.as_ref()], None)?;
+    trace!("Received {:?}", root.rbac_policy);
+    Ok(root.rbac_policy)
+}
+
+/// Get a router.
+pub fn get_router<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Router> {
+    let s = id_or_name.as_ref();
+    get_router_by_id(session, s).if_not_found_then(|| get_router_by_name(session, s))
+}
+
+/// Get a router by its ID.
+pub fn get_router_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Router> {
+    trace!("Get router by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let root: RouterRoot = session.get_json(NETWORK, &["routers", id.as_ref()], None)?;
+    trace!("Received {:?}", root.router);
+    Ok(root.router)
+}
+
+/// Get a router by its name.
+pub fn get_router_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Router> {
+    trace!("Get router by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let root: RoutersRoot =
+        session.get_json_query(NETWORK, &["routers"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.routers,
+        "Router with given name or ID not found",
+        "Too many routers found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a security group.
+pub fn get_security_group<S: AsRef<str>>(
+    session: &Session,
+    id_or_name: S,
+) -> Result<SecurityGroup> {
+    let s = id_or_name.as_ref();
+    get_security_group_by_id(session, s)
+        .if_not_found_then(|| get_security_group_by_name(session, s))
+}
+
+/// Get a security group by its ID.
+pub fn get_security_group_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<SecurityGroup> {
+    trace!("Get security group by ID {}", id.as_ref());
+    let root: SecurityGroupRoot =
+        session.get_json(NETWORK, &["security-groups", id.as_ref()], None)?;
+    trace!("Received {:?}", root.security_group);
+    Ok(root.security_group)
+}
+
+/// Get a security group by its name.
+pub fn get_security_group_by_name<S: AsRef<str>>(
+    session: &Session,
+    name: S,
+) -> Result<SecurityGroup> {
+    trace!("Get security group by name {}", name.as_ref());
+    let root: SecurityGroupsRoot = session.get_json_query(
+        NETWORK,
+        &["security-groups"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.security_groups,
+        "Security group with given name or ID not found",
+        "Too many security groups found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a security group rule.
+pub fn get_security_group_rule<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+) -> Result<SecurityGroupRule> {
+    trace!("Get security group rule by ID {}", id.as_ref());
+    let root: SecurityGroupRuleRoot =
+        session.get_json(NETWORK, &["security-group-rules", id.as_ref()], None)?;
+    trace!("Received {:?}", root.security_group_rule);
+    Ok(root.security_group_rule)
+}
+
+/// Get a network segment.
+pub fn get_segment<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Segment> {
+    let s = id_or_name.as_ref();
+    get_segment_by_id(session, s).if_not_found_then(|| get_segment_by_name(session, s))
+}
+
+/// Get a network segment by its ID.
+pub fn get_segment_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Segment> {
+    trace!("Get segment by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let root: SegmentRoot = session.get_json(NETWORK, &["segments", id.as_ref()], None)?;
+    trace!("Received {:?}", root.segment);
+    Ok(root.segment)
+}
+
+/// Get a network segment by its name.
+pub fn get_segment_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Segment> {
+    trace!("Get segment by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let root: SegmentsRoot =
+        session.get_json_query(NETWORK, &["segments"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.segments,
+        "Segment with given name or ID not found",
+        "Too many segments found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a subnet.
+pub fn get_subnet<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Subnet> {
+    let s = id_or_name.as_ref();
+    get_subnet_by_id(session, s).if_not_found_then(|| get_subnet_by_name(session, s))
+}
+
+/// Get a subnet by its ID.
+pub fn get_subnet_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Subnet> {
+    trace!("Get subnet by ID {}", id.as_ref());
+    let root: SubnetRoot = session.get_json(NETWORK, &["subnets", id.as_ref()], None)?;
+    trace!("Received {:?}", root.subnet);
+    Ok(root.subnet)
+}
+
+/// Get a subnet by its name.
+pub fn get_subnet_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Subnet> {
+    trace!("Get subnet by name {}", name.as_ref());
+    let root: SubnetsRoot =
+        session.get_json_query(NETWORK, &["subnets"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.subnets,
+        "Subnet with given name or ID not found",
+        "Too many subnets found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a subnet pool.
+pub fn get_subnet_pool<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<SubnetPool> {
+    let s = id_or_name.as_ref();
+    get_subnet_pool_by_id(session, s).if_not_found_then(|| get_subnet_pool_by_name(session, s))
+}
+
+/// Get a subnet pool by its ID.
+pub fn get_subnet_pool_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<SubnetPool> {
+    trace!("Get subnet pool by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let root: SubnetPoolRoot = session.get_json(NETWORK, &["subnetpools", id.as_ref()], None)?;
+    trace!("Received {:?}", root.subnetpool);
+    Ok(root.subnetpool)
+}
+
+/// Get a subnet pool by its name.
+pub fn get_subnet_pool_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<SubnetPool> {
+    trace!("Get subnet pool by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let root: SubnetPoolsRoot =
+        session.get_json_query(NETWORK, &["subnetpools"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.subnetpools,
+        "Subnet pool with given name or ID not found",
+        "Too many subnet pools found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a trunk.
+pub fn get_trunk<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<Trunk> {
+    let s = id_or_name.as_ref();
+    get_trunk_by_id(session, s).if_not_found_then(|| get_trunk_by_name(session, s))
+}
+
+/// Get a trunk by its ID.
+pub fn get_trunk_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<Trunk> {
+    trace!("Get trunk by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let root: TrunkRoot = session.get_json(NETWORK, &["trunks", id.as_ref()], None)?;
+    trace!("Received {:?}", root.trunk);
+    Ok(root.trunk)
+}
+
+/// Get a trunk by its name.
+pub fn get_trunk_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<Trunk> {
+    trace!("Get trunk by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let root: TrunksRoot =
+        session.get_json_query(NETWORK, &["trunks"], &[("name", name.as_ref())], None)?;
+    let result = utils::one(
+        root.trunks,
+        "Trunk with given name or ID not found",
+        "Too many trunks found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// Get a VPN service.
+pub fn get_vpn_service<S: AsRef<str>>(session: &Session, id_or_name: S) -> Result<VpnService> {
+    let s = id_or_name.as_ref();
+    get_vpn_service_by_id(session, s).if_not_found_then(|| get_vpn_service_by_name(session, s))
+}
+
+/// Get a VPN service by its ID.
+pub fn get_vpn_service_by_id<S: AsRef<str>>(session: &Session, id: S) -> Result<VpnService> {
+    trace!("Get VPN service by ID {}", id.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: VpnServiceRoot =
+        session.get_json(NETWORK, &["vpn", "vpnservices", id.as_ref()], None)?;
+    trace!("Received {:?}", root.vpnservice);
+    Ok(root.vpnservice)
+}
+
+/// Get a VPN service by its name.
+pub fn get_vpn_service_by_name<S: AsRef<str>>(session: &Session, name: S) -> Result<VpnService> {
+    trace!("Get VPN service by name {}", name.as_ref());
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: VpnServicesRoot = session.get_json_query(
+        NETWORK,
+        &["vpn", "vpnservices"],
+        &[("name", name.as_ref())],
+        None,
+    )?;
+    let result = utils::one(
+        root.vpnservices,
+        "VPN service with given name or ID not found",
+        "Too many VPN services found with given name",
+    )?;
+    trace!("Received {:?}", result);
+    Ok(result)
+}
+
+/// List network agents.
+pub fn list_agents<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Agent>> {
+    trace!("Listing agents with {:?}", query);
+    require_extension(session, NetworkExtension::Agent, "Network agents")?;
+    let root: AgentsRoot = session.get_json_query(NETWORK, &["agents"], query, None)?;
+    trace!("Received agents: {:?}", root.agents);
+    Ok(root.agents)
+}
+
+/// List address scopes.
+pub fn list_address_scopes<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<AddressScope>> {
+    trace!("Listing address scopes with {:?}", query);
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let root: AddressScopesRoot =
+        session.get_json_query(NETWORK, &["address-scopes"], query, None)?;
+    trace!("Received address scopes: {:?}", root.address_scopes);
+    Ok(root.address_scopes)
+}
+
+/// List VPN endpoint groups.
+pub fn list_endpoint_groups<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<EndpointGroup>> {
+    trace!("Listing VPN endpoint groups with {:?}", query);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: EndpointGroupsRoot =
+        session.get_json_query(NETWORK, &["vpn", "endpoint-groups"], query, None)?;
+    trace!("Received VPN endpoint groups: {:?}", root.endpoint_groups);
+    Ok(root.endpoint_groups)
+}
+
+/// List known extensions of the Network API.
+///
+/// The result is cached in the session, so only the first call makes an API
+/// request.
+pub fn list_extensions(session: &Session) -> Result<Vec<NetworkExtension>> {
+    let cache = session.network_extensions();
+    cache.ensure_value(|| {
+        trace!("Listing network extensions");
+        let root: ExtensionsRoot = session.get_json(NETWORK, &["extensions"], None)?;
+        trace!("Received network extensions: {:?}", root.extensions);
+        Ok(root
+            .extensions
+            .into_iter()
+            .filter_map(|ext| ext.alias)
+            .collect())
+    })?;
+    Ok(cache
+        .extract(|extensions| extensions.clone())
+        .unwrap_or_default())
+}
+
+/// List firewall groups.
+pub fn list_firewall_groups<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<FirewallGroup>> {
+    trace!("Listing firewall groups with {:?}", query);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallGroupsRoot =
+        session.get_json_query(NETWORK, &["fwaas", "firewall_groups"], query, None)?;
+    trace!("Received firewall groups: {:?}", root.firewall_groups);
+    Ok(root.firewall_groups)
+}
+
+/// List firewall policies.
+pub fn list_firewall_policies<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<FirewallPolicy>> {
+    trace!("Listing firewall policies with {:?}", query);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallPoliciesRoot =
+        session.get_json_query(NETWORK, &["fwaas", "firewall_policies"], query, None)?;
+    trace!("Received firewall policies: {:?}", root.firewall_policies);
+    Ok(root.firewall_policies)
+}
+
+/// List firewall rules.
+pub fn list_firewall_rules<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<FirewallRule>> {
+    trace!("Listing firewall rules with {:?}", query);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let root: FirewallRulesRoot =
+        session.get_json_query(NETWORK, &["fwaas", "firewall_rules"], query, None)?;
+    trace!("Received firewall rules: {:?}", root.firewall_rules);
+    Ok(root.firewall_rules)
+}
+
+/// List floating IPs.
+pub fn list_floating_ips<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<FloatingIp>> {
+    trace!("Listing floating IPs with {:?}", query);
+    let root: FloatingIpsRoot = session.get_json_query(NETWORK, &["floatingips"], query, None)?;
+    trace!("Received floating IPs: {:?}", root.floatingips);
+    Ok(root.floatingips)
+}
+
+/// List networks hosted by a DHCP agent.
+pub fn list_dhcp_agent_networks<S, Q>(
+    session: &Session,
+    agent_id: S,
+    query: &Q,
+) -> Result<Vec<Network>>
+where
+    S: AsRef<str>,
+    Q: Serialize + Sync + Debug,
+{
+    trace!(
+        "Listing networks of DHCP agent {} with {:?}",
+        agent_id.as_ref(),
+        query
+    );
+    require_extension(
+        session,
+        NetworkExtension::DhcpAgentScheduler,
+        "DHCP agent scheduling",
+    )?;
+    let root: NetworksRoot = session.get_json_query(
+        NETWORK,
+        &["agents", agent_id.as_ref(), "dhcp-networks"],
+        query,
+        None,
+    )?;
+    trace!("Received networks: {:?}", root.networks);
+    Ok(root.networks)
+}
+
+/// List IKE policies.
+pub fn list_ike_policies<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<IkePolicy>> {
+    trace!("Listing IKE policies with {:?}", query);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IkePoliciesRoot =
+        session.get_json_query(NETWORK, &["vpn", "ikepolicies"], query, None)?;
+    trace!("Received IKE policies: {:?}", root.ikepolicies);
+    Ok(root.ikepolicies)
+}
+
+/// List IPsec policies.
+pub fn list_ipsec_policies<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<IpsecPolicy>> {
+    trace!("Listing IPsec policies with {:?}", query);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecPoliciesRoot =
+        session.get_json_query(NETWORK, &["vpn", "ipsecpolicies"], query, None)?;
+    trace!("Received IPsec policies: {:?}", root.ipsecpolicies);
+    Ok(root.ipsecpolicies)
+}
+
+/// List IPsec site connections.
+pub fn list_ipsec_site_connections<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<IpsecSiteConnection>> {
+    trace!("Listing IPsec site connections with {:?}", query);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: IpsecSiteConnectionsRoot =
+        session.get_json_query(NETWORK, &["vpn", "ipsec-site-connections"], query, None)?;
+    trace!(
+        "Received IPsec site connections: {:?}",
+        root.ipsec_site_connections
+    );
+    Ok(root.ipsec_site_connections)
+}
+
+/// List routers hosted by an L3 agent.
+pub fn list_l3_agent_routers<S, Q>(session: &Session, agent_id: S, query: &Q) -> Result<Vec<Router>>
+where
+    S: AsRef<str>,
+    Q: Serialize + Sync + Debug,
+{
+    trace!(
+        "Listing routers of L3 agent {} with {:?}",
+        agent_id.as_ref(),
+        query
+    );
+    require_extension(
+        session,
+        NetworkExtension::L3AgentScheduler,
+        "L3 agent scheduling",
+    )?;
+    let root: RoutersRoot = session.get_json_query(
+        NETWORK,
+        &["agents", agent_id.as_ref(), "l3-routers"],
+        query,
+        None,
+    )?;
+    trace!("Received routers: {:?}", root.routers);
+    Ok(root.routers)
+}
+
+/// List metering label rules.
+pub fn list_metering_label_rules<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<MeteringLabelRule>> {
+    trace!("Listing metering label rules with {:?}", query);
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let root: MeteringLabelRulesRoot =
+        session.get_json_query(NETWORK, &["metering", "metering-label-rules"], query, None)?;
+    trace!(
+        "Received metering label rules: {:?}",
+        root.metering_label_rules
+    );
+    Ok(root.metering_label_rules)
+}
+
+/// List metering labels.
+pub fn list_metering_labels<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<MeteringLabel>> {
+    trace!("Listing metering labels with {:?}", query);
+    require_extension(session, NetworkExtension::Metering, "Metering")?;
+    let root: MeteringLabelsRoot =
+        session.get_json_query(NETWORK, &["metering", "metering-labels"], query, None)?;
+    trace!("Received metering labels: {:?}", root.metering_labels);
+    Ok(root.metering_labels)
+}
+
+/// List networks.
+pub fn list_networks<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Network>> {
     trace!("Listing networks with {:?}", query);
     let root: NetworksRoot = session.get_json_query(NETWORK, &["networks"], query, None)?;
     trace!("Received networks: {:?}", root.networks);
     Ok(root.networks)
 }
 
+/// List port forwardings of a floating IP.
+pub fn list_port_forwardings<S: AsRef<str>>(
+    session: &Session,
+    floating_ip_id: S,
+) -> Result<Vec<PortForwarding>> {
+    require_extension(
+        session,
+        NetworkExtension::FloatingIpPortForwarding,
+        "Port forwarding",
+    )?;
+    trace!(
+        "Listing port forwardings of floating IP {}",
+        floating_ip_id.as_ref()
+    );
+    let root: PortForwardingsRoot = session.get_json(
+        NETWORK,
+        &["floatingips", floating_ip_id.as_ref(), "port_forwardings"],
+        None,
+    )?;
+    trace!("Received port forwardings: {:?}", root.port_forwardings);
+    Ok(root.port_forwardings)
+}
+
+/// List resources of any type with only some of their fields.
+pub fn list_partial<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    path: &[&str],
+    key: &str,
+    query: &Q,
+) -> Result<Vec<PartialResource>> {
+    trace!("Listing partial {} with {:?}", key, query);
+    let root: HashMap<String, serde_json::Value> =
+        session.get_json_query(NETWORK, path, query, None)?;
+    let result = extract_partial_root(root, key)?;
+    trace!("Received partial {}: {:?}", key, result);
+    Ok(result)
+}
+
 /// List ports.
 pub fn list_ports<Q: Serialize + Sync + Debug>(session: &Session, query: &Q) -> Result<Vec<Port>> {
     trace!("Listing ports with {:?}", query);
@@ -216,6 +1931,83 @@ pub fn list_ports<Q: Serialize + Sync + Debug>(session: &Session, query: &Q) ->
     Ok(root.ports)
 }
 
+/// List QoS policies.
+pub fn list_qos_policies<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<QosPolicy>> {
+    trace!("Listing QoS policies with {:?}", query);
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let root: QosPoliciesRoot =
+        session.get_json_query(NETWORK, &["qos", "policies"], query, None)?;
+    trace!("Received QoS policies: {:?}", root.policies);
+    Ok(root.policies)
+}
+
+/// List RBAC policies.
+pub fn list_rbac_policies<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<RbacPolicy>> {
+    trace!("Listing RBAC policies with {:?}", query);
+    require_extension(session, NetworkExtension::Rbac, "RBAC policies")?;
+    let root: RbacPoliciesRoot =
+        session.get_json_query(NETWORK, &["rbac-policies"], query, None)?;
+    trace!("Received RBAC policies: {:?}", root.rbac_policies);
+    Ok(root.rbac_policies)
+}
+
+/// List routers.
+pub fn list_routers<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Router>> {
+    trace!("Listing routers with {:?}", query);
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let root: RoutersRoot = session.get_json_query(NETWORK, &["routers"], query, None)?;
+    trace!("Received routers: {:?}", root.routers);
+    Ok(root.routers)
+}
+
+/// List security groups.
+pub fn list_security_groups<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<SecurityGroup>> {
+    trace!("Listing security groups with {:?}", query);
+    let root: SecurityGroupsRoot =
+        session.get_json_query(NETWORK, &["security-groups"], query, None)?;
+    trace!("Received security groups: {:?}", root.security_groups);
+    Ok(root.security_groups)
+}
+
+/// List security group rules.
+pub fn list_security_group_rules<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<SecurityGroupRule>> {
+    trace!("Listing security group rules with {:?}", query);
+    let root: SecurityGroupRulesRoot =
+        session.get_json_query(NETWORK, &["security-group-rules"], query, None)?;
+    trace!(
+        "Received security group rules: {:?}",
+        root.security_group_rules
+    );
+    Ok(root.security_group_rules)
+}
+
+/// List segments.
+pub fn list_segments<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Segment>> {
+    trace!("Listing segments with {:?}", query);
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let root: SegmentsRoot = session.get_json_query(NETWORK, &["segments"], query, None)?;
+    trace!("Received segments: {:?}", root.segments);
+    Ok(root.segments)
+}
+
 /// List subnets.
 pub fn list_subnets<Q: Serialize + Sync + Debug>(
     session: &Session,
@@ -227,51 +2019,1077 @@ pub fn list_subnets<Q: Serialize + Sync + Debug>(
     Ok(root.subnets)
 }
 
+/// List subnet pools.
+pub fn list_subnet_pools<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<SubnetPool>> {
+    trace!("Listing subnet pools with {:?}", query);
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let root: SubnetPoolsRoot = session.get_json_query(NETWORK, &["subnetpools"], query, None)?;
+    trace!("Received subnet pools: {:?}", root.subnetpools);
+    Ok(root.subnetpools)
+}
+
+/// List tags of a resource of the given type.
+pub fn list_tags<S: AsRef<str>>(
+    session: &Session,
+    resource_type: &str,
+    id: S,
+) -> Result<Vec<String>> {
+    require_extension(session, NetworkExtension::StandardAttrTag, "Tags")?;
+    trace!("Listing tags of {} {}", resource_type, id.as_ref());
+    let root: TagsRoot = session.get_json(NETWORK, &[resource_type, id.as_ref(), "tags"], None)?;
+    trace!("Received tags: {:?}", root.tags);
+    Ok(root.tags)
+}
+
+/// List trunks.
+pub fn list_trunks<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<Trunk>> {
+    trace!("Listing trunks with {:?}", query);
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let root: TrunksRoot = session.get_json_query(NETWORK, &["trunks"], query, None)?;
+    trace!("Received trunks: {:?}", root.trunks);
+    Ok(root.trunks)
+}
+
+/// List VPN services.
+pub fn list_vpn_services<Q: Serialize + Sync + Debug>(
+    session: &Session,
+    query: &Q,
+) -> Result<Vec<VpnService>> {
+    trace!("Listing VPN services with {:?}", query);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let root: VpnServicesRoot =
+        session.get_json_query(NETWORK, &["vpn", "vpnservices"], query, None)?;
+    trace!("Received VPN services: {:?}", root.vpnservices);
+    Ok(root.vpnservices)
+}
+
+/// Remove a network from a DHCP agent.
+pub fn remove_network_from_dhcp_agent<S1, S2>(
+    session: &Session,
+    agent_id: S1,
+    network_id: S2,
+) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    debug!(
+        "Removing network {} from DHCP agent {}",
+        network_id.as_ref(),
+        agent_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::DhcpAgentScheduler,
+        "DHCP agent scheduling",
+    )?;
+    let _ = session.delete(
+        NETWORK,
+        &[
+            "agents",
+            agent_id.as_ref(),
+            "dhcp-networks",
+            network_id.as_ref(),
+        ],
+        None,
+    )?;
+    debug!(
+        "Network {} was removed from DHCP agent {}",
+        network_id.as_ref(),
+        agent_id.as_ref()
+    );
+    Ok(())
+}
+
+/// Remove a router from an L3 agent.
+pub fn remove_router_from_l3_agent<S1, S2>(
+    session: &Session,
+    agent_id: S1,
+    router_id: S2,
+) -> Result<()>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    debug!(
+        "Removing router {} from L3 agent {}",
+        router_id.as_ref(),
+        agent_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::L3AgentScheduler,
+        "L3 agent scheduling",
+    )?;
+    let _ = session.delete(
+        NETWORK,
+        &[
+            "agents",
+            agent_id.as_ref(),
+            "l3-routers",
+            router_id.as_ref(),
+        ],
+        None,
+    )?;
+    debug!(
+        "Router {} was removed from L3 agent {}",
+        router_id.as_ref(),
+        agent_id.as_ref()
+    );
+    Ok(())
+}
+
+/// Replace all tags of a resource of the given type.
+pub fn replace_tags<S: AsRef<str>>(
+    session: &Session,
+    resource_type: &str,
+    id: S,
+    tags: Vec<String>,
+) -> Result<Vec<String>> {
+    require_extension(session, NetworkExtension::StandardAttrTag, "Tags")?;
+    debug!(
+        "Replacing tags of {} {} with {:?}",
+        resource_type,
+        id.as_ref(),
+        tags
+    );
+    let body = TagsRoot { tags };
+    let root: TagsRoot =
+        session.put_json(NETWORK, &[resource_type, id.as_ref(), "tags"], body, None)?;
+    debug!(
+        "New tags of {} {}: {:?}",
+        resource_type,
+        id.as_ref(),
+        root.tags
+    );
+    Ok(root.tags)
+}
+
+/// Fail if the Network API does not support the given extension.
+pub fn require_extension(
+    session: &Session,
+    extension: NetworkExtension,
+    feature: &str,
+) -> Result<()> {
+    if supports_extension(session, extension)? {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::IncompatibleApiVersion,
+            format!("{} requires the {} network extension", feature, extension),
+        ))
+    }
+}
+
+/// Fail if a network request uses extensions the Network API does not support.
+fn require_network_extensions(session: &Session, request: &Network) -> Result<()> {
+    if request.port_security_enabled.is_some() {
+        require_extension(session, NetworkExtension::PortSecurity, "Port security")?;
+    }
+    if request.provider_network_type.is_some()
+        || request.provider_physical_network.is_some()
+        || request.provider_segmentation_id.is_some()
+    {
+        require_extension(session, NetworkExtension::Provider, "Provider networks")?;
+    }
+    Ok(())
+}
+
+/// Insert a rule into or remove a rule from a firewall policy.
+pub fn firewall_policy_rule_action<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    action: &str,
+    request: FirewallPolicyRuleRequest,
+) -> Result<FirewallPolicy> {
+    debug!(
+        "Running {} on firewall policy {} with {:?}",
+        action,
+        id.as_ref(),
+        request
+    );
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let policy: FirewallPolicy = session.put_json(
+        NETWORK,
+        &["fwaas", "firewall_policies", id.as_ref(), action],
+        request,
+        None,
+    )?;
+    debug!("Firewall policy after {}: {:?}", action, policy);
+    Ok(policy)
+}
+
+/// Add or remove an interface of a router.
+pub fn router_interface_action<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    action: &str,
+    request: RouterInterfaceRequest,
+) -> Result<()> {
+    debug!(
+        "Running {} on router {} with {:?}",
+        action,
+        id.as_ref(),
+        request
+    );
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    let _ = session.put(NETWORK, &["routers", id.as_ref(), action], request, None)?;
+    debug!("Successfully ran {} on router {}", action, id.as_ref());
+    Ok(())
+}
+
+/// Add or remove extra routes of a router.
+pub fn router_routes_action<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    action: &str,
+    routes: Vec<HostRoute>,
+) -> Result<Router> {
+    debug!(
+        "Running {} on router {} with {:?}",
+        action,
+        id.as_ref(),
+        routes
+    );
+    require_extension(
+        session,
+        NetworkExtension::ExtraRouteAtomic,
+        "Atomic update of extra routes",
+    )?;
+    let body = RouterRoutesRoot {
+        router: RouterRoutes { routes },
+    };
+    let root: RouterRoot =
+        session.put_json(NETWORK, &["routers", id.as_ref(), action], body, None)?;
+    debug!("Router after {}: {:?}", action, root.router);
+    Ok(root.router)
+}
+
+/// Add or remove sub-ports of a trunk.
+pub fn trunk_sub_ports_action<S: AsRef<str>, T: Serialize + Send + Debug>(
+    session: &Session,
+    id: S,
+    action: &str,
+    request: T,
+) -> Result<Trunk> {
+    debug!(
+        "Running {} on trunk {} with {:?}",
+        action,
+        id.as_ref(),
+        request
+    );
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let trunk: Trunk =
+        session.put_json(NETWORK, &["trunks", id.as_ref(), action], request, None)?;
+    debug!("Trunk after {}: {:?}", action, trunk);
+    Ok(trunk)
+}
+
+/// Whether the Network API supports the given extension.
+pub fn supports_extension(session: &Session, extension: NetworkExtension) -> Result<bool> {
+    Ok(list_extensions(session)?.contains(&extension))
+}
+
+/// Update a network agent.
+pub fn update_agent<S: AsRef<str>>(session: &Session, id: S, update: AgentUpdate) -> Result<Agent> {
+    debug!("Updating agent {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Agent, "Network agents")?;
+    let body = AgentUpdateRoot { agent: update };
+    let root: AgentRoot = session.put_json(NETWORK, &["agents", id.as_ref()], body, None)?;
+    debug!("Updated agent {:?}", root.agent);
+    Ok(root.agent)
+}
+
+/// Update an address scope.
+pub fn update_address_scope<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: AddressScopeUpdate,
+) -> Result<AddressScope> {
+    debug!("Updating address scope {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::AddressScope, "Address scopes")?;
+    let body = AddressScopeUpdateRoot {
+        address_scope: update,
+    };
+    let root: AddressScopeRoot =
+        session.put_json(NETWORK, &["address-scopes", id.as_ref()], body, None)?;
+    debug!("Updated address scope {:?}", root.address_scope);
+    Ok(root.address_scope)
+}
+
+/// Update a VPN endpoint group.
+pub fn update_endpoint_group<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: EndpointGroupUpdate,
+) -> Result<EndpointGroup> {
+    debug!(
+        "Updating VPN endpoint group {} with {:?}",
+        id.as_ref(),
+        update
+    );
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = EndpointGroupUpdateRoot {
+        endpoint_group: update,
+    };
+    let root: EndpointGroupRoot = session.put_json(
+        NETWORK,
+        &["vpn", "endpoint-groups", id.as_ref()],
+        body,
+        None,
+    )?;
+    debug!("Updated VPN endpoint group {:?}", root.endpoint_group);
+    Ok(root.endpoint_group)
+}
+
+/// Update a firewall group.
+pub fn update_firewall_group<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: FirewallGroupUpdate,
+) -> Result<FirewallGroup> {
+    debug!("Updating firewall group {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallGroupUpdateRoot {
+        firewall_group: update,
+    };
+    let root: FirewallGroupRoot = session.put_json(
+        NETWORK,
+        &["fwaas", "firewall_groups", id.as_ref()],
+        body,
+        None,
+    )?;
+    debug!("Updated firewall group {:?}", root.firewall_group);
+    Ok(root.firewall_group)
+}
+
+/// Update a firewall policy.
+pub fn update_firewall_policy<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: FirewallPolicyUpdate,
+) -> Result<FirewallPolicy> {
+    debug!("Updating firewall policy {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallPolicyUpdateRoot {
+        firewall_policy: update,
+    };
+    let root: FirewallPolicyRoot = session.put_json(
+        NETWORK,
+        &["fwaas", "firewall_policies", id.as_ref()],
+        body,
+        None,
+    )?;
+    debug!("Updated firewall policy {:?}", root.firewall_policy);
+    Ok(root.firewall_policy)
+}
+
+/// Update a firewall rule.
+pub fn update_firewall_rule<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: FirewallRuleUpdate,
+) -> Result<FirewallRule> {
+    debug!("Updating firewall rule {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Fwaas, "Firewall-as-a-Service")?;
+    let body = FirewallRuleUpdateRoot {
+        firewall_rule: update,
+    };
+    let root: FirewallRuleRoot = session.put_json(
+        NETWORK,
+        &["fwaas", "firewall_rules", id.as_ref()],
+        body,
+        None,
+    )?;
+    debug!("Updated firewall rule {:?}", root.firewall_rule);
+    Ok(root.firewall_rule)
+}
+
 /// Update a floating IP.
 pub fn update_floating_ip<S: AsRef<str>>(
     session: &Session,
     id: S,
     update: FloatingIpUpdate,
+    revision: Option<u32>,
 ) -> Result<FloatingIp> {
     debug!("Updating floating IP {} with {:?}", id.as_ref(), update);
     let body = FloatingIpUpdateRoot { floatingip: update };
     let root: FloatingIpRoot =
-        session.put_json(NETWORK, &["floatingips", id.as_ref()], body, None)?;
+        put_json_if_revision(session, &["floatingips", id.as_ref()], body, revision)?;
     debug!("Updated floating IP {:?}", root.floatingip);
     Ok(root.floatingip)
 }
 
+/// Update an IKE policy.
+pub fn update_ike_policy<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: IkePolicyUpdate,
+) -> Result<IkePolicy> {
+    debug!("Updating IKE policy {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IkePolicyUpdateRoot { ikepolicy: update };
+    let root: IkePolicyRoot =
+        session.put_json(NETWORK, &["vpn", "ikepolicies", id.as_ref()], body, None)?;
+    debug!("Updated IKE policy {:?}", root.ikepolicy);
+    Ok(root.ikepolicy)
+}
+
+/// Update an IPsec policy.
+pub fn update_ipsec_policy<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: IpsecPolicyUpdate,
+) -> Result<IpsecPolicy> {
+    debug!("Updating IPsec policy {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IpsecPolicyUpdateRoot {
+        ipsecpolicy: update,
+    };
+    let root: IpsecPolicyRoot =
+        session.put_json(NETWORK, &["vpn", "ipsecpolicies", id.as_ref()], body, None)?;
+    debug!("Updated IPsec policy {:?}", root.ipsecpolicy);
+    Ok(root.ipsecpolicy)
+}
+
+/// Update an IPsec site connection.
+pub fn update_ipsec_site_connection<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: IpsecSiteConnectionUpdate,
+) -> Result<IpsecSiteConnection> {
+    debug!(
+        "Updating IPsec site connection {} with {:?}",
+        id.as_ref(),
+        update
+    );
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = IpsecSiteConnectionUpdateRoot {
+        ipsec_site_connection: update,
+    };
+    let root: IpsecSiteConnectionRoot = session.put_json(
+        NETWORK,
+        &["vpn", "ipsec-site-connections", id.as_ref()],
+        body,
+        None,
+    )?;
+    debug!(
+        "Updated IPsec site connection {:?}",
+        root.ipsec_site_connection
+    );
+    Ok(root.ipsec_site_connection)
+}
+
 /// Update a network.
 pub fn update_network<S: AsRef<str>>(
     session: &Session,
     id: S,
     update: NetworkUpdate,
+    revision: Option<u32>,
 ) -> Result<Network> {
     debug!("Updating network {} with {:?}", id.as_ref(), update);
+    if update.port_security_enabled.is_some() {
+        require_extension(session, NetworkExtension::PortSecurity, "Port security")?;
+    }
     let body = NetworkUpdateRoot { network: update };
-    let root: NetworkRoot = session.put_json(NETWORK, &["networks", id.as_ref()], body, None)?;
+    let root: NetworkRoot =
+        put_json_if_revision(session, &["networks", id.as_ref()], body, revision)?;
     debug!("Updated network {:?}", root.network);
     Ok(root.network)
 }
 
 /// Update a port.
-pub fn update_port<S: AsRef<str>>(session: &Session, id: S, update: PortUpdate) -> Result<Port> {
+pub fn update_port<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: PortUpdate,
+    revision: Option<u32>,
+) -> Result<Port> {
     debug!("Updating port {} with {:?}", id.as_ref(), update);
+    if update.dns_domain.is_some() || update.dns_name.is_some() {
+        require_extension(session, NetworkExtension::DnsIntegration, "DNS name")?;
+    }
+    if update.port_security_enabled.is_some() {
+        require_extension(session, NetworkExtension::PortSecurity, "Port security")?;
+    }
     let body = PortUpdateRoot { port: update };
-    let root: PortRoot = session.put_json(NETWORK, &["ports", id.as_ref()], body, None)?;
+    let root: PortRoot = put_json_if_revision(session, &["ports", id.as_ref()], body, revision)?;
     debug!("Updated port {:?}", root.port);
     Ok(root.port)
 }
 
+/// Update a port forwarding of a floating IP.
+pub fn update_port_forwarding<S1, S2>(
+    session: &Session,
+    floating_ip_id: S1,
+    id: S2,
+    update: PortForwardingUpdate,
+) -> Result<PortForwarding>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+{
+    require_extension(
+        session,
+        NetworkExtension::FloatingIpPortForwarding,
+        "Port forwarding",
+    )?;
+    debug!(
+        "Updating port forwarding {} of floating IP {} with {:?}",
+        id.as_ref(),
+        floating_ip_id.as_ref(),
+        update
+    );
+    let body = PortForwardingUpdateRoot {
+        port_forwarding: update,
+    };
+    let root: PortForwardingRoot = session.put_json(
+        NETWORK,
+        &[
+            "floatingips",
+            floating_ip_id.as_ref(),
+            "port_forwardings",
+            id.as_ref(),
+        ],
+        body,
+        None,
+    )?;
+    debug!("Updated port forwarding {:?}", root.port_forwarding);
+    Ok(root.port_forwarding)
+}
+
+/// Update a QoS policy.
+pub fn update_qos_policy<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: QosPolicyUpdate,
+) -> Result<QosPolicy> {
+    debug!("Updating QoS policy {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let body = QosPolicyUpdateRoot { policy: update };
+    let root: QosPolicyRoot =
+        session.put_json(NETWORK, &["qos", "policies", id.as_ref()], body, None)?;
+    debug!("Updated QoS policy {:?}", root.policy);
+    Ok(root.policy)
+}
+
+/// Update a QoS rule of the given kind (e.g. `bandwidth_limit`).
+pub fn update_qos_rule<S1, S2, T>(
+    session: &Session,
+    policy_id: S1,
+    kind: &str,
+    id: S2,
+    update: T,
+) -> Result<T>
+where
+    S1: AsRef<str>,
+    S2: AsRef<str>,
+    T: Serialize + DeserializeOwned + Debug + Send,
+{
+    debug!(
+        "Updating {} rule {} of QoS policy {} with {:?}",
+        kind,
+        id.as_ref(),
+        policy_id.as_ref(),
+        update
+    );
+    require_extension(session, NetworkExtension::Qos, "QoS")?;
+    let key = format!("{}_rule", kind);
+    let mut body = HashMap::new();
+    let _ = body.insert(key.clone(), update);
+    let root: HashMap<String, T> = session.put_json(
+        NETWORK,
+        &[
+            "qos",
+            "policies",
+            policy_id.as_ref(),
+            &format!("{}_rules", kind),
+            id.as_ref(),
+        ],
+        body,
+        None,
+    )?;
+    let result = extract_root(root, &key)?;
+    debug!("Updated QoS rule {:?}", result);
+    Ok(result)
+}
+
+/// Update an RBAC policy.
+pub fn update_rbac_policy<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: RbacPolicyUpdate,
+) -> Result<RbacPolicy> {
+    debug!("Updating RBAC policy {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Rbac, "RBAC policies")?;
+    let body = RbacPolicyUpdateRoot {
+        rbac_policy: update,
+    };
+    let root: RbacPolicyRoot =
+        session.put_json(NETWORK, &["rbac-policies", id.as_ref()], body, None)?;
+    debug!("Updated RBAC policy {:?}", root.rbac_policy);
+    Ok(root.rbac_policy)
+}
+
+/// Update a router.
+pub fn update_router<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: RouterUpdate,
+    revision: Option<u32>,
+) -> Result<Router> {
+    debug!("Updating router {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Router, "Routers")?;
+    if update.routes.is_some() {
+        require_extension(session, NetworkExtension::ExtraRoute, "Extra routes")?;
+    }
+    let body = RouterUpdateRoot { router: update };
+    let root: RouterRoot =
+        put_json_if_revision(session, &["routers", id.as_ref()], body, revision)?;
+    debug!("Updated router {:?}", root.router);
+    Ok(root.router)
+}
+
+/// Update a security group.
+pub fn update_security_group<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: SecurityGroupUpdate,
+) -> Result<SecurityGroup> {
+    debug!("Updating security group {} with {:?}", id.as_ref(), update);
+    let body = SecurityGroupUpdateRoot {
+        security_group: update,
+    };
+    let root: SecurityGroupRoot =
+        session.put_json(NETWORK, &["security-groups", id.as_ref()], body, None)?;
+    debug!("Updated security group {:?}", root.security_group);
+    Ok(root.security_group)
+}
+
+/// Update a network segment.
+pub fn update_segment<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: SegmentUpdate,
+) -> Result<Segment> {
+    debug!("Updating segment {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Segment, "Network segments")?;
+    let body = SegmentUpdateRoot { segment: update };
+    let root: SegmentRoot = session.put_json(NETWORK, &["segments", id.as_ref()], body, None)?;
+    debug!("Updated segment {:?}", root.segment);
+    Ok(root.segment)
+}
+
 /// Update a subnet.
 pub fn update_subnet<S: AsRef<str>>(
     session: &Session,
     id: S,
     update: SubnetUpdate,
+    revision: Option<u32>,
 ) -> Result<Subnet> {
     debug!("Updating subnet {} with {:?}", id.as_ref(), update);
     let body = SubnetUpdateRoot { subnet: update };
-    let root: SubnetRoot = session.put_json(NETWORK, &["subnets", id.as_ref()], body, None)?;
+    let root: SubnetRoot =
+        put_json_if_revision(session, &["subnets", id.as_ref()], body, revision)?;
     debug!("Updated subnet {:?}", root.subnet);
     Ok(root.subnet)
 }
+
+/// Update a subnet pool.
+pub fn update_subnet_pool<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: SubnetPoolUpdate,
+) -> Result<SubnetPool> {
+    debug!("Updating subnet pool {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::SubnetAllocation, "Subnet pools")?;
+    let body = SubnetPoolUpdateRoot { subnetpool: update };
+    let root: SubnetPoolRoot =
+        session.put_json(NETWORK, &["subnetpools", id.as_ref()], body, None)?;
+    debug!("Updated subnet pool {:?}", root.subnetpool);
+    Ok(root.subnetpool)
+}
+
+/// Update a trunk.
+pub fn update_trunk<S: AsRef<str>>(session: &Session, id: S, update: TrunkUpdate) -> Result<Trunk> {
+    debug!("Updating trunk {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Trunk, "Trunks")?;
+    let body = TrunkUpdateRoot { trunk: update };
+    let root: TrunkRoot = session.put_json(NETWORK, &["trunks", id.as_ref()], body, None)?;
+    debug!("Updated trunk {:?}", root.trunk);
+    Ok(root.trunk)
+}
+
+/// Update a VPN service.
+pub fn update_vpn_service<S: AsRef<str>>(
+    session: &Session,
+    id: S,
+    update: VpnServiceUpdate,
+) -> Result<VpnService> {
+    debug!("Updating VPN service {} with {:?}", id.as_ref(), update);
+    require_extension(session, NetworkExtension::Vpnaas, "VPN-as-a-Service")?;
+    let body = VpnServiceUpdateRoot { vpnservice: update };
+    let root: VpnServiceRoot =
+        session.put_json(NETWORK, &["vpn", "vpnservices", id.as_ref()], body, None)?;
+    debug!("Updated VPN service {:?}", root.vpnservice);
+    Ok(root.vpnservice)
+}
+
+/// Check that the auto-allocated topology can be created for a project.
+pub fn validate_auto_allocated_topology<S: AsRef<str>>(
+    session: &Session,
+    project_id: S,
+) -> Result<()> {
+    trace!(
+        "Validating auto-allocated topology of project {}",
+        project_id.as_ref()
+    );
+    require_extension(
+        session,
+        NetworkExtension::AutoAllocatedTopology,
+        "Auto-allocated topology",
+    )?;
+    let _: serde_json::Value = session.get_json_query(
+        NETWORK,
+        &["auto-allocated-topology", project_id.as_ref()],
+        &[("fields", "dry-run")],
+        None,
+    )?;
+    trace!(
+        "Auto-allocated topology of project {} can be created",
+        project_id.as_ref()
+    );
+    Ok(())
+}
+
+/// PUT a JSON, optionally only if the revision number matches.
+fn put_json_if_revision<T, R>(
+    session: &Session,
+    path: &[&str],
+    body: T,
+    revision: Option<u32>,
+) -> Result<R>
+where
+    T: Serialize + Send,
+    R: DeserializeOwned + Send,
+{
+    let mut builder = session
+        .request(NETWORK, Method::PUT, path, None)?
+        .json(&body);
+    if let Some(revision) = revision {
+        require_extension(
+            session,
+            NetworkExtension::RevisionIfMatch,
+            "Conditional update",
+        )?;
+        trace!("Requesting an update only for revision {}", revision);
+        builder = builder.header("If-Match", format!("revision_number={}", revision));
+    }
+    session.fetch_json(builder)
+}
+
+fn bulk_rolled_back(err: Error, count: usize, resource_type: &str) -> Error {
+    match err.kind() {
+        // Only an error response guarantees that the request was rolled back.
+        ErrorKind::AuthenticationFailed
+        | ErrorKind::AccessDenied
+        | ErrorKind::ResourceNotFound
+        | ErrorKind::InvalidInput
+        | ErrorKind::IncompatibleApiVersion
+        | ErrorKind::Conflict
+        | ErrorKind::InternalServerError => Error::new(
+            err.kind(),
+            format!(
+                "Bulk creation of {} {} failed, no resources were created: {}",
+                count, resource_type, err
+            ),
+        ),
+        _ => err,
+    }
+}
+
+fn check_bulk_result<T>(result: Vec<T>, count: usize, resource_type: &str) -> Result<Vec<T>> {
+    if result.len() == count {
+        Ok(result)
+    } else {
+        Err(Error::new(
+            ErrorKind::InvalidResponse,
+            format!(
+                "Requested creation of {} {}, but {} were returned",
+                count,
+                resource_type,
+                result.len()
+            ),
+        ))
+    }
+}
+
+fn extract_root<T>(mut root: HashMap<String, T>, key: &str) -> Result<T> {
+    root.remove(key).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidResponse,
+            format!("Missing {} in the response", key),
+        )
+    })
+}
+
+fn extract_partial_root(
+    root: HashMap<String, serde_json::Value>,
+    key: &str,
+) -> Result<Vec<PartialResource>> {
+    // Other keys (e.g. pagination links) have a different structure.
+    serde_json::from_value(extract_root(root, key)?).map_err(|err| {
+        Error::new(
+            ErrorKind::InvalidResponse,
+            format!("Cannot parse {}: {}", key, err),
+        )
+    })
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use serde_json;
+
+    use super::super::super::utils::test::{mock_session, MockRequest};
+    use super::super::super::utils::Session;
+    use super::super::super::ErrorKind;
+    use super::super::protocol::Network;
+    use super::super::protocol::{NetworkType, PortUpdate};
+    use super::{
+        create_network, create_networks, extract_partial_root, put_json_if_revision,
+        router_routes_action, update_port,
+    };
+
+    /// A session to a mock Network API with the given extensions.
+    ///
+    /// All requests except for listing extensions succeed with an empty object.
+    pub fn network_session(
+        extensions: &'static [&'static str],
+    ) -> (Session, Arc<Mutex<Vec<MockRequest>>>) {
+        mock_network_session(extensions, |_| (200, serde_json::json!({})))
+    }
+
+    /// A session to a mock Network API with the given extensions.
+    ///
+    /// All requests except for listing extensions are answered by the handler.
+    pub fn mock_network_session<F>(
+        extensions: &'static [&'static str],
+        handler: F,
+    ) -> (Session, Arc<Mutex<Vec<MockRequest>>>)
+    where
+        F: Fn(&MockRequest) -> (u16, serde_json::Value) + Send + 'static,
+    {
+        mock_session("2.1", move |request| {
+            if request.path.ends_with("/extensions") {
+                let extensions: Vec<_> = extensions
+                    .iter()
+                    .map(|alias| serde_json::json!({ "alias": alias }))
+                    .collect();
+                (200, serde_json::json!({ "extensions": extensions }))
+            } else {
+                handler(request)
+            }
+        })
+    }
+
+    #[test]
+    fn test_extract_partial_root_with_links() {
+        let root: HashMap<String, serde_json::Value> = serde_json::from_str(
+            r#"{
+                "ports": [{"id": "abcd", "name": "port"}],
+                "ports_links": [{"href": "https://example.com/ports?marker=abcd", "rel": "next"}]
+            }"#,
+        )
+        .unwrap();
+        let result = extract_partial_root(root, "ports").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id(), "abcd");
+    }
+
+    #[test]
+    fn test_put_json_if_revision() {
+        let (session, requests) = network_session(&["revision-if-match"]);
+        let body = serde_json::json!({ "network": { "name": "new" } });
+        let _: serde_json::Value =
+            put_json_if_revision(&session, &["networks", "id"], body.clone(), Some(42)).unwrap();
+        let _: serde_json::Value =
+            put_json_if_revision(&session, &["networks", "id"], body, None).unwrap();
+
+        let requests = requests.lock().unwrap();
+        let updates: Vec<_> = requests.iter().filter(|r| r.method == "PUT").collect();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].path, "/v2.1/networks/id");
+        assert_eq!(updates[0].header("if-match"), Some("revision_number=42"));
+        assert_eq!(updates[1].header("if-match"), None);
+    }
+
+    #[test]
+    fn test_put_json_if_revision_requires_extension() {
+        let (session, requests) = network_session(&[]);
+        let body = serde_json::json!({ "network": { "name": "new" } });
+        let err = put_json_if_revision::<_, serde_json::Value>(
+            &session,
+            &["networks", "id"],
+            body,
+            Some(42),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(requests.lock().unwrap().iter().all(|r| r.method != "PUT"));
+    }
+
+    #[test]
+    fn test_router_routes_require_atomic_extension() {
+        let (session, requests) = network_session(&["router", "extraroute"]);
+        let err = router_routes_action(&session, "id", "add_extraroutes", Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(requests.lock().unwrap().iter().all(|r| r.method != "PUT"));
+    }
+
+    #[test]
+    fn test_create_network_requires_provider_extension() {
+        let (session, requests) = network_session(&["port-security"]);
+        let mut request = network_request("provider");
+        request.port_security_enabled = Some(false);
+        request.provider_network_type = Some(NetworkType::Vlan);
+        let err = create_network(&session, request).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(err.to_string().contains("provider"), "{}", err);
+        assert!(requests.lock().unwrap().iter().all(|r| r.method != "POST"));
+    }
+
+    #[test]
+    fn test_create_networks_requires_port_security_extension() {
+        let (session, requests) = network_session(&["provider"]);
+        let mut second = network_request("second");
+        second.port_security_enabled = Some(false);
+        let requests_to_create = vec![network_request("first"), second];
+        let err = create_networks(&session, requests_to_create).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(err.to_string().contains("port-security"), "{}", err);
+        assert!(requests.lock().unwrap().iter().all(|r| r.method != "POST"));
+    }
+
+    #[test]
+    fn test_update_port_requires_port_security_extension() {
+        let (session, requests) = network_session(&[]);
+        let update = PortUpdate {
+            port_security_enabled: Some(true),
+            ..Default::default()
+        };
+        let err = update_port(&session, "id", update, None).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IncompatibleApiVersion);
+        assert!(requests.lock().unwrap().iter().all(|r| r.method != "PUT"));
+
+        let (session, requests) = network_session(&["port-security"]);
+        let update = PortUpdate {
+            port_security_enabled: Some(true),
+            ..Default::default()
+        };
+        let _ = update_port(&session, "id", update, None);
+        assert!(requests.lock().unwrap().iter().any(|r| r.method == "PUT"));
+    }
+
+    fn network_request(name: &str) -> Network {
+        serde_json::from_value(serde_json::json!({
+            "admin_state_up": true,
+            "id": "",
+            "name": name,
+            "shared": false,
+            "status": "ACTIVE",
+            "subnets": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_create_networks_keeps_order() {
+        let (session, requests) = mock_session("2.1", |request| {
+            let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+            let networks: Vec<_> = body["networks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .enumerate()
+                .map(|(index, network)| {
+                    serde_json::json!({
+                        "admin_state_up": true,
+                        "id": format!("id-{}", index),
+                        "name": network["name"],
+                        "shared": false,
+                        "status": "ACTIVE",
+                        "subnets": [],
+                    })
+                })
+                .collect();
+            (201, serde_json::json!({ "networks": networks }))
+        });
+        let networks = vec![network_request("first"), network_request("second")];
+        let result = create_networks(&session, networks).unwrap();
+        let names: Vec<_> = result.iter().map(|n| n.name.clone().unwrap()).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        let ids: Vec<_> = result.iter().map(|n| n.id.clone()).collect();
+        assert_eq!(ids, vec!["id-0", "id-1"]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/v2.1/networks");
+    }
+
+    #[test]
+    fn test_create_networks_rolled_back() {
+        let (session, _) = mock_session("2.1", |_| {
+            (
+                409,
+                serde_json::json!({
+                    "NeutronError": {
+                        "type": "IpAddressGenerationFailure",
+                        "message": "No more IP addresses available",
+                        "detail": "",
+                    }
+                }),
+            )
+        });
+        let requests = vec![network_request("first"), network_request("second")];
+        let err = create_networks(&session, requests).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Conflict);
+        let message = err.to_string();
+        assert!(
+            message.contains("Bulk creation of 2 networks failed, no resources were created"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_create_networks_short_response() {
+        let (session, _) = mock_session("2.1", |_| {
+            (
+                201,
+                serde_json::json!({
+                    "networks": [{
+                        "admin_state_up": true,
+                        "id": "id-0",
+                        "name": "first",
+                        "shared": false,
+                        "status": "ACTIVE",
+                        "subnets": [],
+                    }]
+                }),
+            )
+        });
+        let requests = vec![network_request("first"), network_request("second")];
+        let err = create_networks(&session, requests).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidResponse);
+    }
+}
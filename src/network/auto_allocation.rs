@@ -0,0 +1,47 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Auto-allocated topology ("get me a network") via Network API.
+
+use std::rc::Rc;
+
+use super::super::common::{IntoVerified, ProjectRef};
+use super::super::utils::Session;
+use super::super::Result;
+use super::{api, Network};
+
+/// Get the auto-allocated network of a project, allocating it if needed.
+pub(crate) fn auto_allocated_topology(
+    session: Rc<Session>,
+    project: ProjectRef,
+) -> Result<Network> {
+    let project = project.into_verified(&session)?;
+    let topology = api::get_auto_allocated_topology(&session, project)?;
+    Network::load(session, topology.id)
+}
+
+/// Delete the auto-allocated topology of a project.
+pub(crate) fn delete_auto_allocated_topology(session: &Session, project: ProjectRef) -> Result<()> {
+    let project = project.into_verified(session)?;
+    api::delete_auto_allocated_topology(session, project)
+}
+
+/// Check that the auto-allocated topology can be created for a project.
+pub(crate) fn validate_auto_allocated_topology(
+    session: &Session,
+    project: ProjectRef,
+) -> Result<()> {
+    let project = project.into_verified(session)?;
+    api::validate_auto_allocated_topology(session, project)
+}
@@ -0,0 +1,353 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! VPN endpoint groups management via Network API (VPNaaS).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, EndpointGroupRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol};
+
+/// A query to VPN endpoint group list.
+#[derive(Clone, Debug)]
+pub struct EndpointGroupQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single VPN endpoint group.
+#[derive(Clone, Debug)]
+pub struct EndpointGroup {
+    session: Rc<Session>,
+    inner: protocol::EndpointGroup,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a VPN endpoint group.
+#[derive(Clone, Debug)]
+pub struct NewEndpointGroup {
+    session: Rc<Session>,
+    inner: protocol::EndpointGroup,
+}
+
+impl EndpointGroup {
+    /// Create a VPN endpoint group object.
+    fn new(session: Rc<Session>, inner: protocol::EndpointGroup) -> EndpointGroup {
+        EndpointGroup {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an EndpointGroup object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<EndpointGroup> {
+        let inner = api::get_endpoint_group(&session, id)?;
+        Ok(EndpointGroup::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Endpoint group description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Type of the endpoints."]
+        endpoint_type: protocol::EndpointGroupType
+    }
+
+    transparent_property! {
+        #[doc = "Endpoints: subnet IDs or CIDRs depending on the endpoint type."]
+        endpoints: ref Vec<String>
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Endpoint group name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the endpoint group (if available)."]
+        project_id: ref Option<String>
+    }
+
+    /// Delete the VPN endpoint group.
+    pub fn delete(self) -> Result<DeletionWaiter<EndpointGroup>> {
+        api::delete_endpoint_group(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the VPN endpoint group is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the VPN endpoint group.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::EndpointGroupUpdate::default();
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_endpoint_group(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for EndpointGroup {
+    /// Refresh the VPN endpoint group.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_endpoint_group_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl EndpointGroupQuery {
+    pub(crate) fn new(session: Rc<Session>) -> EndpointGroupQuery {
+        EndpointGroupQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::EndpointGroupSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    /// Filter by endpoint type.
+    pub fn set_endpoint_type(&mut self, value: protocol::EndpointGroupType) {
+        self.query.push("type", value);
+    }
+
+    /// Filter by endpoint type.
+    #[inline]
+    pub fn with_endpoint_type(mut self, value: protocol::EndpointGroupType) -> Self {
+        self.set_endpoint_type(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<EndpointGroupQuery> {
+        debug!("Fetching VPN endpoint groups with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<EndpointGroup>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<EndpointGroup> {
+        debug!("Fetching one VPN endpoint group with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for EndpointGroupQuery {
+    type Item = EndpointGroup;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_endpoint_groups(&self.session, &query)?
+            .into_iter()
+            .map(|item| EndpointGroup::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    EndpointGroupQuery: ["vpn", "endpoint-groups"] -> "endpoint_groups",
+    Vpnaas: "VPN-as-a-Service"
+);
+
+impl NewEndpointGroup {
+    /// Start creating a VPN endpoint group.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        endpoint_type: protocol::EndpointGroupType,
+    ) -> NewEndpointGroup {
+        NewEndpointGroup {
+            session,
+            inner: protocol::EndpointGroup {
+                description: None,
+                endpoints: Vec::new(),
+                endpoint_type,
+                id: String::new(),
+                name: None,
+                project_id: None,
+            },
+        }
+    }
+
+    /// Request creation of the VPN endpoint group.
+    ///
+    /// Fails with `InvalidInput` if no endpoints were added.
+    pub fn create(self) -> Result<EndpointGroup> {
+        if self.inner.endpoints.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "At least one endpoint is required",
+            ));
+        }
+
+        let inner = api::create_endpoint_group(&self.session, self.inner)?;
+        Ok(EndpointGroup::new(self.session, inner))
+    }
+
+    /// Add an endpoint: a subnet ID or a CIDR depending on the endpoint type.
+    pub fn add_endpoint<S: Into<String>>(&mut self, endpoint: S) {
+        self.inner.endpoints.push(endpoint.into());
+    }
+
+    /// Add an endpoint: a subnet ID or a CIDR depending on the endpoint type.
+    pub fn with_endpoint<S: Into<String>>(mut self, endpoint: S) -> Self {
+        self.add_endpoint(endpoint);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the endpoint group."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the endpoint group."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the endpoint group (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+}
+
+impl IntoFallibleIterator for EndpointGroupQuery {
+    type Item = EndpointGroup;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<EndpointGroupQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<EndpointGroup> for EndpointGroupRef {
+    fn from(value: EndpointGroup) -> EndpointGroupRef {
+        EndpointGroupRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for EndpointGroupRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<EndpointGroupRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            EndpointGroupRef::new_verified(api::get_endpoint_group(session, &self.value)?.id)
+        })
+    }
+}
@@ -0,0 +1,567 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Firewall groups management via Network API (FWaaS v2).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, FirewallPolicyRef, IntoVerified, PortRef, Refresh, ResourceIterator,
+    ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, FirewallPolicy, Port};
+
+/// A query to firewall group list.
+#[derive(Clone, Debug)]
+pub struct FirewallGroupQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single firewall group.
+#[derive(Clone, Debug)]
+pub struct FirewallGroup {
+    session: Rc<Session>,
+    inner: protocol::FirewallGroup,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a firewall group.
+#[derive(Clone, Debug)]
+pub struct NewFirewallGroup {
+    session: Rc<Session>,
+    inner: protocol::FirewallGroup,
+    egress_firewall_policy: Option<FirewallPolicyRef>,
+    ingress_firewall_policy: Option<FirewallPolicyRef>,
+    ports: Vec<PortRef>,
+}
+
+impl FirewallGroup {
+    /// Create a firewall group object.
+    fn new(session: Rc<Session>, inner: protocol::FirewallGroup) -> FirewallGroup {
+        FirewallGroup {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a FirewallGroup object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<FirewallGroup> {
+        let inner = api::get_firewall_group(&session, id)?;
+        Ok(FirewallGroup::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "The administrative state of the firewall group."]
+        admin_state_up: bool
+    }
+
+    update_field! {
+        #[doc = "Set the administrative state of the firewall group."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    transparent_property! {
+        #[doc = "Firewall group description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the policy applied to the egress traffic (if any)."]
+        egress_firewall_policy_id: ref Option<String>
+    }
+
+    /// Fetch the policy applied to the egress traffic.
+    ///
+    /// Fails with `ResourceNotFound` if no egress policy is set.
+    pub fn egress_firewall_policy(&self) -> Result<FirewallPolicy> {
+        match self.inner.egress_firewall_policy_id {
+            Some(ref id) => FirewallPolicy::load(self.session.clone(), id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "Firewall group has no egress policy",
+            )),
+        }
+    }
+
+    /// Apply a policy to the egress traffic.
+    #[allow(unused_results)]
+    pub fn set_egress_firewall_policy<P>(&mut self, policy: P) -> Result<()>
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        let verified = policy.into().into_verified(&self.session)?;
+        self.inner.egress_firewall_policy_id = Some(verified.into());
+        self.dirty.insert("egress_firewall_policy_id");
+        Ok(())
+    }
+
+    /// Remove the policy applied to the egress traffic.
+    #[allow(unused_results)]
+    pub fn clear_egress_firewall_policy(&mut self) {
+        self.inner.egress_firewall_policy_id = None;
+        self.dirty.insert("egress_firewall_policy_id");
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the policy applied to the ingress traffic (if any)."]
+        ingress_firewall_policy_id: ref Option<String>
+    }
+
+    /// Fetch the policy applied to the ingress traffic.
+    ///
+    /// Fails with `ResourceNotFound` if no ingress policy is set.
+    pub fn ingress_firewall_policy(&self) -> Result<FirewallPolicy> {
+        match self.inner.ingress_firewall_policy_id {
+            Some(ref id) => FirewallPolicy::load(self.session.clone(), id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "Firewall group has no ingress policy",
+            )),
+        }
+    }
+
+    /// Apply a policy to the ingress traffic.
+    #[allow(unused_results)]
+    pub fn set_ingress_firewall_policy<P>(&mut self, policy: P) -> Result<()>
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        let verified = policy.into().into_verified(&self.session)?;
+        self.inner.ingress_firewall_policy_id = Some(verified.into());
+        self.dirty.insert("ingress_firewall_policy_id");
+        Ok(())
+    }
+
+    /// Remove the policy applied to the ingress traffic.
+    #[allow(unused_results)]
+    pub fn clear_ingress_firewall_policy(&mut self) {
+        self.inner.ingress_firewall_policy_id = None;
+        self.dirty.insert("ingress_firewall_policy_id");
+    }
+
+    transparent_property! {
+        #[doc = "Firewall group name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "IDs of the ports the firewall group is associated with."]
+        ports: ref Vec<String>
+    }
+
+    /// Fetch the ports the firewall group is associated with.
+    pub fn fetch_ports(&self) -> Result<Vec<Port>> {
+        self.inner
+            .ports
+            .iter()
+            .map(|id| Port::load(self.session.clone(), id))
+            .collect()
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the firewall group (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the firewall group is shared with all projects."]
+        shared: bool
+    }
+
+    update_field! {
+        #[doc = "Configure whether the firewall group is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    transparent_property! {
+        #[doc = "Status of the firewall group."]
+        status: protocol::FirewallGroupStatus
+    }
+
+    /// Associate the firewall group with a port.
+    ///
+    /// This call takes effect immediately. Associating with a port that is
+    /// already associated is a no-op.
+    pub fn add_port<P>(&mut self, port: P) -> Result<()>
+    where
+        P: Into<PortRef>,
+    {
+        let port: String = port.into().into_verified(&self.session)?.into();
+        if self.inner.ports.contains(&port) {
+            return Ok(());
+        }
+
+        let mut ports = self.inner.ports.clone();
+        ports.push(port);
+        self.update_ports(ports)
+    }
+
+    /// Dissociate the firewall group from a port.
+    ///
+    /// This call takes effect immediately. Fails with `ResourceNotFound`
+    /// if the port is not associated with the firewall group.
+    pub fn remove_port<P>(&mut self, port: P) -> Result<()>
+    where
+        P: Into<PortRef>,
+    {
+        let port: String = port.into().into_verified(&self.session)?.into();
+        if !self.inner.ports.contains(&port) {
+            return Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                format!("Port {} is not associated with the firewall group", port),
+            ));
+        }
+
+        let ports = self
+            .inner
+            .ports
+            .iter()
+            .filter(|item| **item != port)
+            .cloned()
+            .collect();
+        self.update_ports(ports)
+    }
+
+    /// Delete the firewall group.
+    pub fn delete(self) -> Result<DeletionWaiter<FirewallGroup>> {
+        api::delete_firewall_group(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the firewall group is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the firewall group.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::FirewallGroupUpdate::default();
+        save_fields! {
+            self -> update: admin_state_up egress_firewall_policy_id ingress_firewall_policy_id
+                shared
+        };
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_firewall_group(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Update only the ports, keeping other local changes.
+    fn update_ports(&mut self, ports: Vec<String>) -> Result<()> {
+        let update = protocol::FirewallGroupUpdate {
+            ports: Some(ports),
+            ..Default::default()
+        };
+        let inner = api::update_firewall_group(&self.session, &self.inner.id, update)?;
+        self.inner.ports = inner.ports;
+        self.inner.status = inner.status;
+        Ok(())
+    }
+}
+
+impl Refresh for FirewallGroup {
+    /// Refresh the firewall group.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_firewall_group_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl FirewallGroupQuery {
+    pub(crate) fn new(session: Rc<Session>) -> FirewallGroupQuery {
+        FirewallGroupQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::FirewallGroupSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by administrative state."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the firewall group is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by status."]
+        set_status, with_status -> status: protocol::FirewallGroupStatus
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<FirewallGroupQuery> {
+        debug!("Fetching firewall groups with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<FirewallGroup>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<FirewallGroup> {
+        debug!("Fetching one firewall group with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for FirewallGroupQuery {
+    type Item = FirewallGroup;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_firewall_groups(&self.session, &query)?
+            .into_iter()
+            .map(|item| FirewallGroup::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    FirewallGroupQuery: ["fwaas", "firewall_groups"] -> "firewall_groups",
+    Fwaas: "Firewall-as-a-Service"
+);
+
+impl NewFirewallGroup {
+    /// Start creating a firewall group.
+    pub(crate) fn new(session: Rc<Session>) -> NewFirewallGroup {
+        NewFirewallGroup {
+            session,
+            inner: protocol::FirewallGroup {
+                admin_state_up: true,
+                description: None,
+                // Will be replaced in create()
+                egress_firewall_policy_id: None,
+                id: String::new(),
+                // Will be replaced in create()
+                ingress_firewall_policy_id: None,
+                name: None,
+                // Will be replaced in create()
+                ports: Vec::new(),
+                project_id: None,
+                shared: false,
+                // Dummy value, not used when serializing
+                status: protocol::FirewallGroupStatus::Inactive,
+            },
+            egress_firewall_policy: None,
+            ingress_firewall_policy: None,
+            ports: Vec::new(),
+        }
+    }
+
+    /// Request creation of the firewall group.
+    pub fn create(mut self) -> Result<FirewallGroup> {
+        if let Some(policy) = self.egress_firewall_policy {
+            let verified = policy.into_verified(&self.session)?;
+            self.inner.egress_firewall_policy_id = Some(verified.into());
+        }
+        if let Some(policy) = self.ingress_firewall_policy {
+            let verified = policy.into_verified(&self.session)?;
+            self.inner.ingress_firewall_policy_id = Some(verified.into());
+        }
+        for port in self.ports {
+            let verified = port.into_verified(&self.session)?;
+            self.inner.ports.push(verified.into());
+        }
+
+        let inner = api::create_firewall_group(&self.session, self.inner)?;
+        Ok(FirewallGroup::new(self.session, inner))
+    }
+
+    /// Add a port to associate the firewall group with.
+    pub fn add_port<P>(&mut self, port: P)
+    where
+        P: Into<PortRef>,
+    {
+        self.ports.push(port.into());
+    }
+
+    /// Add a port to associate the firewall group with.
+    pub fn with_port<P>(mut self, port: P) -> Self
+    where
+        P: Into<PortRef>,
+    {
+        self.add_port(port);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set administrative status for the firewall group."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the firewall group."]
+        set_description, with_description -> description: optional String
+    }
+
+    /// Set the policy to apply to the egress traffic.
+    pub fn set_egress_firewall_policy<P>(&mut self, policy: P)
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        self.egress_firewall_policy = Some(policy.into());
+    }
+
+    /// Set the policy to apply to the egress traffic.
+    pub fn with_egress_firewall_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        self.set_egress_firewall_policy(policy);
+        self
+    }
+
+    /// Set the policy to apply to the ingress traffic.
+    pub fn set_ingress_firewall_policy<P>(&mut self, policy: P)
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        self.ingress_firewall_policy = Some(policy.into());
+    }
+
+    /// Set the policy to apply to the ingress traffic.
+    pub fn with_ingress_firewall_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<FirewallPolicyRef>,
+    {
+        self.set_ingress_firewall_policy(policy);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the firewall group."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the firewall group (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the firewall group is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+}
+
+impl IntoFallibleIterator for FirewallGroupQuery {
+    type Item = FirewallGroup;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<FirewallGroupQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
@@ -0,0 +1,518 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Firewall policies management via Network API (FWaaS v2).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, FirewallPolicyRef, FirewallRuleRef, IntoVerified, Refresh, ResourceIterator,
+    ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, FirewallRule};
+
+/// A query to firewall policy list.
+#[derive(Clone, Debug)]
+pub struct FirewallPolicyQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single firewall policy.
+#[derive(Clone, Debug)]
+pub struct FirewallPolicy {
+    session: Rc<Session>,
+    inner: protocol::FirewallPolicy,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a firewall policy.
+#[derive(Clone, Debug)]
+pub struct NewFirewallPolicy {
+    session: Rc<Session>,
+    inner: protocol::FirewallPolicy,
+    firewall_rules: Vec<FirewallRuleRef>,
+}
+
+impl FirewallPolicy {
+    /// Create a firewall policy object.
+    fn new(session: Rc<Session>, inner: protocol::FirewallPolicy) -> FirewallPolicy {
+        FirewallPolicy {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a FirewallPolicy object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<FirewallPolicy> {
+        let inner = api::get_firewall_policy(&session, id)?;
+        Ok(FirewallPolicy::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Whether the policy has been audited."]
+        audited: bool
+    }
+
+    update_field! {
+        #[doc = "Mark the policy as audited or not."]
+        set_audited, with_audited -> audited: bool
+    }
+
+    transparent_property! {
+        #[doc = "Policy description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "IDs of the rules of the policy in the order they are applied."]
+        firewall_rules: ref Vec<String>
+    }
+
+    /// Fetch the rules of the policy in the order they are applied.
+    pub fn rules(&self) -> Result<Vec<FirewallRule>> {
+        self.inner
+            .firewall_rules
+            .iter()
+            .map(|id| FirewallRule::load(self.session.clone(), id))
+            .collect()
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Policy name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the policy (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the policy is shared with all projects."]
+        shared: bool
+    }
+
+    update_field! {
+        #[doc = "Configure whether the policy is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Insert a rule into the policy at the given position.
+    ///
+    /// The rule is placed before the rule currently at `position`, positions
+    /// past the end of the rule list append the rule. This call takes effect
+    /// immediately.
+    pub fn insert_rule<R>(&mut self, rule: R, position: usize) -> Result<()>
+    where
+        R: Into<FirewallRuleRef>,
+    {
+        let rule = rule.into().into_verified(&self.session)?;
+        let rules = &self.inner.firewall_rules;
+        let (insert_before, insert_after) = if position < rules.len() {
+            (Some(rules[position].clone()), None)
+        } else {
+            (None, rules.last().cloned())
+        };
+        let request = protocol::FirewallPolicyRuleRequest {
+            firewall_rule_id: rule.into(),
+            insert_after,
+            insert_before,
+        };
+        self.rule_action("insert_rule", request)
+    }
+
+    /// Remove a rule from the policy.
+    ///
+    /// This call takes effect immediately.
+    pub fn remove_rule<R>(&mut self, rule: R) -> Result<()>
+    where
+        R: Into<FirewallRuleRef>,
+    {
+        let rule = rule.into().into_verified(&self.session)?;
+        let request = protocol::FirewallPolicyRuleRequest {
+            firewall_rule_id: rule.into(),
+            insert_after: None,
+            insert_before: None,
+        };
+        self.rule_action("remove_rule", request)
+    }
+
+    /// Delete the firewall policy.
+    pub fn delete(self) -> Result<DeletionWaiter<FirewallPolicy>> {
+        api::delete_firewall_policy(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the firewall policy is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the firewall policy.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::FirewallPolicyUpdate::default();
+        save_fields! {
+            self -> update: audited shared
+        };
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_firewall_policy(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Insert or remove a rule, keeping other local changes.
+    fn rule_action(
+        &mut self,
+        action: &str,
+        request: protocol::FirewallPolicyRuleRequest,
+    ) -> Result<()> {
+        let inner =
+            api::firewall_policy_rule_action(&self.session, &self.inner.id, action, request)?;
+        self.inner.firewall_rules = inner.firewall_rules;
+        if !self.dirty.contains("audited") {
+            self.inner.audited = inner.audited;
+        }
+        Ok(())
+    }
+}
+
+impl Refresh for FirewallPolicy {
+    /// Refresh the firewall policy.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_firewall_policy_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl FirewallPolicyQuery {
+    pub(crate) fn new(session: Rc<Session>) -> FirewallPolicyQuery {
+        FirewallPolicyQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::FirewallPolicySortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the policy is audited."]
+        set_audited, with_audited -> audited: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the policy is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<FirewallPolicyQuery> {
+        debug!("Fetching firewall policies with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<FirewallPolicy>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<FirewallPolicy> {
+        debug!("Fetching one firewall policy with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for FirewallPolicyQuery {
+    type Item = FirewallPolicy;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_firewall_policies(&self.session, &query)?
+            .into_iter()
+            .map(|item| FirewallPolicy::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    FirewallPolicyQuery: ["fwaas", "firewall_policies"] -> "firewall_policies",
+    Fwaas: "Firewall-as-a-Service"
+);
+
+impl NewFirewallPolicy {
+    /// Start creating a firewall policy.
+    pub(crate) fn new(session: Rc<Session>) -> NewFirewallPolicy {
+        NewFirewallPolicy {
+            session,
+            inner: protocol::FirewallPolicy {
+                audited: false,
+                description: None,
+                // Will be replaced in create()
+                firewall_rules: Vec::new(),
+                id: String::new(),
+                name: None,
+                project_id: None,
+                shared: false,
+            },
+            firewall_rules: Vec::new(),
+        }
+    }
+
+    /// Request creation of the firewall policy.
+    pub fn create(mut self) -> Result<FirewallPolicy> {
+        for rule in self.firewall_rules {
+            let verified = rule.into_verified(&self.session)?;
+            self.inner.firewall_rules.push(verified.into());
+        }
+
+        let inner = api::create_firewall_policy(&self.session, self.inner)?;
+        Ok(FirewallPolicy::new(self.session, inner))
+    }
+
+    /// Add a rule to the end of the policy.
+    pub fn add_firewall_rule<R>(&mut self, rule: R)
+    where
+        R: Into<FirewallRuleRef>,
+    {
+        self.firewall_rules.push(rule.into());
+    }
+
+    /// Add a rule to the end of the policy.
+    pub fn with_firewall_rule<R>(mut self, rule: R) -> Self
+    where
+        R: Into<FirewallRuleRef>,
+    {
+        self.add_firewall_rule(rule);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the policy is audited."]
+        set_audited, with_audited -> audited: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the policy."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the policy."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the policy (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the policy is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+}
+
+impl IntoFallibleIterator for FirewallPolicyQuery {
+    type Item = FirewallPolicy;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<FirewallPolicyQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<FirewallPolicy> for FirewallPolicyRef {
+    fn from(value: FirewallPolicy) -> FirewallPolicyRef {
+        FirewallPolicyRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for FirewallPolicyRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<FirewallPolicyRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            FirewallPolicyRef::new_verified(api::get_firewall_policy(session, &self.value)?.id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::common::FirewallRuleRef;
+    use super::super::api::test::mock_network_session;
+    use super::FirewallPolicy;
+
+    fn policy_json(rules: &[&str]) -> Value {
+        json!({
+            "audited": false,
+            "firewall_rules": rules,
+            "id": "policy-id",
+            "shared": false,
+        })
+    }
+
+    fn insert_rule(position: usize) -> Value {
+        let (session, requests) = mock_network_session(&["fwaas_v2"], |_| {
+            (200, policy_json(&["rule-1", "new-rule", "rule-2"]))
+        });
+        let inner = serde_json::from_value(policy_json(&["rule-1", "rule-2"])).unwrap();
+        let mut policy = FirewallPolicy::new(Rc::new(session), inner);
+        policy
+            .insert_rule(FirewallRuleRef::new_verified("new-rule".into()), position)
+            .unwrap();
+        assert_eq!(
+            policy.inner.firewall_rules,
+            vec!["rule-1", "new-rule", "rule-2"]
+        );
+
+        let requests = requests.lock().unwrap();
+        let update = requests.iter().find(|r| r.method == "PUT").unwrap();
+        assert_eq!(
+            update.path,
+            "/v2.1/fwaas/firewall_policies/policy-id/insert_rule"
+        );
+        serde_json::from_str(&update.body).unwrap()
+    }
+
+    #[test]
+    fn test_insert_rule_first() {
+        assert_eq!(
+            insert_rule(0),
+            json!({"firewall_rule_id": "new-rule", "insert_before": "rule-1"})
+        );
+    }
+
+    #[test]
+    fn test_insert_rule_middle() {
+        assert_eq!(
+            insert_rule(1),
+            json!({"firewall_rule_id": "new-rule", "insert_before": "rule-2"})
+        );
+    }
+
+    #[test]
+    fn test_insert_rule_last() {
+        assert_eq!(
+            insert_rule(2),
+            json!({"firewall_rule_id": "new-rule", "insert_after": "rule-2"})
+        );
+        assert_eq!(
+            insert_rule(42),
+            json!({"firewall_rule_id": "new-rule", "insert_after": "rule-2"})
+        );
+    }
+}
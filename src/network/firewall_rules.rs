@@ -0,0 +1,511 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Firewall rules management via Network API (FWaaS v2).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use ipnet;
+
+use super::super::common::{
+    DeletionWaiter, FirewallRuleRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol};
+
+/// A query to firewall rule list.
+#[derive(Clone, Debug)]
+pub struct FirewallRuleQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single firewall rule.
+#[derive(Clone, Debug)]
+pub struct FirewallRule {
+    session: Rc<Session>,
+    inner: protocol::FirewallRule,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a firewall rule.
+#[derive(Clone, Debug)]
+pub struct NewFirewallRule {
+    session: Rc<Session>,
+    inner: protocol::FirewallRule,
+    ip_version: Option<protocol::IpVersion>,
+}
+
+impl FirewallRule {
+    /// Create a firewall rule object.
+    fn new(session: Rc<Session>, inner: protocol::FirewallRule) -> FirewallRule {
+        FirewallRule {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a FirewallRule object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<FirewallRule> {
+        let inner = api::get_firewall_rule(&session, id)?;
+        Ok(FirewallRule::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Action applied to the matching traffic."]
+        action: protocol::FirewallRuleAction
+    }
+
+    update_field! {
+        #[doc = "Update the action applied to the matching traffic."]
+        set_action, with_action -> action: protocol::FirewallRuleAction
+    }
+
+    transparent_property! {
+        #[doc = "Rule description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Destination IP address or prefix (if any)."]
+        destination_ip_address: Option<ipnet::IpNet>
+    }
+
+    update_field! {
+        #[doc = "Update the destination IP address or prefix (`None` for any)."]
+        set_destination_ip_address, with_destination_ip_address -> destination_ip_address: Option<ipnet::IpNet>
+    }
+
+    transparent_property! {
+        #[doc = "Destination port or port range like `80:90` (if any)."]
+        destination_port: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the destination port or port range like `80:90` (`None` for any)."]
+        set_destination_port, with_destination_port -> destination_port: Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the rule is enabled."]
+        enabled: bool
+    }
+
+    update_field! {
+        #[doc = "Enable or disable the rule."]
+        set_enabled, with_enabled -> enabled: bool
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "IP protocol version of the rule."]
+        ip_version: protocol::IpVersion
+    }
+
+    transparent_property! {
+        #[doc = "Rule name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the rule (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "IP protocol name or number (if any)."]
+        protocol: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the IP protocol (`None` for any)."]
+        set_protocol, with_protocol -> protocol: Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the rule is shared with all projects."]
+        shared: bool
+    }
+
+    update_field! {
+        #[doc = "Configure whether the rule is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    transparent_property! {
+        #[doc = "Source IP address or prefix (if any)."]
+        source_ip_address: Option<ipnet::IpNet>
+    }
+
+    update_field! {
+        #[doc = "Update the source IP address or prefix (`None` for any)."]
+        set_source_ip_address, with_source_ip_address -> source_ip_address: Option<ipnet::IpNet>
+    }
+
+    transparent_property! {
+        #[doc = "Source port or port range like `80:90` (if any)."]
+        source_port: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the source port or port range like `80:90` (`None` for any)."]
+        set_source_port, with_source_port -> source_port: Option<String>
+    }
+
+    /// Delete the firewall rule.
+    pub fn delete(self) -> Result<DeletionWaiter<FirewallRule>> {
+        api::delete_firewall_rule(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the firewall rule is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the firewall rule.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::FirewallRuleUpdate::default();
+        save_fields! {
+            self -> update: action destination_ip_address destination_port enabled protocol
+                shared source_ip_address source_port
+        };
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_firewall_rule(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for FirewallRule {
+    /// Refresh the firewall rule.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_firewall_rule_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl FirewallRuleQuery {
+    pub(crate) fn new(session: Rc<Session>) -> FirewallRuleQuery {
+        FirewallRuleQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::FirewallRuleSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by action."]
+        set_action, with_action -> action: protocol::FirewallRuleAction
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the rule is enabled."]
+        set_enabled, with_enabled -> enabled: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by IP protocol."]
+        set_protocol, with_protocol -> protocol
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the rule is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<FirewallRuleQuery> {
+        debug!("Fetching firewall rules with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<FirewallRule>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<FirewallRule> {
+        debug!("Fetching one firewall rule with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for FirewallRuleQuery {
+    type Item = FirewallRule;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_firewall_rules(&self.session, &query)?
+            .into_iter()
+            .map(|item| FirewallRule::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    FirewallRuleQuery: ["fwaas", "firewall_rules"] -> "firewall_rules",
+    Fwaas: "Firewall-as-a-Service"
+);
+
+impl NewFirewallRule {
+    /// Start creating a firewall rule.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        action: protocol::FirewallRuleAction,
+    ) -> NewFirewallRule {
+        NewFirewallRule {
+            session,
+            inner: protocol::FirewallRule {
+                action,
+                description: None,
+                destination_ip_address: None,
+                destination_port: None,
+                enabled: true,
+                id: String::new(),
+                // Will be replaced in create()
+                ip_version: protocol::IpVersion::V4,
+                name: None,
+                project_id: None,
+                protocol: None,
+                shared: false,
+                source_ip_address: None,
+                source_port: None,
+            },
+            ip_version: None,
+        }
+    }
+
+    /// Request creation of the firewall rule.
+    ///
+    /// Fails with `InvalidInput` if the source and destination IP addresses
+    /// have different IP versions.
+    pub fn create(mut self) -> Result<FirewallRule> {
+        let addresses = [
+            self.inner.source_ip_address,
+            self.inner.destination_ip_address,
+        ];
+        let versions: Vec<protocol::IpVersion> = addresses
+            .iter()
+            .filter_map(|addr| addr.map(|value| ip_version_of(&value)))
+            .collect();
+        if versions.windows(2).any(|pair| pair[0] != pair[1]) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Source and destination IP addresses must have the same IP version",
+            ));
+        }
+
+        self.inner.ip_version = match (self.ip_version, versions.first()) {
+            (Some(ip_version), _) => ip_version,
+            (None, Some(ip_version)) => *ip_version,
+            (None, None) => protocol::IpVersion::V4,
+        };
+        let inner = api::create_firewall_rule(&self.session, self.inner)?;
+        Ok(FirewallRule::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the rule."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the destination IP address or prefix to match."]
+        set_destination_ip_address, with_destination_ip_address -> destination_ip_address: optional ipnet::IpNet
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the destination port or port range like `80:90` to match."]
+        set_destination_port, with_destination_port -> destination_port: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the rule is enabled (the default)."]
+        set_enabled, with_enabled -> enabled: bool
+    }
+
+    /// Set IP protocol version of the rule.
+    ///
+    /// Defaults to the version of the source or destination IP address or IPv4.
+    pub fn set_ip_version(&mut self, value: protocol::IpVersion) {
+        self.ip_version = Some(value);
+    }
+
+    /// Set IP protocol version of the rule.
+    ///
+    /// Defaults to the version of the source or destination IP address or IPv4.
+    pub fn with_ip_version(mut self, value: protocol::IpVersion) -> Self {
+        self.set_ip_version(value);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the rule."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the rule (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the IP protocol name (e.g. `tcp`) to match."]
+        set_protocol, with_protocol -> protocol: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the rule is shared with all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the source IP address or prefix to match."]
+        set_source_ip_address, with_source_ip_address -> source_ip_address: optional ipnet::IpNet
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the source port or port range like `80:90` to match."]
+        set_source_port, with_source_port -> source_port: optional String
+    }
+}
+
+impl IntoFallibleIterator for FirewallRuleQuery {
+    type Item = FirewallRule;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<FirewallRuleQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<FirewallRule> for FirewallRuleRef {
+    fn from(value: FirewallRule) -> FirewallRuleRef {
+        FirewallRuleRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for FirewallRuleRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<FirewallRuleRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            FirewallRuleRef::new_verified(api::get_firewall_rule(session, &self.value)?.id)
+        })
+    }
+}
+
+fn ip_version_of(value: &ipnet::IpNet) -> protocol::IpVersion {
+    match value {
+        ipnet::IpNet::V4(..) => protocol::IpVersion::V4,
+        ipnet::IpNet::V6(..) => protocol::IpVersion::V6,
+    }
+}
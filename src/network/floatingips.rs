@@ -27,10 +27,9 @@ use super::super::common::{
     DeletionWaiter, IntoVerified, NetworkRef, PortRef, Refresh, ResourceIterator, ResourceQuery,
     RouterRef, SubnetRef,
 };
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::utils::{Query, Session};
 use super::super::{Error, ErrorKind, Result, Sort};
-use super::{api, protocol, Network, Port};
+use super::{api, protocol, Network, Port, Router};
 
 /// Structure representing a single floating IP.
 #[derive(Clone, Debug)]
@@ -48,6 +47,7 @@ pub struct FloatingIpQuery {
     can_paginate: bool,
     floating_network: Option<NetworkRef>,
     port: Option<PortRef>,
+    router: Option<RouterRef>,
 }
 
 /// A request to create a floating IP.
@@ -138,6 +138,9 @@ impl FloatingIp {
 
     transparent_property! {
         #[doc = "List of port forwardings (if any)."]
+        #[doc = ""]
+        #[doc = "The list embedded in the floating IP may lack IDs, use"]
+        #[doc = "`refresh_port_forwardings` to fetch the complete records."]
         port_forwardings: ref Vec<protocol::PortForwarding>
     }
 
@@ -146,6 +149,24 @@ impl FloatingIp {
         port_id: ref Option<String>
     }
 
+    transparent_property! {
+        #[doc = "Revision number of the floating IP (if available)."]
+        revision_number: Option<u32>
+    }
+
+    /// Fetch the router of this floating IP.
+    ///
+    /// Fails with `ResourceNotFound` if the floating IP is not associated.
+    pub fn router(&self) -> Result<Router> {
+        match self.inner.router_id {
+            Some(ref router_id) => Router::load(self.session.clone(), router_id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "Floating IP is not associated with a router",
+            )),
+        }
+    }
+
     transparent_property! {
         #[doc = "ID of the router of this floating IP."]
         router_id: ref Option<String>
@@ -199,6 +220,104 @@ impl FloatingIp {
         self.update_port(serde_json::Value::Null, None)
     }
 
+    /// Add a port forwarding to this floating IP.
+    ///
+    /// Traffic coming to `external_port` of this floating IP is forwarded
+    /// to `internal_port_number` on `internal_ip` of the `internal_port`.
+    ///
+    /// Requires the `floating-ip-port-forwarding` extension.
+    pub fn add_port_forwarding<P>(
+        &mut self,
+        internal_port: P,
+        internal_ip: net::IpAddr,
+        internal_port_number: u16,
+        external_port: u16,
+        protocol: protocol::PortForwardingProtocol,
+    ) -> Result<protocol::PortForwarding>
+    where
+        P: Into<PortRef>,
+    {
+        let request = protocol::PortForwarding {
+            description: None,
+            external_port,
+            id: String::new(),
+            internal_ip_address: internal_ip,
+            internal_port: internal_port_number,
+            internal_port_id: Some(internal_port.into().into_verified(&self.session)?.into()),
+            protocol: protocol.into(),
+        };
+        let result = api::create_port_forwarding(&self.session, &self.inner.id, request)?;
+        let created = result.clone();
+        self.sync_port_forwardings(move |forwardings| forwardings.push(created));
+        Ok(result)
+    }
+
+    /// Update a port forwarding of this floating IP.
+    ///
+    /// The port forwarding is identified by its `id`, all other fields are
+    /// updated from the provided value.
+    pub fn update_port_forwarding(
+        &mut self,
+        forwarding: protocol::PortForwarding,
+    ) -> Result<protocol::PortForwarding> {
+        if forwarding.id.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Port forwarding ID is required for updating",
+            ));
+        }
+
+        let update = protocol::PortForwardingUpdate {
+            description: forwarding.description,
+            external_port: Some(forwarding.external_port),
+            internal_ip_address: Some(forwarding.internal_ip_address),
+            internal_port: Some(forwarding.internal_port),
+            internal_port_id: forwarding.internal_port_id,
+            protocol: Some(forwarding.protocol),
+        };
+        let result =
+            api::update_port_forwarding(&self.session, &self.inner.id, &forwarding.id, update)?;
+        let updated = result.clone();
+        self.sync_port_forwardings(move |forwardings| {
+            for item in forwardings.iter_mut().filter(|item| item.id == updated.id) {
+                *item = updated.clone();
+            }
+        });
+        Ok(result)
+    }
+
+    /// Remove a port forwarding from this floating IP.
+    pub fn remove_port_forwarding<Id: AsRef<str>>(&mut self, id: Id) -> Result<()> {
+        api::delete_port_forwarding(&self.session, &self.inner.id, id.as_ref())?;
+        self.sync_port_forwardings(|forwardings| forwardings.retain(|item| item.id != id.as_ref()));
+        Ok(())
+    }
+
+    /// Refresh port forwardings of this floating IP.
+    ///
+    /// Unlike `refresh`, this call fetches complete port forwarding records,
+    /// including their IDs.
+    pub fn refresh_port_forwardings(&mut self) -> Result<()> {
+        self.inner.port_forwardings = api::list_port_forwardings(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+
+    /// Refresh port forwardings after a change, applying it locally on failure.
+    ///
+    /// The change itself has already succeeded, so a failure to refresh is not an error.
+    fn sync_port_forwardings<F>(&mut self, apply: F)
+    where
+        F: FnOnce(&mut Vec<protocol::PortForwarding>),
+    {
+        if let Err(err) = self.refresh_port_forwardings() {
+            debug!(
+                "Failed to refresh port forwardings of floating IP {}: {}",
+                self.inner.id, err
+            );
+            apply(&mut self.inner.port_forwardings);
+        }
+    }
+
     /// Delete the floating IP.
     pub fn delete(self) -> Result<DeletionWaiter<FloatingIp>> {
         api::delete_floating_ip(&self.session, &self.inner.id)?;
@@ -211,11 +330,15 @@ impl FloatingIp {
 
     /// Save the changes to the floating IP.
     pub fn save(&mut self) -> Result<()> {
+        self.save_revision(None)
+    }
+
+    fn save_revision(&mut self, revision: Option<u32>) -> Result<()> {
         let mut update = protocol::FloatingIpUpdate::default();
         save_option_fields! {
             self -> update: description fixed_ip_address
         };
-        self.inner = api::update_floating_ip(&self.session, self.id(), update)?;
+        self.inner = api::update_floating_ip(&self.session, self.id(), update, revision)?;
         self.dirty.clear();
         Ok(())
     }
@@ -230,7 +353,7 @@ impl FloatingIp {
             fixed_ip_address,
             port_id: Some(value),
         };
-        let mut inner = api::update_floating_ip(&self.session, self.id(), update)?;
+        let mut inner = api::update_floating_ip(&self.session, self.id(), update, None)?;
 
         // NOTE(dtantsur): description is independent of port.
         let desc_changed = self.dirty.contains("description");
@@ -245,6 +368,10 @@ impl FloatingIp {
     }
 }
 
+taggable_resource!(FloatingIp -> "floatingips");
+
+revisioned_resource!(FloatingIp -> "floating IP");
+
 impl Refresh for FloatingIp {
     /// Refresh the floating_ip.
     fn refresh(&mut self) -> Result<()> {
@@ -261,6 +388,7 @@ impl FloatingIpQuery {
             can_paginate: true,
             floating_network: None,
             port: None,
+            router: None,
         }
     }
 
@@ -327,20 +455,17 @@ impl FloatingIpQuery {
         self
     }
 
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
     /// Filter by router.
-    ///
-    /// # Warning
-    ///
-    /// Due to architectural limitations, names do not work here.
     pub fn set_router<N: Into<RouterRef>>(&mut self, value: N) {
-        self.query.push_str("router_id", value.into());
+        self.router = Some(value.into());
     }
 
-    /// Filter by network.
-    ///
-    /// # Warning
-    ///
-    /// Due to architectural limitations, names do not work here.
+    /// Filter by router.
     pub fn with_router<N: Into<RouterRef>>(mut self, value: N) -> Self {
         self.set_router(value);
         self
@@ -351,6 +476,8 @@ impl FloatingIpQuery {
         set_status, with_status -> status: protocol::FloatingIpStatus
     }
 
+    tag_query_filters!();
+
     /// Convert this query into an iterator executing the request.
     ///
     /// Returns a `FallibleIterator`, which is an iterator with each `next`
@@ -415,10 +542,16 @@ impl ResourceQuery for FloatingIpQuery {
             let verified = port.into_verified(&self.session)?;
             self.query.push_str("port_id", verified);
         }
+        if let Some(router) = self.router.take() {
+            let verified = router.into_verified(&self.session)?;
+            self.query.push_str("router_id", verified);
+        }
         Ok(())
     }
 }
 
+projectable_query!(FloatingIpQuery: ["floatingips"] -> "floatingips");
+
 impl NewFloatingIp {
     /// Start creating a floating IP.
     pub(crate) fn new(session: Rc<Session>, floating_network: NetworkRef) -> NewFloatingIp {
@@ -438,10 +571,12 @@ impl NewFloatingIp {
                 id: String::new(),
                 port_id: None,
                 port_forwardings: Vec::new(),
+                revision_number: None,
                 router_id: None,
                 // Dummy value, not used when serializing
                 status: protocol::FloatingIpStatus::Active,
                 subnet_id: None,
+                tags: Vec::new(),
                 updated_at: None,
             },
             floating_network,
@@ -470,12 +605,12 @@ impl NewFloatingIp {
     }
 
     creation_inner_field! {
-        #[doc = "Set DNS domain for the floating IP."]
+        #[doc = "Set DNS domain for the floating IP (requires the `dns-integration` extension)."]
         set_dns_domain, with_dns_domain -> dns_domain: optional String
     }
 
     creation_inner_field! {
-        #[doc = "Set DNS name for the floating IP."]
+        #[doc = "Set DNS name for the floating IP (requires the `dns-integration` extension)."]
         set_dns_name, with_dns_name -> dns_name: optional String
     }
 
@@ -535,3 +670,128 @@ impl IntoFallibleIterator for FloatingIpQuery {
         self.into_iter()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::common::PortRef;
+    use super::super::super::utils::test::MockRequest;
+    use super::super::api::test::mock_network_session;
+    use super::super::protocol::{PortForwarding, PortForwardingProtocol};
+    use super::FloatingIp;
+
+    const PATH: &str = "/v2.1/floatingips/fip-id/port_forwardings";
+
+    fn forwarding(external_port: u16) -> Value {
+        json!({
+            "external_port": external_port,
+            "id": "pf-id",
+            "internal_ip_address": "10.0.0.10",
+            "internal_port": 80,
+            "internal_port_id": "port-id",
+            "protocol": "tcp",
+        })
+    }
+
+    fn handler(request: &MockRequest, refresh: bool) -> (u16, Value) {
+        match request.method.as_str() {
+            "GET" if refresh => (200, json!({ "port_forwardings": [forwarding(8080)] })),
+            "GET" => (500, Value::Null),
+            "POST" => (201, json!({ "port_forwarding": forwarding(8080) })),
+            "PUT" => (200, json!({ "port_forwarding": forwarding(9090) })),
+            _ => (204, Value::Null),
+        }
+    }
+
+    fn floating_ip(refresh: bool) -> (FloatingIp, impl Fn() -> Vec<String>) {
+        let (session, requests) =
+            mock_network_session(&["floating-ip-port-forwarding"], move |request| {
+                handler(request, refresh)
+            });
+        let inner = serde_json::from_value(json!({
+            "floating_ip_address": "172.24.4.10",
+            "floating_network_id": "net-id",
+            "id": "fip-id",
+            "status": "ACTIVE",
+        }))
+        .unwrap();
+        let calls = move || {
+            requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.path.starts_with(PATH))
+                .map(|r| format!("{} {}", r.method, r.path))
+                .collect()
+        };
+        (FloatingIp::new(Rc::new(session), inner), calls)
+    }
+
+    #[test]
+    fn test_port_forwardings_refreshed() {
+        let (mut fip, calls) = floating_ip(true);
+        let result = fip
+            .add_port_forwarding(
+                PortRef::new_verified("port-id".to_string()),
+                "10.0.0.10".parse().unwrap(),
+                80,
+                8080,
+                PortForwardingProtocol::Tcp,
+            )
+            .unwrap();
+        assert_eq!(result.id, "pf-id");
+        assert_eq!(fip.port_forwardings().len(), 1);
+
+        fip.remove_port_forwarding("pf-id").unwrap();
+        // The mock server still reports the port forwarding.
+        assert_eq!(fip.port_forwardings().len(), 1);
+
+        assert_eq!(
+            calls(),
+            vec![
+                format!("POST {}", PATH),
+                format!("GET {}", PATH),
+                format!("DELETE {}/pf-id", PATH),
+                format!("GET {}", PATH),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_port_forwardings_refresh_failure() {
+        let (mut fip, _) = floating_ip(false);
+        let _ = fip
+            .add_port_forwarding(
+                PortRef::new_verified("port-id".to_string()),
+                "10.0.0.10".parse().unwrap(),
+                80,
+                8080,
+                PortForwardingProtocol::Tcp,
+            )
+            .unwrap();
+        assert_eq!(fip.port_forwardings().len(), 1);
+        assert_eq!(fip.port_forwardings()[0].external_port, 8080);
+
+        let mut forwarding = fip.port_forwardings()[0].clone();
+        forwarding.external_port = 9090;
+        let result = fip.update_port_forwarding(forwarding).unwrap();
+        assert_eq!(result.external_port, 9090);
+        assert_eq!(fip.port_forwardings().len(), 1);
+        assert_eq!(fip.port_forwardings()[0].external_port, 9090);
+
+        fip.remove_port_forwarding("pf-id").unwrap();
+        assert!(fip.port_forwardings().is_empty());
+    }
+
+    #[test]
+    fn test_update_port_forwarding_requires_id() {
+        let (mut fip, calls) = floating_ip(true);
+        let mut forwarding: PortForwarding = serde_json::from_value(forwarding(8080)).unwrap();
+        forwarding.id.clear();
+        assert!(fip.update_port_forwarding(forwarding).is_err());
+        assert!(calls().is_empty());
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IKE policies management via Network API (VPNaaS).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IkePolicyRef, IntoVerified, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol};
+
+/// A query to IKE policy list.
+#[derive(Clone, Debug)]
+pub struct IkePolicyQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single IKE policy.
+#[derive(Clone, Debug)]
+pub struct IkePolicy {
+    session: Rc<Session>,
+    inner: protocol::IkePolicy,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create an IKE policy.
+#[derive(Clone, Debug)]
+pub struct NewIkePolicy {
+    session: Rc<Session>,
+    inner: protocol::IkePolicy,
+}
+
+impl IkePolicy {
+    /// Create an IKE policy object.
+    fn new(session: Rc<Session>, inner: protocol::IkePolicy) -> IkePolicy {
+        IkePolicy {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an IkePolicy object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<IkePolicy> {
+        let inner = api::get_ike_policy(&session, id)?;
+        Ok(IkePolicy::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Authentication hash algorithm, e.g. `sha1` or `sha256`."]
+        auth_algorithm: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the authentication hash algorithm."]
+        set_auth_algorithm, with_auth_algorithm -> auth_algorithm
+    }
+
+    transparent_property! {
+        #[doc = "Policy description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Encryption algorithm, e.g. `aes-128` or `aes-256`."]
+        encryption_algorithm: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the encryption algorithm."]
+        set_encryption_algorithm, with_encryption_algorithm -> encryption_algorithm
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Version of the IKE protocol."]
+        ike_version: protocol::IkeVersion
+    }
+
+    update_field! {
+        #[doc = "Update the version of the IKE protocol."]
+        set_ike_version, with_ike_version -> ike_version: protocol::IkeVersion
+    }
+
+    transparent_property! {
+        #[doc = "Lifetime of the security association."]
+        lifetime: ref protocol::VpnLifetime
+    }
+
+    update_field! {
+        #[doc = "Update the lifetime of the security association."]
+        set_lifetime, with_lifetime -> lifetime: protocol::VpnLifetime
+    }
+
+    transparent_property! {
+        #[doc = "Policy name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Perfect forward secrecy mode, e.g. `group5` or `group14`."]
+        pfs: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the perfect forward secrecy mode."]
+        set_pfs, with_pfs -> pfs
+    }
+
+    transparent_property! {
+        #[doc = "IKE phase 1 negotiation mode, e.g. `main` or `aggressive`."]
+        phase1_negotiation_mode: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the IKE phase 1 negotiation mode."]
+        set_phase1_negotiation_mode, with_phase1_negotiation_mode -> phase1_negotiation_mode
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the policy (if available)."]
+        project_id: ref Option<String>
+    }
+
+    /// Delete the IKE policy.
+    pub fn delete(self) -> Result<DeletionWaiter<IkePolicy>> {
+        api::delete_ike_policy(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the IKE policy is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the IKE policy.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::IkePolicyUpdate::default();
+        save_fields! {
+            self -> update: auth_algorithm encryption_algorithm ike_version lifetime pfs
+                phase1_negotiation_mode
+        };
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_ike_policy(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for IkePolicy {
+    /// Refresh the IKE policy.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_ike_policy_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl IkePolicyQuery {
+    pub(crate) fn new(session: Rc<Session>) -> IkePolicyQuery {
+        IkePolicyQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::IkePolicySortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by IKE version."]
+        set_ike_version, with_ike_version -> ike_version: protocol::IkeVersion
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<IkePolicyQuery> {
+        debug!("Fetching IKE policies with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<IkePolicy>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<IkePolicy> {
+        debug!("Fetching one IKE policy with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for IkePolicyQuery {
+    type Item = IkePolicy;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_ike_policies(&self.session, &query)?
+            .into_iter()
+            .map(|item| IkePolicy::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    IkePolicyQuery: ["vpn", "ikepolicies"] -> "ikepolicies",
+    Vpnaas: "VPN-as-a-Service"
+);
+
+impl NewIkePolicy {
+    /// Start creating an IKE policy.
+    ///
+    /// The defaults match the ones of the Network API.
+    pub(crate) fn new(session: Rc<Session>) -> NewIkePolicy {
+        NewIkePolicy {
+            session,
+            inner: protocol::IkePolicy {
+                auth_algorithm: String::from("sha1"),
+                description: None,
+                encryption_algorithm: String::from("aes-128"),
+                id: String::new(),
+                ike_version: protocol::IkeVersion::V1,
+                lifetime: protocol::VpnLifetime::seconds(3600),
+                name: None,
+                pfs: String::from("group5"),
+                phase1_negotiation_mode: String::from("main"),
+                project_id: None,
+            },
+        }
+    }
+
+    /// Request creation of the IKE policy.
+    pub fn create(self) -> Result<IkePolicy> {
+        let inner = api::create_ike_policy(&self.session, self.inner)?;
+        Ok(IkePolicy::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the authentication hash algorithm (defaults to `sha1`)."]
+        set_auth_algorithm, with_auth_algorithm -> auth_algorithm
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the policy."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the encryption algorithm (defaults to `aes-128`)."]
+        set_encryption_algorithm, with_encryption_algorithm -> encryption_algorithm
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the version of the IKE protocol (defaults to v1)."]
+        set_ike_version, with_ike_version -> ike_version: protocol::IkeVersion
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the lifetime of the security association (defaults to 3600 seconds)."]
+        set_lifetime, with_lifetime -> lifetime: protocol::VpnLifetime
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the policy."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the perfect forward secrecy mode (defaults to `group5`)."]
+        set_pfs, with_pfs -> pfs
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the IKE phase 1 negotiation mode (defaults to `main`)."]
+        set_phase1_negotiation_mode, with_phase1_negotiation_mode -> phase1_negotiation_mode
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the policy (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+}
+
+impl IntoFallibleIterator for IkePolicyQuery {
+    type Item = IkePolicy;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<IkePolicyQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<IkePolicy> for IkePolicyRef {
+    fn from(value: IkePolicy) -> IkePolicyRef {
+        IkePolicyRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for IkePolicyRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<IkePolicyRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            IkePolicyRef::new_verified(api::get_ike_policy(session, &self.value)?.id)
+        })
+    }
+}
@@ -0,0 +1,694 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! IPsec site connections management via Network API (VPNaaS).
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use ipnet;
+use waiter::{Waiter, WaiterCurrentState};
+
+use super::super::common::{
+    DeletionWaiter, EndpointGroupRef, IkePolicyRef, IntoVerified, IpsecPolicyRef, Refresh,
+    ResourceIterator, ResourceQuery, VpnServiceRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, EndpointGroup, IkePolicy, IpsecPolicy, VpnService};
+
+/// A query to IPsec site connection list.
+#[derive(Clone, Debug)]
+pub struct IpsecSiteConnectionQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    vpn_service: Option<VpnServiceRef>,
+}
+
+/// Structure representing a single IPsec site connection.
+#[derive(Clone, Debug)]
+pub struct IpsecSiteConnection {
+    session: Rc<Session>,
+    inner: protocol::IpsecSiteConnection,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create an IPsec site connection.
+#[derive(Clone, Debug)]
+pub struct NewIpsecSiteConnection {
+    session: Rc<Session>,
+    inner: protocol::IpsecSiteConnection,
+    vpn_service: VpnServiceRef,
+    ike_policy: IkePolicyRef,
+    ipsec_policy: IpsecPolicyRef,
+    local_endpoint_group: Option<EndpointGroupRef>,
+    peer_endpoint_group: Option<EndpointGroupRef>,
+}
+
+/// Waiter for IPsec site connection status to change.
+#[derive(Debug)]
+pub struct IpsecSiteConnectionStatusWaiter<'connection> {
+    connection: &'connection mut IpsecSiteConnection,
+    target: protocol::VpnStatus,
+}
+
+impl IpsecSiteConnection {
+    /// Create an IPsec site connection object.
+    fn new(session: Rc<Session>, inner: protocol::IpsecSiteConnection) -> IpsecSiteConnection {
+        IpsecSiteConnection {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an IpsecSiteConnection object.
+    pub(crate) fn load<Id: AsRef<str>>(
+        session: Rc<Session>,
+        id: Id,
+    ) -> Result<IpsecSiteConnection> {
+        let inner = api::get_ipsec_site_connection(&session, id)?;
+        Ok(IpsecSiteConnection::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "The administrative state of the connection."]
+        admin_state_up: bool
+    }
+
+    update_field! {
+        #[doc = "Set the administrative state of the connection."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    transparent_property! {
+        #[doc = "Connection description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Dead peer detection settings (if available)."]
+        dpd: ref Option<protocol::DeadPeerDetection>
+    }
+
+    update_field! {
+        #[doc = "Update the dead peer detection settings."]
+        set_dpd, with_dpd -> dpd: optional protocol::DeadPeerDetection
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    /// Fetch the IKE policy of this connection.
+    pub fn ike_policy(&self) -> Result<IkePolicy> {
+        IkePolicy::load(self.session.clone(), &self.inner.ikepolicy_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the IKE policy of this connection."]
+        ikepolicy_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Whether the connection initiates the negotiation."]
+        initiator: protocol::IpsecInitiator
+    }
+
+    update_field! {
+        #[doc = "Update whether the connection initiates the negotiation."]
+        set_initiator, with_initiator -> initiator: protocol::IpsecInitiator
+    }
+
+    /// Fetch the IPsec policy of this connection.
+    pub fn ipsec_policy(&self) -> Result<IpsecPolicy> {
+        IpsecPolicy::load(self.session.clone(), &self.inner.ipsecpolicy_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the IPsec policy of this connection."]
+        ipsecpolicy_id: ref String
+    }
+
+    /// Fetch the local endpoint group of this connection.
+    ///
+    /// Fails with `ResourceNotFound` if the connection uses peer CIDRs.
+    pub fn local_endpoint_group(&self) -> Result<EndpointGroup> {
+        match self.inner.local_ep_group_id {
+            Some(ref id) => EndpointGroup::load(self.session.clone(), id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "IPsec site connection has no local endpoint group",
+            )),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "ID of the local endpoint group (if any)."]
+        local_ep_group_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Local ID of the connection (if set)."]
+        local_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Maximum transmission unit of the connection."]
+        mtu: u32
+    }
+
+    update_field! {
+        #[doc = "Update the maximum transmission unit."]
+        set_mtu, with_mtu -> mtu: u32
+    }
+
+    transparent_property! {
+        #[doc = "Connection name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Address of the peer gateway."]
+        peer_address: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the address of the peer gateway."]
+        set_peer_address, with_peer_address -> peer_address
+    }
+
+    transparent_property! {
+        #[doc = "Peer CIDRs (empty when endpoint groups are used)."]
+        peer_cidrs: ref Vec<ipnet::IpNet>
+    }
+
+    /// Fetch the peer endpoint group of this connection.
+    ///
+    /// Fails with `ResourceNotFound` if the connection uses peer CIDRs.
+    pub fn peer_endpoint_group(&self) -> Result<EndpointGroup> {
+        match self.inner.peer_ep_group_id {
+            Some(ref id) => EndpointGroup::load(self.session.clone(), id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "IPsec site connection has no peer endpoint group",
+            )),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "ID of the peer endpoint group (if any)."]
+        peer_ep_group_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Peer router identity used for authentication."]
+        peer_id: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the peer router identity."]
+        set_peer_id, with_peer_id -> peer_id
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the connection (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Pre-shared key of the connection."]
+        psk: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the pre-shared key."]
+        set_psk, with_psk -> psk
+    }
+
+    transparent_property! {
+        #[doc = "Connection status."]
+        status: protocol::VpnStatus
+    }
+
+    /// Fetch the VPN service of this connection.
+    pub fn vpn_service(&self) -> Result<VpnService> {
+        VpnService::load(self.session.clone(), &self.inner.vpnservice_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the VPN service of this connection."]
+        vpnservice_id: ref String
+    }
+
+    /// Wait for the connection to become active.
+    ///
+    /// The connection stays down until the peer side is configured.
+    pub fn wait_for_active<'connection>(
+        &'connection mut self,
+    ) -> IpsecSiteConnectionStatusWaiter<'connection> {
+        IpsecSiteConnectionStatusWaiter {
+            connection: self,
+            target: protocol::VpnStatus::Active,
+        }
+    }
+
+    /// Delete the IPsec site connection.
+    pub fn delete(self) -> Result<DeletionWaiter<IpsecSiteConnection>> {
+        api::delete_ipsec_site_connection(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the IPsec site connection is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the IPsec site connection.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::IpsecSiteConnectionUpdate::default();
+        save_fields! {
+            self -> update: admin_state_up initiator mtu peer_address peer_id psk
+        };
+        save_option_fields! {
+            self -> update: description dpd name
+        };
+        let inner = api::update_ipsec_site_connection(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for IpsecSiteConnection {
+    /// Refresh the IPsec site connection.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_ipsec_site_connection_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl<'connection> Waiter<(), Error> for IpsecSiteConnectionStatusWaiter<'connection> {
+    fn default_wait_timeout(&self) -> Option<Duration> {
+        Some(Duration::new(600, 0))
+    }
+
+    fn default_delay(&self) -> Duration {
+        Duration::new(1, 0)
+    }
+
+    fn timeout_error(&self) -> Error {
+        Error::new(
+            ErrorKind::OperationTimedOut,
+            format!(
+                "Timeout waiting for IPsec site connection {} to reach state {}",
+                self.connection.id(),
+                self.target
+            ),
+        )
+    }
+
+    fn poll(&mut self) -> Result<Option<()>> {
+        self.connection.refresh()?;
+        if self.connection.status() == self.target {
+            debug!(
+                "IPsec site connection {} reached state {}",
+                self.connection.id(),
+                self.target
+            );
+            Ok(Some(()))
+        } else if self.connection.status() == protocol::VpnStatus::Error {
+            debug!(
+                "Failed to move IPsec site connection {} to {} - status is ERROR",
+                self.connection.id(),
+                self.target
+            );
+            Err(Error::new(
+                ErrorKind::OperationFailed,
+                format!(
+                    "IPsec site connection {} got into ERROR state",
+                    self.connection.id()
+                ),
+            ))
+        } else {
+            trace!(
+                "Still waiting for IPsec site connection {} to get to state {}, current is {}",
+                self.connection.id(),
+                self.target,
+                self.connection.status()
+            );
+            Ok(None)
+        }
+    }
+}
+
+impl<'connection> WaiterCurrentState<IpsecSiteConnection>
+    for IpsecSiteConnectionStatusWaiter<'connection>
+{
+    fn waiter_current_state(&self) -> &IpsecSiteConnection {
+        self.connection
+    }
+}
+
+impl IpsecSiteConnectionQuery {
+    pub(crate) fn new(session: Rc<Session>) -> IpsecSiteConnectionQuery {
+        IpsecSiteConnectionQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            vpn_service: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::IpsecSiteConnectionSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by the administrative state."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by peer address."]
+        set_peer_address, with_peer_address -> peer_address
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by status."]
+        set_status, with_status -> status: protocol::VpnStatus
+    }
+
+    /// Filter by VPN service.
+    pub fn set_vpn_service<S: Into<VpnServiceRef>>(&mut self, value: S) {
+        self.vpn_service = Some(value.into());
+    }
+
+    /// Filter by VPN service.
+    pub fn with_vpn_service<S: Into<VpnServiceRef>>(mut self, value: S) -> Self {
+        self.set_vpn_service(value);
+        self
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<IpsecSiteConnectionQuery> {
+        debug!("Fetching IPsec site connections with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<IpsecSiteConnection>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<IpsecSiteConnection> {
+        debug!("Fetching one IPsec site connection with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for IpsecSiteConnectionQuery {
+    type Item = IpsecSiteConnection;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_ipsec_site_connections(&self.session, &query)?
+            .into_iter()
+            .map(|item| IpsecSiteConnection::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(vpn_service) = self.vpn_service.take() {
+            let verified = vpn_service.into_verified(&self.session)?;
+            self.query.push_str("vpnservice_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(
+    IpsecSiteConnectionQuery: ["vpn", "ipsec-site-connections"] -> "ipsec_site_connections",
+    Vpnaas: "VPN-as-a-Service"
+);
+
+impl NewIpsecSiteConnection {
+    /// Start creating an IPsec site connection.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        vpn_service: VpnServiceRef,
+        ike_policy: IkePolicyRef,
+        ipsec_policy: IpsecPolicyRef,
+        peer_address: String,
+        psk: String,
+    ) -> NewIpsecSiteConnection {
+        NewIpsecSiteConnection {
+            session,
+            inner: protocol::IpsecSiteConnection {
+                admin_state_up: true,
+                description: None,
+                dpd: None,
+                id: String::new(),
+                // Will be replaced in create()
+                ikepolicy_id: String::new(),
+                initiator: protocol::IpsecInitiator::BiDirectional,
+                // Will be replaced in create()
+                ipsecpolicy_id: String::new(),
+                local_ep_group_id: None,
+                local_id: None,
+                mtu: 1500,
+                name: None,
+                peer_id: peer_address.clone(),
+                peer_address,
+                peer_cidrs: Vec::new(),
+                peer_ep_group_id: None,
+                project_id: None,
+                psk,
+                // Dummy value, not used when serializing
+                status: protocol::VpnStatus::PendingCreate,
+                // Will be replaced in create()
+                vpnservice_id: String::new(),
+            },
+            vpn_service,
+            ike_policy,
+            ipsec_policy,
+            local_endpoint_group: None,
+            peer_endpoint_group: None,
+        }
+    }
+
+    /// Request creation of the IPsec site connection.
+    ///
+    /// Fails with `InvalidInput` unless either peer CIDRs or both local and
+    /// peer endpoint groups are provided.
+    pub fn create(mut self) -> Result<IpsecSiteConnection> {
+        let has_endpoint_groups =
+            self.local_endpoint_group.is_some() || self.peer_endpoint_group.is_some();
+        if !self.inner.peer_cidrs.is_empty() && has_endpoint_groups {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Peer CIDRs cannot be combined with endpoint groups",
+            ));
+        }
+        if self.inner.peer_cidrs.is_empty()
+            && (self.local_endpoint_group.is_none() || self.peer_endpoint_group.is_none())
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Either peer CIDRs or both local and peer endpoint groups are required",
+            ));
+        }
+
+        self.inner.vpnservice_id = self.vpn_service.into_verified(&self.session)?.into();
+        self.inner.ikepolicy_id = self.ike_policy.into_verified(&self.session)?.into();
+        self.inner.ipsecpolicy_id = self.ipsec_policy.into_verified(&self.session)?.into();
+        if let Some(group) = self.local_endpoint_group {
+            self.inner.local_ep_group_id = Some(group.into_verified(&self.session)?.into());
+        }
+        if let Some(group) = self.peer_endpoint_group {
+            self.inner.peer_ep_group_id = Some(group.into_verified(&self.session)?.into());
+        }
+
+        let inner = api::create_ipsec_site_connection(&self.session, self.inner)?;
+        Ok(IpsecSiteConnection::new(self.session, inner))
+    }
+
+    /// Add a peer CIDR (cannot be combined with endpoint groups).
+    pub fn add_peer_cidr(&mut self, cidr: ipnet::IpNet) {
+        self.inner.peer_cidrs.push(cidr);
+    }
+
+    /// Add a peer CIDR (cannot be combined with endpoint groups).
+    pub fn with_peer_cidr(mut self, cidr: ipnet::IpNet) -> Self {
+        self.add_peer_cidr(cidr);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set administrative status for the connection."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the connection."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the dead peer detection settings."]
+        set_dpd, with_dpd -> dpd: optional protocol::DeadPeerDetection
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the connection initiates the negotiation."]
+        set_initiator, with_initiator -> initiator: protocol::IpsecInitiator
+    }
+
+    /// Set the local endpoint group (must contain subnets).
+    pub fn set_local_endpoint_group<G: Into<EndpointGroupRef>>(&mut self, value: G) {
+        self.local_endpoint_group = Some(value.into());
+    }
+
+    /// Set the local endpoint group (must contain subnets).
+    pub fn with_local_endpoint_group<G: Into<EndpointGroupRef>>(mut self, value: G) -> Self {
+        self.set_local_endpoint_group(value);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the local ID of the connection."]
+        set_local_id, with_local_id -> local_id: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the maximum transmission unit (defaults to 1500)."]
+        set_mtu, with_mtu -> mtu: u32
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the connection."]
+        set_name, with_name -> name: optional String
+    }
+
+    /// Set the peer endpoint group (must contain CIDRs).
+    pub fn set_peer_endpoint_group<G: Into<EndpointGroupRef>>(&mut self, value: G) {
+        self.peer_endpoint_group = Some(value.into());
+    }
+
+    /// Set the peer endpoint group (must contain CIDRs).
+    pub fn with_peer_endpoint_group<G: Into<EndpointGroupRef>>(mut self, value: G) -> Self {
+        self.set_peer_endpoint_group(value);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the peer router identity (defaults to the peer address)."]
+        set_peer_id, with_peer_id -> peer_id
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the connection (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+}
+
+impl IntoFallibleIterator for IpsecSiteConnectionQuery {
+    type Item = IpsecSiteConnection;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<IpsecSiteConnectionQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
@@ -0,0 +1,338 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metering label rules management via Network API.
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use ipnet;
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, MeteringLabelRef, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, MeteringLabel};
+
+/// A query to metering label rule list.
+#[derive(Clone, Debug)]
+pub struct MeteringLabelRuleQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    metering_label: Option<MeteringLabelRef>,
+}
+
+/// Structure representing a single metering label rule.
+///
+/// Metering label rules cannot be updated, only created and deleted.
+#[derive(Clone, Debug)]
+pub struct MeteringLabelRule {
+    session: Rc<Session>,
+    inner: protocol::MeteringLabelRule,
+}
+
+/// A request to create a metering label rule.
+#[derive(Clone, Debug)]
+pub struct NewMeteringLabelRule {
+    session: Rc<Session>,
+    inner: protocol::MeteringLabelRule,
+    metering_label: MeteringLabelRef,
+}
+
+impl MeteringLabelRule {
+    /// Create a metering label rule object.
+    fn new(session: Rc<Session>, inner: protocol::MeteringLabelRule) -> MeteringLabelRule {
+        MeteringLabelRule { session, inner }
+    }
+
+    /// Load a MeteringLabelRule object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<MeteringLabelRule> {
+        let inner = api::get_metering_label_rule(&session, id)?;
+        Ok(MeteringLabelRule::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Destination IP prefix (if any)."]
+        destination_ip_prefix: Option<ipnet::IpNet>
+    }
+
+    transparent_property! {
+        #[doc = "Direction of the traffic the rule applies to."]
+        direction: protocol::MeteringLabelRuleDirection
+    }
+
+    transparent_property! {
+        #[doc = "Whether the matching traffic is excluded from counting."]
+        excluded: bool
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    /// Get the metering label this rule belongs to.
+    pub fn metering_label(&self) -> Result<MeteringLabel> {
+        MeteringLabel::load(self.session.clone(), &self.inner.metering_label_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the metering label this rule belongs to."]
+        metering_label_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Remote IP prefix (if any)."]
+        remote_ip_prefix: Option<ipnet::IpNet>
+    }
+
+    transparent_property! {
+        #[doc = "Source IP prefix (if any)."]
+        source_ip_prefix: Option<ipnet::IpNet>
+    }
+
+    /// Delete the metering label rule.
+    pub fn delete(self) -> Result<DeletionWaiter<MeteringLabelRule>> {
+        api::delete_metering_label_rule(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+}
+
+impl Refresh for MeteringLabelRule {
+    /// Refresh the metering label rule.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_metering_label_rule(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+}
+
+impl MeteringLabelRuleQuery {
+    pub(crate) fn new(session: Rc<Session>) -> MeteringLabelRuleQuery {
+        MeteringLabelRuleQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            metering_label: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::MeteringLabelRuleSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by direction."]
+        set_direction, with_direction -> direction: protocol::MeteringLabelRuleDirection
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the matching traffic is excluded."]
+        set_excluded, with_excluded -> excluded: bool
+    }
+
+    /// Filter by metering label.
+    pub fn set_metering_label<L: Into<MeteringLabelRef>>(&mut self, value: L) {
+        self.metering_label = Some(value.into());
+    }
+
+    /// Filter by metering label.
+    pub fn with_metering_label<L: Into<MeteringLabelRef>>(mut self, value: L) -> Self {
+        self.set_metering_label(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by remote IP prefix."]
+        set_remote_ip_prefix, with_remote_ip_prefix -> remote_ip_prefix: ipnet::IpNet
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<MeteringLabelRuleQuery> {
+        debug!("Fetching metering label rules with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<MeteringLabelRule>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<MeteringLabelRule> {
+        debug!("Fetching one metering label rule with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for MeteringLabelRuleQuery {
+    type Item = MeteringLabelRule;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_metering_label_rules(&self.session, &query)?
+            .into_iter()
+            .map(|item| MeteringLabelRule::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(metering_label) = self.metering_label.take() {
+            let verified = metering_label.into_verified(&self.session)?;
+            self.query.push_str("metering_label_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(
+    MeteringLabelRuleQuery: ["metering", "metering-label-rules"] -> "metering_label_rules",
+    Metering: "Metering"
+);
+
+impl NewMeteringLabelRule {
+    /// Start creating a metering label rule.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        metering_label: MeteringLabelRef,
+        direction: protocol::MeteringLabelRuleDirection,
+    ) -> NewMeteringLabelRule {
+        NewMeteringLabelRule {
+            session,
+            inner: protocol::MeteringLabelRule {
+                destination_ip_prefix: None,
+                direction,
+                excluded: false,
+                id: String::new(),
+                // Will be replaced in create()
+                metering_label_id: String::new(),
+                remote_ip_prefix: None,
+                source_ip_prefix: None,
+            },
+            metering_label,
+        }
+    }
+
+    /// Request creation of the metering label rule.
+    ///
+    /// Fails with `InvalidInput` if neither of remote, source or destination
+    /// IP prefixes is set, or if the remote IP prefix is combined with
+    /// the source or destination one.
+    pub fn create(mut self) -> Result<MeteringLabelRule> {
+        let has_new_prefixes =
+            self.inner.source_ip_prefix.is_some() || self.inner.destination_ip_prefix.is_some();
+        if self.inner.remote_ip_prefix.is_some() && has_new_prefixes {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Remote IP prefix cannot be combined with source or destination IP prefix",
+            ));
+        }
+        if self.inner.remote_ip_prefix.is_none() && !has_new_prefixes {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Remote, source or destination IP prefix is required",
+            ));
+        }
+
+        self.inner.metering_label_id = self.metering_label.into_verified(&self.session)?.into();
+        let inner = api::create_metering_label_rule(&self.session, self.inner)?;
+        Ok(MeteringLabelRule::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the destination IP prefix to match."]
+        set_destination_ip_prefix, with_destination_ip_prefix -> destination_ip_prefix: optional ipnet::IpNet
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the matching traffic is excluded from counting."]
+        set_excluded, with_excluded -> excluded: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the remote IP prefix to match (deprecated in favor of source and destination)."]
+        set_remote_ip_prefix, with_remote_ip_prefix -> remote_ip_prefix: optional ipnet::IpNet
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the source IP prefix to match."]
+        set_source_ip_prefix, with_source_ip_prefix -> source_ip_prefix: optional ipnet::IpNet
+    }
+}
+
+impl IntoFallibleIterator for MeteringLabelRuleQuery {
+    type Item = MeteringLabelRule;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<MeteringLabelRuleQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
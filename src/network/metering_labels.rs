@@ -0,0 +1,298 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metering labels management via Network API.
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, MeteringLabelRef, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, MeteringLabelRuleQuery};
+
+/// A query to metering label list.
+#[derive(Clone, Debug)]
+pub struct MeteringLabelQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single metering label.
+///
+/// Metering labels cannot be updated, only created and deleted.
+#[derive(Clone, Debug)]
+pub struct MeteringLabel {
+    session: Rc<Session>,
+    inner: protocol::MeteringLabel,
+}
+
+/// A request to create a metering label.
+#[derive(Clone, Debug)]
+pub struct NewMeteringLabel {
+    session: Rc<Session>,
+    inner: protocol::MeteringLabel,
+}
+
+impl MeteringLabel {
+    /// Create a metering label object.
+    fn new(session: Rc<Session>, inner: protocol::MeteringLabel) -> MeteringLabel {
+        MeteringLabel { session, inner }
+    }
+
+    /// Load a MeteringLabel object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<MeteringLabel> {
+        let inner = api::get_metering_label(&session, id)?;
+        Ok(MeteringLabel::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Metering label description."]
+        description: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Metering label name."]
+        name: ref String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the metering label (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the metering label applies to routers of all projects."]
+        shared: bool
+    }
+
+    /// Rules of this metering label.
+    pub fn rules(&self) -> MeteringLabelRuleQuery {
+        MeteringLabelRuleQuery::new(self.session.clone())
+            .with_metering_label(MeteringLabelRef::new_verified(self.inner.id.clone()))
+    }
+
+    /// Delete the metering label.
+    ///
+    /// The rules of the metering label are deleted with it.
+    pub fn delete(self) -> Result<DeletionWaiter<MeteringLabel>> {
+        api::delete_metering_label(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+}
+
+impl Refresh for MeteringLabel {
+    /// Refresh the metering label.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_metering_label_by_id(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+}
+
+impl MeteringLabelQuery {
+    pub(crate) fn new(session: Rc<Session>) -> MeteringLabelQuery {
+        MeteringLabelQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::MeteringLabelSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by description."]
+        set_description, with_description -> description
+    }
+
+    query_filter! {
+        #[doc = "Filter by name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the label is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<MeteringLabelQuery> {
+        debug!("Fetching metering labels with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<MeteringLabel>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<MeteringLabel> {
+        debug!("Fetching one metering label with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for MeteringLabelQuery {
+    type Item = MeteringLabel;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_metering_labels(&self.session, &query)?
+            .into_iter()
+            .map(|item| MeteringLabel::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(
+    MeteringLabelQuery: ["metering", "metering-labels"] -> "metering_labels",
+    Metering: "Metering"
+);
+
+impl NewMeteringLabel {
+    /// Start creating a metering label.
+    pub(crate) fn new(session: Rc<Session>, name: String) -> NewMeteringLabel {
+        NewMeteringLabel {
+            session,
+            inner: protocol::MeteringLabel {
+                description: None,
+                id: String::new(),
+                name,
+                project_id: None,
+                shared: false,
+            },
+        }
+    }
+
+    /// Request creation of the metering label.
+    pub fn create(self) -> Result<MeteringLabel> {
+        let inner = api::create_metering_label(&self.session, self.inner)?;
+        Ok(MeteringLabel::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the metering label."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the metering label (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set whether the metering label applies to routers of all projects."]
+        set_shared, with_shared -> shared: bool
+    }
+}
+
+impl IntoFallibleIterator for MeteringLabelQuery {
+    type Item = MeteringLabel;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<MeteringLabelQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<MeteringLabel> for MeteringLabelRef {
+    fn from(value: MeteringLabel) -> MeteringLabelRef {
+        MeteringLabelRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for MeteringLabelRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<MeteringLabelRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            MeteringLabelRef::new_verified(api::get_metering_label(session, &self.value)?.id)
+        })
+    }
+}
@@ -14,18 +14,89 @@
 
 //! Network API implementation bits.
 
+mod address_scopes;
+mod agents;
 mod api;
+mod auto_allocation;
+mod endpoint_groups;
+mod firewall_groups;
+mod firewall_policies;
+mod firewall_rules;
 mod floatingips;
+mod ike_policies;
+mod ipsec_policies;
+mod ipsec_site_connections;
+mod metering_label_rules;
+mod metering_labels;
 mod networks;
 mod ports;
 mod protocol;
+mod qos_policies;
+mod rbac_policies;
+mod routers;
+mod security_group_rules;
+mod security_groups;
+mod segments;
+mod subnet_pools;
 mod subnets;
+mod topology;
+mod trunks;
+mod vpn_services;
 
+pub use self::address_scopes::{AddressScope, AddressScopeQuery, NewAddressScope};
+pub use self::agents::{Agent, AgentQuery};
+pub(crate) use self::api::list_extensions as network_extensions;
+pub(crate) use self::auto_allocation::{
+    auto_allocated_topology, delete_auto_allocated_topology, validate_auto_allocated_topology,
+};
+pub use self::endpoint_groups::{EndpointGroup, EndpointGroupQuery, NewEndpointGroup};
+pub use self::firewall_groups::{FirewallGroup, FirewallGroupQuery, NewFirewallGroup};
+pub use self::firewall_policies::{FirewallPolicy, FirewallPolicyQuery, NewFirewallPolicy};
+pub use self::firewall_rules::{FirewallRule, FirewallRuleQuery, NewFirewallRule};
 pub use self::floatingips::{FloatingIp, FloatingIpQuery, NewFloatingIp};
+pub use self::ike_policies::{IkePolicy, IkePolicyQuery, NewIkePolicy};
+pub use self::ipsec_policies::{IpsecPolicy, IpsecPolicyQuery, NewIpsecPolicy};
+pub use self::ipsec_site_connections::{
+    IpsecSiteConnection, IpsecSiteConnectionQuery, IpsecSiteConnectionStatusWaiter,
+    NewIpsecSiteConnection,
+};
+pub use self::metering_label_rules::{
+    MeteringLabelRule, MeteringLabelRuleQuery, NewMeteringLabelRule,
+};
+pub use self::metering_labels::{MeteringLabel, MeteringLabelQuery, NewMeteringLabel};
+pub(crate) use self::networks::create_networks;
 pub use self::networks::{Network, NetworkQuery, NewNetwork};
+pub(crate) use self::ports::create_ports;
 pub use self::ports::{NewPort, Port, PortIpAddress, PortIpRequest, PortQuery};
 pub use self::protocol::{
-    AllocationPool, FloatingIpSortKey, FloatingIpStatus, HostRoute, IpVersion, Ipv6Mode,
-    NetworkSortKey, NetworkStatus, PortExtraDhcpOption, PortForwarding, PortSortKey, SubnetSortKey,
+    AddressScopeSortKey, AgentSortKey, AllocationPool, DeadPeerDetection, DeadPeerDetectionAction,
+    EndpointGroupSortKey, EndpointGroupType, EtherType, ExternalGateway, FirewallGroupSortKey,
+    FirewallGroupStatus, FirewallPolicySortKey, FirewallRuleAction, FirewallRuleSortKey, FixedIp,
+    FloatingIpSortKey, FloatingIpStatus, HostRoute, IkePolicySortKey, IkeVersion, IpVersion,
+    IpsecEncapsulationMode, IpsecInitiator, IpsecPolicySortKey, IpsecSiteConnectionSortKey,
+    IpsecTransformProtocol, Ipv6Mode, MeteringLabelRuleDirection, MeteringLabelRuleSortKey,
+    MeteringLabelSortKey, NetworkExtension, NetworkIpAvailability, NetworkSegment, NetworkSortKey,
+    NetworkStatus, NetworkType, PortAllowedAddressPair, PortExtraDhcpOption, PortForwarding,
+    PortForwardingProtocol, PortSortKey, QosBandwidthLimitRule, QosDscpMarkingRule,
+    QosMinimumBandwidthRule, QosPolicySortKey, QosRule, QosRuleDirection, RbacPolicyAction,
+    RbacPolicyObjectType, RbacPolicySortKey, RouterSortKey, RouterStatus,
+    SecurityGroupRuleDirection, SecurityGroupSortKey, SegmentSortKey, SegmentationType,
+    SubnetIpAvailability, SubnetPoolSortKey, SubnetSortKey, TrunkSortKey, TrunkStatus,
+    TrunkSubPort, VnicType, VpnLifetime, VpnServiceSortKey, VpnStatus,
+};
+pub use self::qos_policies::{NewQosPolicy, QosPolicy, QosPolicyQuery};
+pub use self::rbac_policies::{NewRbacPolicy, RbacPolicy, RbacPolicyQuery};
+pub use self::routers::{NewRouter, Router, RouterInterface, RouterQuery};
+pub use self::security_group_rules::{
+    NewSecurityGroupRule, SecurityGroupRule, SecurityGroupRuleQuery,
 };
-pub use self::subnets::{NewSubnet, Subnet, SubnetQuery};
+pub use self::security_groups::{NewSecurityGroup, SecurityGroup, SecurityGroupQuery};
+pub use self::segments::{NewSegment, Segment, SegmentQuery};
+pub use self::subnet_pools::{NewSubnetPool, SubnetPool, SubnetPoolQuery};
+pub(crate) use self::subnets::create_subnets;
+pub use self::subnets::{FreeAddresses, NewSubnet, Subnet, SubnetQuery};
+pub(crate) use self::topology::network_topology;
+pub use self::topology::{NetworkTopology, TopologyEdge, TopologyNode, TopologyNodeKind};
+pub use self::trunks::{NewTrunk, Trunk, TrunkQuery};
+pub use self::vpn_services::{NewVpnService, VpnService, VpnServiceQuery};
+pub use super::common::Taggable;
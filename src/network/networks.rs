@@ -22,12 +22,12 @@ use chrono::{DateTime, FixedOffset};
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 
 use super::super::common::{
-    DeletionWaiter, IntoVerified, NetworkRef, Refresh, ResourceIterator, ResourceQuery,
+    DeletionWaiter, IntoVerified, NetworkRef, ProjectRef, QosPolicyRef, Refresh, ResourceIterator,
+    ResourceQuery,
 };
-use super::super::session::Session;
-use super::super::utils::Query;
-use super::super::{Error, Result, Sort};
-use super::{api, protocol};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, NewRbacPolicy, RbacPolicy, RbacPolicyQuery};
 
 /// A query to network list.
 #[derive(Clone, Debug)]
@@ -35,6 +35,7 @@ pub struct NetworkQuery {
     session: Rc<Session>,
     query: Query,
     can_paginate: bool,
+    dhcp_agent: Option<String>,
 }
 
 /// Structure representing a single network.
@@ -42,6 +43,7 @@ pub struct NetworkQuery {
 pub struct Network {
     session: Rc<Session>,
     inner: protocol::Network,
+    qos_policy: Option<QosPolicyRef>,
     dirty: HashSet<&'static str>,
 }
 
@@ -50,6 +52,7 @@ pub struct Network {
 pub struct NewNetwork {
     session: Rc<Session>,
     inner: protocol::Network,
+    qos_policy: Option<QosPolicyRef>,
 }
 
 impl Network {
@@ -58,6 +61,7 @@ impl Network {
         Network {
             session,
             inner,
+            qos_policy: None,
             dirty: HashSet::new(),
         }
     }
@@ -123,6 +127,14 @@ impl Network {
         id: ref String
     }
 
+    /// Fetch IP address availability of the network.
+    ///
+    /// Requires the `network-ip-availability` extension, which is usually
+    /// only accessible to administrators.
+    pub fn ip_availability(&self) -> Result<protocol::NetworkIpAvailability> {
+        api::get_network_ip_availability(&self.session, &self.inner.id)
+    }
+
     transparent_property! {
         #[doc = "Whether the network is the default pool (if available)."]
         is_default: Option<bool>
@@ -164,11 +176,72 @@ impl Network {
     }
 
     update_field! {
-        #[doc = "Configure whether port security is enabled by default."]
+        #[doc = "Configure whether port security is enabled by default (requires the `port-security` extension)."]
         set_port_security_enabled, with_port_security_enabled
             -> port_security_enabled: optional bool
     }
 
+    transparent_property! {
+        #[doc = "Network type of the provider network (if available)."]
+        provider_network_type: ref Option<protocol::NetworkType>
+    }
+
+    transparent_property! {
+        #[doc = "Physical network of the provider network (if available)."]
+        provider_physical_network: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Segmentation ID (e.g. VLAN ID) of the provider network (if available)."]
+        provider_segmentation_id: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "ID of the QoS policy applied to the network (if any)."]
+        qos_policy_id: ref Option<String>
+    }
+
+    /// Apply a QoS policy to the network.
+    ///
+    /// The policy is resolved when the network is saved.
+    #[allow(unused_results)]
+    pub fn set_qos_policy<P>(&mut self, policy: P)
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.qos_policy = Some(policy.into());
+        self.dirty.insert("qos_policy_id");
+    }
+
+    /// Apply a QoS policy to the network.
+    ///
+    /// The policy is resolved when the network is saved.
+    pub fn with_qos_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.set_qos_policy(policy);
+        self
+    }
+
+    /// Remove the QoS policy from the network.
+    #[allow(unused_results)]
+    pub fn clear_qos_policy(&mut self) {
+        self.qos_policy = None;
+        self.inner.qos_policy_id = None;
+        self.dirty.insert("qos_policy_id");
+    }
+
+    transparent_property! {
+        #[doc = "Revision number of the network (if available)."]
+        revision_number: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "Segments of a multi-segment network."]
+        segments: ref Vec<protocol::NetworkSegment>
+    }
+
     transparent_property! {
         #[doc = "Whether the network is shared."]
         shared: bool
@@ -179,6 +252,48 @@ impl Network {
         set_shared, with_shared -> shared: bool
     }
 
+    /// Share the network with a specific project.
+    ///
+    /// Creates an RBAC policy granting `access_as_shared` to the project.
+    pub fn share_with<P: Into<ProjectRef>>(&self, project: P) -> Result<RbacPolicy> {
+        NewRbacPolicy::new(
+            self.session.clone(),
+            protocol::RbacPolicyObjectType::Network,
+            self.inner.id.clone(),
+            protocol::RbacPolicyAction::AccessAsShared,
+            project.into(),
+        )
+        .create()
+    }
+
+    /// Stop sharing the network with a specific project.
+    ///
+    /// Deletes RBAC policies created by `share_with`. Fails with
+    /// `ResourceNotFound` if the network is not shared with the project.
+    pub fn unshare_from<P: Into<ProjectRef>>(&self, project: P) -> Result<()> {
+        let project = project.into().into_verified(&self.session)?;
+        let policies = RbacPolicyQuery::new(self.session.clone())
+            .with_object_type(protocol::RbacPolicyObjectType::Network)
+            .with_object_id(self.inner.id.clone())
+            .with_action(protocol::RbacPolicyAction::AccessAsShared)
+            .with_target_project(project.clone())
+            .all()?;
+        if policies.is_empty() {
+            return Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                format!(
+                    "Network {} is not shared with project {}",
+                    self.inner.id, project
+                ),
+            ));
+        }
+
+        for policy in policies {
+            let _ = policy.delete()?;
+        }
+        Ok(())
+    }
+
     transparent_property! {
         #[doc = "Status of the network."]
         status: protocol::NetworkStatus
@@ -211,25 +326,39 @@ impl Network {
 
     /// Save the changes to the network.
     pub fn save(&mut self) -> Result<()> {
+        self.save_revision(None)
+    }
+
+    fn save_revision(&mut self, revision: Option<u32>) -> Result<()> {
+        if let Some(ref qos_policy) = self.qos_policy {
+            let verified = qos_policy.clone().into_verified(&self.session)?;
+            self.inner.qos_policy_id = Some(verified.into());
+        }
         let mut update = protocol::NetworkUpdate::default();
         save_fields! {
-            self -> update: admin_state_up shared
+            self -> update: admin_state_up qos_policy_id shared
         };
         save_option_fields! {
             self -> update: description external dns_domain is_default mtu name
                 port_security_enabled
         };
-        let inner = api::update_network(&self.session, self.id(), update)?;
+        let inner = api::update_network(&self.session, self.id(), update, revision)?;
+        self.qos_policy = None;
         self.dirty.clear();
         self.inner = inner;
         Ok(())
     }
 }
 
+taggable_resource!(Network -> "networks");
+
+revisioned_resource!(Network -> "network");
+
 impl Refresh for Network {
     /// Refresh the network.
     fn refresh(&mut self) -> Result<()> {
         self.inner = api::get_network_by_id(&self.session, &self.inner.id)?;
+        self.qos_policy = None;
         self.dirty.clear();
         Ok(())
     }
@@ -241,6 +370,21 @@ impl NetworkQuery {
             session,
             query: Query::new(),
             can_paginate: true,
+            dhcp_agent: None,
+        }
+    }
+
+    /// Only list networks hosted by the given DHCP agent.
+    pub(crate) fn with_dhcp_agent(mut self, agent_id: String) -> Self {
+        self.dhcp_agent = Some(agent_id);
+        self
+    }
+
+    /// Path to list networks at.
+    fn list_path(&self) -> Vec<&str> {
+        match self.dhcp_agent {
+            Some(ref agent_id) => vec!["agents", agent_id, "dhcp-networks"],
+            None => vec!["networks"],
         }
     }
 
@@ -276,6 +420,13 @@ impl NetworkQuery {
         self
     }
 
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    tag_query_filters!();
+
     /// Convert this query into an iterator executing the request.
     ///
     /// Returns a `FallibleIterator`, which is an iterator with each `next`
@@ -300,7 +451,7 @@ impl NetworkQuery {
     /// with `TooManyItems` if the query produces more than one result.
     pub fn one(mut self) -> Result<Network> {
         debug!("Fetching one network with {:?}", self.query);
-        if self.can_paginate {
+        if self.can_paginate()? {
             // We need only one result. We fetch maximum two to be able
             // to check if the query yieled more than one result.
             self.query.push("limit", 2);
@@ -316,7 +467,8 @@ impl ResourceQuery for NetworkQuery {
     const DEFAULT_LIMIT: usize = 50;
 
     fn can_paginate(&self) -> Result<bool> {
-        Ok(self.can_paginate)
+        // Agent scheduler API does not support pagination.
+        Ok(self.can_paginate && self.dhcp_agent.is_none())
     }
 
     fn extract_marker(&self, resource: &Self::Item) -> String {
@@ -325,26 +477,42 @@ impl ResourceQuery for NetworkQuery {
 
     fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
         let query = self.query.with_marker_and_limit(limit, marker);
-        Ok(api::list_networks(&self.session, &query)?
+        let items = match self.dhcp_agent {
+            Some(ref agent_id) => api::list_dhcp_agent_networks(&self.session, agent_id, &query)?,
+            None => api::list_networks(&self.session, &query)?,
+        };
+        Ok(items
             .into_iter()
             .map(|item| Network::new(self.session.clone(), item))
             .collect())
     }
 }
 
+projectable_query!(NetworkQuery: list_path() -> "networks");
+
 impl NewNetwork {
     /// Start creating a network.
     pub(crate) fn new(session: Rc<Session>) -> NewNetwork {
         NewNetwork {
             session,
             inner: protocol::Network::default(),
+            qos_policy: None,
         }
     }
 
     /// Request creation of a network.
     pub fn create(self) -> Result<Network> {
-        let inner = api::create_network(&self.session, self.inner)?;
-        Ok(Network::new(self.session, inner))
+        let session = self.session.clone();
+        let inner = api::create_network(&session, self.into_request()?)?;
+        Ok(Network::new(session, inner))
+    }
+
+    /// Verify references and convert into a protocol request.
+    fn into_request(mut self) -> Result<protocol::Network> {
+        if let Some(qos_policy) = self.qos_policy {
+            self.inner.qos_policy_id = Some(qos_policy.into_verified(&self.session)?.into());
+        }
+        Ok(self.inner)
     }
 
     creation_inner_field! {
@@ -383,11 +551,51 @@ impl NewNetwork {
     }
 
     creation_inner_field! {
-        #[doc = "Configure whether port security is enabled by default."]
+        #[doc = "Configure whether port security is enabled by default (requires the `port-security` extension)."]
         set_port_security_enabled, with_port_security_enabled
             -> port_security_enabled: optional bool
     }
 
+    creation_inner_field! {
+        #[doc = "Set network type of the provider network, e.g. `vlan` (requires the `provider` extension)."]
+        set_provider_network_type, with_provider_network_type
+            -> provider_network_type: optional protocol::NetworkType
+    }
+
+    creation_inner_field! {
+        #[doc = "Set physical network of the provider network (requires the `provider` extension)."]
+        set_provider_physical_network, with_provider_physical_network
+            -> provider_physical_network: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set segmentation ID (e.g. VLAN ID) of the provider network (requires the `provider` extension)."]
+        set_provider_segmentation_id, with_provider_segmentation_id
+            -> provider_segmentation_id: optional u32
+    }
+
+    /// Apply a QoS policy to the network.
+    pub fn set_qos_policy<P>(&mut self, policy: P)
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.qos_policy = Some(policy.into());
+    }
+
+    /// Apply a QoS policy to the network.
+    pub fn with_qos_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.set_qos_policy(policy);
+        self
+    }
+
+    creation_inner_vec! {
+        #[doc = "Add a segment to a multi-segment network."]
+        add_segment, with_segment -> segments: protocol::NetworkSegment
+    }
+
     creation_inner_field! {
         #[doc = "Configure VLAN transparency mode of the network."]
         set_vlan_transparent, with_vlan_transparent
@@ -395,6 +603,28 @@ impl NewNetwork {
     }
 }
 
+/// Create several networks in one request.
+///
+/// The networks are returned in the same order as the requests. The operation
+/// is atomic: on failure no networks are created.
+pub(crate) fn create_networks(
+    session: Rc<Session>,
+    requests: Vec<NewNetwork>,
+) -> Result<Vec<Network>> {
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let requests = requests
+        .into_iter()
+        .map(NewNetwork::into_request)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(api::create_networks(&session, requests)?
+        .into_iter()
+        .map(|item| Network::new(session.clone(), item))
+        .collect())
+}
+
 impl IntoFallibleIterator for NetworkQuery {
     type Item = Network;
 
@@ -424,3 +654,180 @@ impl IntoVerified for NetworkRef {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use super::super::super::common::Taggable;
+    use super::super::super::utils::test::fake_session;
+    use super::super::super::utils::Session;
+    use super::super::super::ErrorKind;
+    use super::super::api::test::{mock_network_session, network_session};
+    use super::{Network, NetworkQuery};
+
+    fn network(session: Session, revision_number: Option<u32>) -> Network {
+        let inner = serde_json::from_value(serde_json::json!({
+            "admin_state_up": true,
+            "id": "net-id",
+            "name": "net",
+            "revision_number": revision_number,
+            "shared": false,
+            "status": "ACTIVE",
+            "subnets": [],
+            "tags": ["a"],
+        }))
+        .unwrap();
+        Network::new(Rc::new(session), inner)
+    }
+
+    #[test]
+    fn test_save_if_unchanged_without_revision() {
+        let mut network = network(fake_session(), None);
+        network.set_name("new");
+        let err = network.save_if_unchanged().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(network.is_dirty());
+    }
+
+    #[test]
+    fn test_tags_stay_unique() {
+        let (session, requests) = network_session(&["standard-attr-tag"]);
+        let mut network = network(session, Some(1));
+        network.add_tag("a").unwrap();
+        network.add_tag("b").unwrap();
+        network.add_tag("b").unwrap();
+        assert_eq!(network.tags(), &vec!["a".to_string(), "b".to_string()]);
+        network.remove_tag("a").unwrap();
+        assert_eq!(network.tags(), &vec!["b".to_string()]);
+
+        let requests = requests.lock().unwrap();
+        let paths: Vec<_> = requests
+            .iter()
+            .filter(|r| r.method != "GET")
+            .map(|r| format!("{} {}", r.method, r.path))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "PUT /v2.1/networks/net-id/tags/a",
+                "PUT /v2.1/networks/net-id/tags/b",
+                "PUT /v2.1/networks/net-id/tags/b",
+                "DELETE /v2.1/networks/net-id/tags/a",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unshare_from_not_shared() {
+        let (session, requests) = mock_network_session(&["rbac-policies"], |_| {
+            (200, serde_json::json!({ "rbac_policies": [] }))
+        });
+        let network = network(session, None);
+        let err = network.unshare_from("project-id").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ResourceNotFound);
+
+        let requests = requests.lock().unwrap();
+        let calls: Vec<_> = requests
+            .iter()
+            .filter(|r| r.path.starts_with("/v2.1/rbac-policies"))
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].method, "GET");
+        assert!(calls[0].path.contains("object_id=net-id"));
+        assert!(calls[0].path.contains("target_tenant=project-id"));
+    }
+
+    #[test]
+    fn test_unshare_from() {
+        let (session, requests) = mock_network_session(&["rbac-policies"], |request| {
+            // The second page (with a marker) is empty.
+            if request.method == "GET" && !request.path.contains("marker=") {
+                let policy = serde_json::json!({
+                    "action": "access_as_shared",
+                    "id": "policy-id",
+                    "object_id": "net-id",
+                    "object_type": "network",
+                    "target_tenant": "project-id",
+                });
+                (200, serde_json::json!({ "rbac_policies": [policy] }))
+            } else if request.method == "GET" {
+                (200, serde_json::json!({ "rbac_policies": [] }))
+            } else {
+                (204, serde_json::Value::Null)
+            }
+        });
+        let network = network(session, None);
+        network.unshare_from("project-id").unwrap();
+
+        let requests = requests.lock().unwrap();
+        let deletions: Vec<_> = requests.iter().filter(|r| r.method == "DELETE").collect();
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].path, "/v2.1/rbac-policies/policy-id");
+    }
+
+    #[test]
+    fn test_dhcp_agent_networks_one() {
+        let (session, requests) = mock_network_session(&["dhcp_agent_scheduler"], |_| {
+            let network = serde_json::json!({
+                "admin_state_up": true,
+                "id": "net-id",
+                "name": "net",
+                "shared": false,
+                "status": "ACTIVE",
+                "subnets": [],
+            });
+            (200, serde_json::json!({ "networks": [network] }))
+        });
+        let network = NetworkQuery::new(Rc::new(session))
+            .with_dhcp_agent("agent-id".into())
+            .one()
+            .unwrap();
+        assert_eq!(network.id(), "net-id");
+
+        let requests = requests.lock().unwrap();
+        let calls: Vec<_> = requests
+            .iter()
+            .filter(|r| r.path.starts_with("/v2.1/agents"))
+            .collect();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].path, "/v2.1/agents/agent-id/dhcp-networks");
+    }
+
+    #[test]
+    fn test_qos_policy_resolved_on_save() {
+        let (session, requests) = mock_network_session(&["qos"], |request| {
+            if request.path == "/v2.1/qos/policies/gold" {
+                let policy = serde_json::json!({"id": "policy-id", "name": "gold"});
+                (200, serde_json::json!({ "policy": policy }))
+            } else {
+                let network = serde_json::json!({
+                    "admin_state_up": true,
+                    "id": "net-id",
+                    "name": "net",
+                    "qos_policy_id": "policy-id",
+                    "shared": false,
+                    "status": "ACTIVE",
+                    "subnets": [],
+                });
+                (200, serde_json::json!({ "network": network }))
+            }
+        });
+        let mut network = network(session, None).with_qos_policy("gold");
+        assert!(network.is_dirty());
+        assert!(requests.lock().unwrap().is_empty());
+
+        network.save().unwrap();
+        assert!(!network.is_dirty());
+        assert_eq!(network.qos_policy_id().as_ref().unwrap(), "policy-id");
+
+        let requests = requests.lock().unwrap();
+        let update = requests.iter().find(|r| r.method == "PUT").unwrap();
+        assert_eq!(update.path, "/v2.1/networks/net-id");
+        let body: serde_json::Value = serde_json::from_str(&update.body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "network": { "qos_policy_id": "policy-id" } })
+        );
+    }
+}
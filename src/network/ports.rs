@@ -14,7 +14,7 @@
 
 //! Ports management via Port API.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::mem;
 use std::net;
 use std::rc::Rc;
@@ -23,13 +23,13 @@ use std::time::Duration;
 use chrono::{DateTime, FixedOffset};
 use eui48::MacAddress;
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use serde_json::Value;
 
 use super::super::common::{
-    DeletionWaiter, IntoVerified, NetworkRef, PortRef, Refresh, ResourceIterator, ResourceQuery,
-    SubnetRef,
+    DeletionWaiter, IntoVerified, NetworkRef, PortRef, QosPolicyRef, Refresh, ResourceIterator,
+    ResourceQuery, SecurityGroupRef, SubnetRef,
 };
-use super::super::session::Session;
-use super::super::utils::Query;
+use super::super::utils::{Query, Session};
 use super::super::{Error, Result, Sort};
 use super::{api, protocol, Network, Subnet};
 
@@ -40,6 +40,7 @@ pub struct PortQuery {
     query: Query,
     can_paginate: bool,
     network: Option<NetworkRef>,
+    subnet: Option<SubnetRef>,
 }
 
 /// A fixed IP address of a port.
@@ -58,6 +59,7 @@ pub struct Port {
     session: Rc<Session>,
     inner: protocol::Port,
     fixed_ips: Vec<PortIpAddress>,
+    qos_policy: Option<QosPolicyRef>,
     dirty: HashSet<&'static str>,
 }
 
@@ -79,6 +81,8 @@ pub struct NewPort {
     inner: protocol::Port,
     network: NetworkRef,
     fixed_ips: Vec<PortIpRequest>,
+    qos_policy: Option<QosPolicyRef>,
+    security_groups: Vec<SecurityGroupRef>,
 }
 
 fn convert_fixed_ips(session: &Rc<Session>, inner: &mut protocol::Port) -> Vec<PortIpAddress> {
@@ -102,6 +106,7 @@ impl Port {
             session,
             inner,
             fixed_ips,
+            qos_policy: None,
             dirty: HashSet::new(),
         }
     }
@@ -122,6 +127,17 @@ impl Port {
         set_admin_state_up, with_admin_state_up -> admin_state_up: bool
     }
 
+    transparent_property! {
+        #[doc = "Address pairs allowed to pass through the port."]
+        allowed_address_pairs: ref Vec<protocol::PortAllowedAddressPair>
+    }
+
+    update_field_mut! {
+        #[doc = "Update the allowed address pairs."]
+        allowed_address_pairs_mut, set_allowed_address_pairs, with_allowed_address_pairs
+            -> allowed_address_pairs: Vec<protocol::PortAllowedAddressPair>
+    }
+
     /// Whether the `device_owner` is a Compute server.
     pub fn attached_to_server(&self) -> bool {
         match self.inner.device_owner {
@@ -130,6 +146,44 @@ impl Port {
         }
     }
 
+    transparent_property! {
+        #[doc = "ID of the host the port is bound to (if available)."]
+        binding_host_id: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the host the port is bound to (admin-only)."]
+        set_binding_host_id, with_binding_host_id -> binding_host_id: optional String
+    }
+
+    /// Unbind the port from its host (admin-only).
+    #[allow(unused_results)]
+    pub fn clear_binding_host_id(&mut self) {
+        self.inner.binding_host_id = None;
+        self.dirty.insert("binding_host_id");
+    }
+
+    transparent_property! {
+        #[doc = "Binding profile of the port (if available)."]
+        binding_profile: ref HashMap<String, Value>
+    }
+
+    update_field_mut! {
+        #[doc = "Update the binding profile (admin-only)."]
+        binding_profile_mut, set_binding_profile, with_binding_profile
+            -> binding_profile: HashMap<String, Value>
+    }
+
+    transparent_property! {
+        #[doc = "Type of the virtual NIC the port is bound to (if available)."]
+        binding_vnic_type: ref Option<protocol::VnicType>
+    }
+
+    update_field! {
+        #[doc = "Update the type of the virtual NIC."]
+        set_binding_vnic_type, with_binding_vnic_type -> binding_vnic_type: optional protocol::VnicType
+    }
+
     transparent_property! {
         #[doc = "Creation data and time (if available)."]
         created_at: Option<DateTime<FixedOffset>>
@@ -171,7 +225,7 @@ impl Port {
     }
 
     update_field! {
-        #[doc = "Update the DNS domain."]
+        #[doc = "Update the DNS domain (requires the `dns-integration` extension)."]
         set_dns_domain, with_dns_domain -> dns_domain: optional String
     }
 
@@ -181,7 +235,7 @@ impl Port {
     }
 
     update_field! {
-        #[doc = "Update the DNS name."]
+        #[doc = "Update the DNS name (requires the `dns-integration` extension)."]
         set_dns_name, with_dns_name -> dns_name: optional String
     }
 
@@ -244,6 +298,80 @@ impl Port {
         network_id: ref String
     }
 
+    transparent_property! {
+        #[doc = "Whether port security is enabled (if available)."]
+        port_security_enabled: Option<bool>
+    }
+
+    update_field! {
+        #[doc = "Enable or disable port security (requires the `port-security` extension)."]
+        set_port_security_enabled, with_port_security_enabled -> port_security_enabled: optional bool
+    }
+
+    transparent_property! {
+        #[doc = "ID of the QoS policy applied to the port (if any)."]
+        qos_policy_id: ref Option<String>
+    }
+
+    /// Apply a QoS policy to the port.
+    ///
+    /// The policy is resolved when the port is saved.
+    #[allow(unused_results)]
+    pub fn set_qos_policy<P>(&mut self, policy: P)
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.qos_policy = Some(policy.into());
+        self.dirty.insert("qos_policy_id");
+    }
+
+    /// Apply a QoS policy to the port.
+    ///
+    /// The policy is resolved when the port is saved.
+    pub fn with_qos_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.set_qos_policy(policy);
+        self
+    }
+
+    /// Remove the QoS policy from the port.
+    #[allow(unused_results)]
+    pub fn clear_qos_policy(&mut self) {
+        self.qos_policy = None;
+        self.inner.qos_policy_id = None;
+        self.dirty.insert("qos_policy_id");
+    }
+
+    transparent_property! {
+        #[doc = "Revision number of the port (if available)."]
+        revision_number: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "IDs of security groups applied to the port."]
+        security_groups: ref Vec<String>
+    }
+
+    /// Update security groups applied to the port.
+    ///
+    /// Every reference is verified before being stored.
+    #[allow(unused_results)]
+    pub fn set_security_groups<I>(&mut self, groups: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<SecurityGroupRef>,
+    {
+        let mut verified = Vec::new();
+        for group in groups {
+            verified.push(group.into().into_verified(&self.session)?.into());
+        }
+        self.inner.security_groups = verified;
+        self.dirty.insert("security_groups");
+        Ok(())
+    }
+
     transparent_property! {
         #[doc = "Port status."]
         status: protocol::NetworkStatus
@@ -271,26 +399,45 @@ impl Port {
 
     /// Save the changes to the port.
     pub fn save(&mut self) -> Result<()> {
+        self.save_revision(None)
+    }
+
+    fn save_revision(&mut self, revision: Option<u32>) -> Result<()> {
+        if let Some(ref qos_policy) = self.qos_policy {
+            let verified = qos_policy.clone().into_verified(&self.session)?;
+            self.inner.qos_policy_id = Some(verified.into());
+        }
         let mut update = protocol::PortUpdate::default();
         save_fields! {
-            self -> update: admin_state_up extra_dhcp_opts mac_address
+            self -> update: admin_state_up allowed_address_pairs binding_profile
+                extra_dhcp_opts mac_address qos_policy_id security_groups
         };
         save_option_fields! {
-            self -> update: description device_id device_owner dns_domain
-                dns_name name
+            self -> update: binding_host_id binding_vnic_type description device_id
+                device_owner dns_domain dns_name name port_security_enabled
         };
-        let mut inner = api::update_port(&self.session, self.id(), update)?;
+        // Neutron unbinds the port when the host is set to an empty string.
+        if self.dirty.contains("binding_host_id") && update.binding_host_id.is_none() {
+            update.binding_host_id = Some(String::new());
+        }
+        let mut inner = api::update_port(&self.session, self.id(), update, revision)?;
         self.fixed_ips = convert_fixed_ips(&self.session, &mut inner);
+        self.qos_policy = None;
         self.dirty.clear();
         self.inner = inner;
         Ok(())
     }
 }
 
+taggable_resource!(Port -> "ports");
+
+revisioned_resource!(Port -> "port");
+
 impl Refresh for Port {
     /// Refresh the port.
     fn refresh(&mut self) -> Result<()> {
         self.inner = api::get_port_by_id(&self.session, &self.inner.id)?;
+        self.qos_policy = None;
         self.fixed_ips = convert_fixed_ips(&self.session, &mut self.inner);
         self.dirty.clear();
         Ok(())
@@ -304,6 +451,27 @@ impl PortIpAddress {
     }
 }
 
+impl PortIpRequest {
+    /// Convert into a protocol fixed IP, verifying the subnet.
+    pub(crate) fn into_fixed_ip(self, session: &Session) -> Result<protocol::FixedIp> {
+        Ok(match self {
+            PortIpRequest::IpAddress(ip) => protocol::FixedIp {
+                ip_address: ip,
+                subnet_id: Default::default(),
+            },
+            PortIpRequest::AnyIpFromSubnet(subnet) => protocol::FixedIp {
+                // 0.0.0.0 is skipped when serializing
+                ip_address: net::IpAddr::V4(net::Ipv4Addr::new(0, 0, 0, 0)),
+                subnet_id: subnet.into_verified(session)?.into(),
+            },
+            PortIpRequest::IpFromSubnet(ip, subnet) => protocol::FixedIp {
+                ip_address: ip,
+                subnet_id: subnet.into_verified(session)?.into(),
+            },
+        })
+    }
+}
+
 impl PortQuery {
     pub(crate) fn new(session: Rc<Session>) -> PortQuery {
         PortQuery {
@@ -311,6 +479,7 @@ impl PortQuery {
             query: Query::new(),
             can_paginate: true,
             network: None,
+            subnet: None,
         }
     }
 
@@ -381,11 +550,29 @@ impl PortQuery {
         self
     }
 
+    /// Filter by subnet (ports with a fixed IP from this subnet).
+    pub fn set_subnet<S: Into<SubnetRef>>(&mut self, value: S) {
+        self.subnet = Some(value.into());
+    }
+
+    /// Filter by subnet (ports with a fixed IP from this subnet).
+    pub fn with_subnet<S: Into<SubnetRef>>(mut self, value: S) -> Self {
+        self.set_subnet(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
     query_filter! {
         #[doc = "Filter by status."]
         set_status, with_status -> status: protocol::NetworkStatus
     }
 
+    tag_query_filters!();
+
     /// Convert this query into an iterator executing the request.
     ///
     /// Returns a `FallibleIterator`, which is an iterator with each `next`
@@ -446,10 +633,17 @@ impl ResourceQuery for PortQuery {
             let verified = network.into_verified(&self.session)?;
             self.query.push_str("network_id", verified);
         }
+        if let Some(subnet) = self.subnet.take() {
+            let verified = subnet.into_verified(&self.session)?;
+            self.query
+                .push_str("fixed_ips", format!("subnet_id={}", verified));
+        }
         Ok(())
     }
 }
 
+projectable_query!(PortQuery: ["ports"] -> "ports");
+
 impl NewPort {
     /// Start creating a port.
     pub(crate) fn new(session: Rc<Session>, network: NetworkRef) -> NewPort {
@@ -457,6 +651,10 @@ impl NewPort {
             session,
             inner: protocol::Port {
                 admin_state_up: true,
+                allowed_address_pairs: Vec::new(),
+                binding_host_id: None,
+                binding_profile: HashMap::new(),
+                binding_vnic_type: None,
                 created_at: None,
                 description: None,
                 device_id: None,
@@ -470,39 +668,47 @@ impl NewPort {
                 name: None,
                 // Will be replaced in create()
                 network_id: String::new(),
+                port_security_enabled: None,
                 project_id: None,
+                qos_policy_id: None,
+                revision_number: None,
                 security_groups: Vec::new(),
                 // Dummy value, not used when serializing
                 status: protocol::NetworkStatus::Active,
+                tags: Vec::new(),
                 updated_at: None,
             },
             network,
             fixed_ips: Vec::new(),
+            qos_policy: None,
+            security_groups: Vec::new(),
         }
     }
 
     /// Request creation of the port.
-    pub fn create(mut self) -> Result<Port> {
+    pub fn create(self) -> Result<Port> {
+        let session = self.session.clone();
+        let port = api::create_port(&session, self.into_request()?)?;
+        Ok(Port::new(session, port))
+    }
+
+    /// Verify references and convert into a protocol request.
+    fn into_request(mut self) -> Result<protocol::Port> {
         self.inner.network_id = self.network.into_verified(&self.session)?.into();
         for request in self.fixed_ips {
-            self.inner.fixed_ips.push(match request {
-                PortIpRequest::IpAddress(ip) => protocol::FixedIp {
-                    ip_address: ip,
-                    subnet_id: Default::default(),
-                },
-                PortIpRequest::AnyIpFromSubnet(subnet) => protocol::FixedIp {
-                    ip_address: net::IpAddr::V4(net::Ipv4Addr::new(0, 0, 0, 0)),
-                    subnet_id: subnet.into_verified(&self.session)?.into(),
-                },
-                PortIpRequest::IpFromSubnet(ip, subnet) => protocol::FixedIp {
-                    ip_address: ip,
-                    subnet_id: subnet.into_verified(&self.session)?.into(),
-                },
-            });
+            self.inner
+                .fixed_ips
+                .push(request.into_fixed_ip(&self.session)?);
         }
-
-        let port = api::create_port(&self.session, self.inner)?;
-        Ok(Port::new(self.session, port))
+        if let Some(qos_policy) = self.qos_policy {
+            self.inner.qos_policy_id = Some(qos_policy.into_verified(&self.session)?.into());
+        }
+        for group in self.security_groups {
+            self.inner
+                .security_groups
+                .push(group.into_verified(&self.session)?.into());
+        }
+        Ok(self.inner)
     }
 
     creation_inner_field! {
@@ -510,7 +716,37 @@ impl NewPort {
         set_admin_state_up, with_admin_state_up -> admin_state_up: bool
     }
 
-    // TODO(dtantsur): allowed_address_pairs
+    /// Address pairs allowed to pass through the port.
+    pub fn allowed_address_pairs(&mut self) -> &mut Vec<protocol::PortAllowedAddressPair> {
+        &mut self.inner.allowed_address_pairs
+    }
+
+    creation_inner_field! {
+        #[doc = "Set address pairs allowed to pass through the port."]
+        set_allowed_address_pairs, with_allowed_address_pairs -> allowed_address_pairs:
+            Vec<protocol::PortAllowedAddressPair>
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the host to bind the port to (admin-only)."]
+        set_binding_host_id, with_binding_host_id -> binding_host_id: optional String
+    }
+
+    /// Binding profile of the port (admin-only).
+    pub fn binding_profile(&mut self) -> &mut HashMap<String, Value> {
+        &mut self.inner.binding_profile
+    }
+
+    creation_inner_field! {
+        #[doc = "Set binding profile of the port (admin-only)."]
+        set_binding_profile, with_binding_profile -> binding_profile: HashMap<String, Value>
+    }
+
+    creation_inner_field! {
+        #[doc = "Set type of the virtual NIC to bind the port to."]
+        set_binding_vnic_type, with_binding_vnic_type -> binding_vnic_type:
+            optional protocol::VnicType
+    }
 
     creation_inner_field! {
         #[doc = "Set description of the port."]
@@ -528,12 +764,12 @@ impl NewPort {
     }
 
     creation_inner_field! {
-        #[doc = "Set DNS domain for the port."]
+        #[doc = "Set DNS domain for the port (requires the `dns-integration` extension)."]
         set_dns_domain, with_dns_domain -> dns_domain: optional String
     }
 
     creation_inner_field! {
-        #[doc = "Set DNS name for the port."]
+        #[doc = "Set DNS name for the port (requires the `dns-integration` extension)."]
         set_dns_name, with_dns_name -> dns_name: optional String
     }
 
@@ -569,7 +805,62 @@ impl NewPort {
         set_name, with_name -> name: optional String
     }
 
-    // TODO(dtantsur): security groups
+    creation_inner_field! {
+        #[doc = "Enable or disable port security (requires the `port-security` extension)."]
+        set_port_security_enabled, with_port_security_enabled -> port_security_enabled:
+            optional bool
+    }
+
+    /// Apply a QoS policy to the port.
+    pub fn set_qos_policy<P>(&mut self, policy: P)
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.qos_policy = Some(policy.into());
+    }
+
+    /// Apply a QoS policy to the port.
+    pub fn with_qos_policy<P>(mut self, policy: P) -> Self
+    where
+        P: Into<QosPolicyRef>,
+    {
+        self.set_qos_policy(policy);
+        self
+    }
+
+    /// Add a security group to the request.
+    ///
+    /// The default security group is used if none are provided.
+    pub fn add_security_group<G: Into<SecurityGroupRef>>(&mut self, group: G) {
+        self.security_groups.push(group.into());
+    }
+
+    /// Add a security group to the request.
+    ///
+    /// The default security group is used if none are provided.
+    pub fn with_security_group<G: Into<SecurityGroupRef>>(mut self, group: G) -> Self {
+        self.add_security_group(group);
+        self
+    }
+}
+
+/// Create several ports in one request.
+///
+/// The ports are returned in the same order as the requests. The operation
+/// is atomic: on failure no ports are created.
+pub(crate) fn create_ports(session: Rc<Session>, requests: Vec<NewPort>) -> Result<Vec<Port>> {
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let requests = requests
+        .into_iter()
+        .map(NewPort::into_request)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(api::create_ports(&session, requests)?
+        .into_iter()
+        .map(|item| Port::new(session.clone(), item))
+        .collect())
 }
 
 impl IntoFallibleIterator for PortQuery {
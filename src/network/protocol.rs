@@ -17,6 +17,8 @@
 #![allow(non_snake_case)]
 #![allow(missing_docs)]
 
+use std::collections::HashMap;
+use std::fmt;
 use std::marker::PhantomData;
 use std::net;
 use std::ops::Not;
@@ -25,7 +27,9 @@ use chrono::{DateTime, FixedOffset};
 use eui48::MacAddress;
 use ipnet;
 use osproto::common::empty_as_default;
-use serde::{Deserialize, Serialize};
+use serde::de::value::{Error as ValueError, StringDeserializer};
+use serde::de::{Error as DeserError, IntoDeserializer, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 use super::super::common;
@@ -48,6 +52,51 @@ protocol_enum! {
     }
 }
 
+protocol_enum! {
+    #[doc = "Known Network API extensions."]
+    enum NetworkExtension {
+        AddressScope = "address-scope",
+        Agent = "agent",
+        AutoAllocatedTopology = "auto-allocated-topology",
+        DhcpAgentScheduler = "dhcp_agent_scheduler",
+        DnsIntegration = "dns-integration",
+        ExternalNetwork = "external-net",
+        ExtraRoute = "extraroute",
+        ExtraRouteAtomic = "extraroute-atomic",
+        FloatingIpPortForwarding = "floating-ip-port-forwarding",
+        Fwaas = "fwaas_v2",
+        L3AgentScheduler = "l3_agent_scheduler",
+        Metering = "metering",
+        NetworkIpAvailability = "network-ip-availability",
+        PortSecurity = "port-security",
+        Provider = "provider",
+        Qos = "qos",
+        Rbac = "rbac-policies",
+        RevisionIfMatch = "revision-if-match",
+        Router = "router",
+        SecurityGroup = "security-group",
+        Segment = "segment",
+        StandardAttrTag = "standard-attr-tag",
+        SubnetAllocation = "subnet_allocation",
+        Trunk = "trunk",
+        Vpnaas = "vpnaas"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Common network types (provider network attribute)."]
+    enum NetworkType {
+        Flat = "flat",
+        Geneve = "geneve",
+        Gre = "gre",
+        Local = "local",
+        Vlan = "vlan",
+        Vxlan = "vxlan";
+        #[doc = "A network type not known to this library."]
+        Other(String)
+    }
+}
+
 protocol_enum! {
     #[doc = "Available sort keys."]
     enum NetworkSortKey {
@@ -85,6 +134,14 @@ impl Default for NetworkSortKey {
     }
 }
 
+protocol_enum! {
+    #[doc = "IP protocol of a port forwarding."]
+    enum PortForwardingProtocol {
+        Tcp = "tcp",
+        Udp = "udp"
+    }
+}
+
 protocol_enum! {
     #[doc = "Available sort keys."]
     enum PortSortKey {
@@ -99,6 +156,64 @@ protocol_enum! {
     }
 }
 
+protocol_enum! {
+    #[doc = "Possible router statuses."]
+    enum RouterStatus {
+        Active = "ACTIVE",
+        Allocating = "ALLOCATING",
+        Error = "ERROR"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum RouterSortKey {
+        AdminStateUp = "admin_state_up",
+        FlavorId = "flavor_id",
+        Id = "id",
+        Name = "name",
+        ProjectId = "project_id",
+        Status = "status"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Direction of a security group rule."]
+    enum SecurityGroupRuleDirection {
+        Egress = "egress",
+        Ingress = "ingress"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Ethernet type of a security group rule."]
+    enum EtherType {
+        Ipv4 = "IPv4",
+        Ipv6 = "IPv6"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum SecurityGroupSortKey {
+        Id = "id",
+        Name = "name",
+        ProjectId = "project_id"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum SegmentSortKey {
+        Id = "id",
+        Name = "name",
+        NetworkId = "network_id",
+        NetworkType = "network_type",
+        PhysicalNetwork = "physical_network",
+        SegmentationId = "segmentation_id"
+    }
+}
+
 protocol_enum! {
     #[doc = "Available sort keys."]
     enum SubnetSortKey {
@@ -162,6 +277,30 @@ pub struct Network {
     pub port_security_enabled: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub project_id: Option<String>,
+    #[serde(
+        rename = "provider:network_type",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub provider_network_type: Option<NetworkType>,
+    #[serde(
+        rename = "provider:physical_network",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub provider_physical_network: Option<String>,
+    #[serde(
+        rename = "provider:segmentation_id",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub provider_segmentation_id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos_policy_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub revision_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<NetworkSegment>,
     #[serde(default, skip_serializing_if = "Not::not")]
     pub shared: bool,
     #[serde(skip_serializing)]
@@ -169,6 +308,8 @@ pub struct Network {
     #[serde(skip_serializing)]
     pub subnets: Vec<String>,
     #[serde(default, skip_serializing)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing)]
     pub updated_at: Option<DateTime<FixedOffset>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub vlan_transparent: Option<bool>,
@@ -190,15 +331,71 @@ impl Default for Network {
             name: None,
             port_security_enabled: None,
             project_id: None,
+            provider_network_type: None,
+            provider_physical_network: None,
+            provider_segmentation_id: None,
+            qos_policy_id: None,
+            revision_number: None,
+            segments: Vec::new(),
             shared: false,
             status: NetworkStatus::Active,
             subnets: Vec::new(),
+            tags: Vec::new(),
             updated_at: None,
             vlan_transparent: None,
         }
     }
 }
 
+/// A segment of a multi-segment network.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct NetworkSegment {
+    /// Network type (e.g. `vlan`).
+    #[serde(rename = "provider:network_type")]
+    pub network_type: NetworkType,
+    /// Physical network this segment is mapped to (if any).
+    #[serde(
+        rename = "provider:physical_network",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub physical_network: Option<String>,
+    /// Segmentation ID (e.g. VLAN ID), if applicable.
+    #[serde(
+        rename = "provider:segmentation_id",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub segmentation_id: Option<u32>,
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __nonexhaustive: PhantomData<()>,
+}
+
+impl NetworkSegment {
+    /// Create a new network segment of the given type.
+    pub fn new<S: Into<NetworkType>>(network_type: S) -> NetworkSegment {
+        NetworkSegment {
+            network_type: network_type.into(),
+            physical_network: None,
+            segmentation_id: None,
+            __nonexhaustive: PhantomData,
+        }
+    }
+
+    /// Set the physical network of the segment.
+    pub fn with_physical_network<S: Into<String>>(mut self, value: S) -> NetworkSegment {
+        self.physical_network = Some(value.into());
+        self
+    }
+
+    /// Set the segmentation ID of the segment.
+    pub fn with_segmentation_id(mut self, value: u32) -> NetworkSegment {
+        self.segmentation_id = Some(value);
+        self
+    }
+}
+
 /// A network.
 #[derive(Debug, Clone, Default, Serialize)]
 pub struct NetworkUpdate {
@@ -219,6 +416,8 @@ pub struct NetworkUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_security_enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub qos_policy_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub shared: Option<bool>,
 }
 
@@ -235,11 +434,274 @@ pub struct NetworkUpdateRoot {
 }
 
 /// A list of networks.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct NetworksRoot {
     pub networks: Vec<Network>,
 }
 
+/// An auto-allocated topology of a project.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoAllocatedTopology {
+    /// ID of the allocated network.
+    pub id: String,
+}
+
+/// An auto-allocated topology of a project.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AutoAllocatedTopologyRoot {
+    pub auto_allocated_topology: AutoAllocatedTopology,
+}
+
+/// A Network API extension.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Extension {
+    #[serde(deserialize_with = "deser_extension_alias")]
+    pub alias: Option<NetworkExtension>,
+}
+
+/// A list of Network API extensions.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExtensionsRoot {
+    pub extensions: Vec<Extension>,
+}
+
+/// Deserialize an extension alias, ignoring extensions we do not know about.
+fn deser_extension_alias<'de, D>(
+    des: D,
+) -> ::std::result::Result<Option<NetworkExtension>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let alias = String::deserialize(des)?;
+    let des: StringDeserializer<ValueError> = alias.into_deserializer();
+    Ok(NetworkExtension::deserialize(des).ok())
+}
+
+/// IP address availability of a subnet.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubnetIpAvailability {
+    /// Network address of the subnet.
+    pub cidr: ipnet::IpNet,
+    /// IP protocol version.
+    pub ip_version: IpVersion,
+    /// ID of the subnet.
+    pub subnet_id: String,
+    /// Name of the subnet (if any).
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub subnet_name: Option<String>,
+    /// Total number of IP addresses in the subnet.
+    #[serde(deserialize_with = "deser_ip_count")]
+    pub total_ips: u128,
+    /// Number of used IP addresses in the subnet.
+    #[serde(deserialize_with = "deser_ip_count")]
+    pub used_ips: u128,
+}
+
+/// IP address availability of a network.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkIpAvailability {
+    /// ID of the network.
+    pub network_id: String,
+    /// Name of the network (if any).
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub network_name: Option<String>,
+    /// ID of the project owning the network (if available).
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Availability of the subnets of the network.
+    #[serde(default)]
+    pub subnet_ip_availability: Vec<SubnetIpAvailability>,
+    /// Total number of IP addresses in the network.
+    #[serde(deserialize_with = "deser_ip_count")]
+    pub total_ips: u128,
+    /// Number of used IP addresses in the network.
+    #[serde(deserialize_with = "deser_ip_count")]
+    pub used_ips: u128,
+}
+
+/// IP address availability of a network.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkIpAvailabilityRoot {
+    pub network_ip_availability: NetworkIpAvailability,
+}
+
+/// Deserialize an IP address count, which may not fit into `u64` for IPv6.
+///
+/// The count is parsed directly as a 128-bit integer to avoid losing precision.
+fn deser_ip_count<'de, D>(des: D) -> ::std::result::Result<u128, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct IpCountVisitor;
+
+    impl<'de> Visitor<'de> for IpCountVisitor {
+        type Value = u128;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a non-negative integer")
+        }
+
+        fn visit_u64<E: DeserError>(self, value: u64) -> ::std::result::Result<u128, E> {
+            Ok(u128::from(value))
+        }
+
+        fn visit_u128<E: DeserError>(self, value: u128) -> ::std::result::Result<u128, E> {
+            Ok(value)
+        }
+    }
+
+    des.deserialize_u128(IpCountVisitor)
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum AddressScopeSortKey {
+        Id = "id",
+        IpVersion = "ip_version",
+        Name = "name",
+        ProjectId = "project_id"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum SubnetPoolSortKey {
+        Id = "id",
+        IpVersion = "ip_version",
+        Name = "name",
+        ProjectId = "project_id"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Direction of traffic a QoS rule applies to."]
+    enum QosRuleDirection {
+        Egress = "egress",
+        Ingress = "ingress"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum QosPolicySortKey {
+        Id = "id",
+        Name = "name",
+        ProjectId = "project_id"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Possible trunk statuses."]
+    enum TrunkStatus {
+        Active = "ACTIVE",
+        Build = "BUILD",
+        Degraded = "DEGRADED",
+        Down = "DOWN",
+        Error = "ERROR"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum TrunkSortKey {
+        AdminStateUp = "admin_state_up",
+        Id = "id",
+        Name = "name",
+        PortId = "port_id",
+        ProjectId = "project_id",
+        Status = "status"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Segmentation type of a trunk sub-port."]
+    enum SegmentationType {
+        Inherit = "inherit",
+        Vlan = "vlan"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Type of a virtual NIC."]
+    enum VnicType {
+        AcceleratorDirect = "accelerator-direct",
+        AcceleratorDirectPhysical = "accelerator-direct-physical",
+        Baremetal = "baremetal",
+        Direct = "direct",
+        DirectPhysical = "direct-physical",
+        Macvtap = "macvtap",
+        Normal = "normal",
+        RemoteManaged = "remote-managed",
+        SmartNic = "smart-nic",
+        VirtioForwarder = "virtio-forwarder",
+        Vdpa = "vdpa";
+        #[doc = "A type not known to this library."]
+        Other(String)
+    }
+}
+
+impl Default for VnicType {
+    fn default() -> VnicType {
+        VnicType::Normal
+    }
+}
+
+/// An address pair allowed to pass through a port.
+#[derive(Copy, Debug, Clone, Deserialize, Serialize)]
+pub struct PortAllowedAddressPair {
+    /// IP address or CIDR.
+    #[serde(deserialize_with = "deser_ip_or_net")]
+    pub ip_address: ipnet::IpNet,
+    /// MAC address (the port's MAC address if not set).
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "common::protocol::ser_opt_mac"
+    )]
+    pub mac_address: Option<MacAddress>,
+    #[doc(hidden)]
+    #[serde(skip)]
+    pub __nonexhaustive: PhantomData<()>,
+}
+
+impl PortAllowedAddressPair {
+    /// Create a new allowed address pair.
+    pub fn new<A: Into<ipnet::IpNet>>(ip_address: A) -> PortAllowedAddressPair {
+        PortAllowedAddressPair {
+            ip_address: ip_address.into(),
+            mac_address: None,
+            __nonexhaustive: PhantomData,
+        }
+    }
+
+    /// Create a new allowed address pair with a MAC address.
+    pub fn new_with_mac_address<A: Into<ipnet::IpNet>>(
+        ip_address: A,
+        mac_address: MacAddress,
+    ) -> PortAllowedAddressPair {
+        PortAllowedAddressPair {
+            ip_address: ip_address.into(),
+            mac_address: Some(mac_address),
+            __nonexhaustive: PhantomData,
+        }
+    }
+}
+
+/// Deserialize either an IP address or a CIDR into a CIDR.
+fn deser_ip_or_net<'de, D>(des: D) -> ::std::result::Result<ipnet::IpNet, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(des)?;
+    match value.parse::<ipnet::IpNet>() {
+        Ok(net) => Ok(net),
+        Err(_) => value
+            .parse::<net::IpAddr>()
+            .map(ipnet::IpNet::from)
+            .map_err(D::Error::custom),
+    }
+}
+
 /// An extra DHCP option.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PortExtraDhcpOption {
@@ -294,8 +756,10 @@ impl PortExtraDhcpOption {
 /// A port's IP address.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FixedIp {
+    /// IP address.
     #[serde(skip_serializing_if = "::std::net::IpAddr::is_unspecified")]
     pub ip_address: net::IpAddr,
+    /// ID of the subnet the address belongs to.
     #[serde(skip_serializing_if = "String::is_empty")]
     pub subnet_id: String,
 }
@@ -304,6 +768,28 @@ pub struct FixedIp {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Port {
     pub admin_state_up: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_address_pairs: Vec<PortAllowedAddressPair>,
+    #[serde(
+        rename = "binding:host_id",
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub binding_host_id: Option<String>,
+    #[serde(
+        rename = "binding:profile",
+        deserialize_with = "common::protocol::deser_null_as_default",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub binding_profile: HashMap<String, Value>,
+    #[serde(
+        rename = "binding:vnic_type",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub binding_vnic_type: Option<VnicType>,
     #[serde(default, skip_serializing)]
     pub created_at: Option<DateTime<FixedOffset>>,
     #[serde(
@@ -354,12 +840,20 @@ pub struct Port {
     pub name: Option<String>,
     pub network_id: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_security_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos_policy_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub revision_number: Option<u32>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub security_groups: Vec<String>,
     #[serde(skip_serializing)]
     pub status: NetworkStatus,
     #[serde(default, skip_serializing)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing)]
     pub updated_at: Option<DateTime<FixedOffset>>,
 }
 
@@ -369,6 +863,14 @@ pub struct PortUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub admin_state_up: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_address_pairs: Option<Vec<PortAllowedAddressPair>>,
+    #[serde(rename = "binding:host_id", skip_serializing_if = "Option::is_none")]
+    pub binding_host_id: Option<String>,
+    #[serde(rename = "binding:profile", skip_serializing_if = "Option::is_none")]
+    pub binding_profile: Option<HashMap<String, Value>>,
+    #[serde(rename = "binding:vnic_type", skip_serializing_if = "Option::is_none")]
+    pub binding_vnic_type: Option<VnicType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub device_id: Option<String>,
@@ -390,6 +892,10 @@ pub struct PortUpdate {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub port_security_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qos_policy_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub security_groups: Option<Vec<String>>,
 }
 
@@ -406,7 +912,7 @@ pub struct PortUpdateRoot {
 }
 
 /// A list of ports.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PortsRoot {
     pub ports: Vec<Port>,
 }
@@ -435,7 +941,11 @@ pub struct HostRoute {
 pub struct Subnet {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allocation_pools: Vec<AllocationPool>,
-    pub cidr: ipnet::IpNet,
+    #[serde(
+        deserialize_with = "deser_required",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cidr: Option<ipnet::IpNet>,
     #[serde(default, skip_serializing)]
     pub created_at: Option<DateTime<FixedOffset>>,
     #[serde(
@@ -469,14 +979,28 @@ pub struct Subnet {
     )]
     pub name: Option<String>,
     pub network_id: String,
+    #[serde(
+        rename = "prefixlen",
+        skip_deserializing,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub prefix_length: Option<u8>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub project_id: Option<String>,
     #[serde(default, skip_serializing)]
+    pub revision_number: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segment_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnetpool_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing)]
     pub updated_at: Option<DateTime<FixedOffset>>,
 }
 
 impl Subnet {
-    pub(crate) fn empty(cidr: ipnet::IpNet) -> Subnet {
+    pub(crate) fn empty(cidr: Option<ipnet::IpNet>) -> Subnet {
         Subnet {
             allocation_pools: Vec::new(),
             cidr,
@@ -488,19 +1012,33 @@ impl Subnet {
             host_routes: Vec::new(),
             id: String::new(),
             ip_version: match cidr {
-                ipnet::IpNet::V4(..) => IpVersion::V4,
-                ipnet::IpNet::V6(..) => IpVersion::V6,
+                Some(ipnet::IpNet::V6(..)) => IpVersion::V6,
+                _ => IpVersion::V4,
             },
             ipv6_address_mode: None,
             ipv6_router_advertisement_mode: None,
             name: None,
             network_id: String::new(),
+            prefix_length: None,
             project_id: None,
+            revision_number: None,
+            segment_id: None,
+            subnetpool_id: None,
+            tags: Vec::new(),
             updated_at: None,
         }
     }
 }
 
+/// Deserialize a value that is optional in requests but always present in responses.
+fn deser_required<'de, D, T>(des: D) -> ::std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(des).map(Some)
+}
+
 /// A subnet.
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct SubnetUpdate {
@@ -533,23 +1071,67 @@ pub struct SubnetUpdateRoot {
 }
 
 /// A list of subnets.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SubnetsRoot {
     pub subnets: Vec<Subnet>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PortForwarding {
-    /// TCP or UDP port used by floating IP.
-    pub external_port: u16,
-    /// Fixed IP address of internal port.
-    pub internal_ip_address: net::IpAddr,
+    /// Port forwarding description.
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    /// TCP or UDP port used by floating IP.
+    pub external_port: u16,
+    /// Unique ID (empty if not returned by the server).
+    #[serde(default, skip_serializing)]
+    pub id: String,
+    /// Fixed IP address of internal port.
+    pub internal_ip_address: net::IpAddr,
     /// TCP or UDP port used by internal port.
     pub internal_port: u16,
+    /// ID of the internal port (if available).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub internal_port_id: Option<String>,
     /// Network IP protocol.
     pub protocol: String,
 }
 
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct PortForwardingUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_ip_address: Option<net::IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub internal_port_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PortForwardingRoot {
+    pub port_forwarding: PortForwarding,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PortForwardingUpdateRoot {
+    pub port_forwarding: PortForwardingUpdate,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PortForwardingsRoot {
+    pub port_forwardings: Vec<PortForwarding>,
+}
+
 /// A floating IP.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct FloatingIp {
@@ -585,12 +1167,16 @@ pub struct FloatingIp {
     #[serde(default, skip_serializing)]
     pub port_forwardings: Vec<PortForwarding>,
     #[serde(default, skip_serializing)]
+    pub revision_number: Option<u32>,
+    #[serde(default, skip_serializing)]
     pub router_id: Option<String>,
     #[serde(skip_serializing)]
     pub status: FloatingIpStatus,
     #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
     pub subnet_id: Option<String>,
     #[serde(default, skip_serializing)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing)]
     pub updated_at: Option<DateTime<FixedOffset>>,
 }
 
@@ -622,3 +1208,1744 @@ pub struct FloatingIpUpdateRoot {
 pub struct FloatingIpsRoot {
     pub floatingips: Vec<FloatingIp>,
 }
+
+/// An external gateway of a router.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExternalGateway {
+    /// Whether source NAT is enabled (if available).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_snat: Option<bool>,
+    /// IP addresses of the gateway on the external network.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_fixed_ips: Vec<FixedIp>,
+    /// ID of the external network.
+    pub network_id: String,
+}
+
+/// A router.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Router {
+    pub admin_state_up: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub availability_zone_hints: Vec<String>,
+    #[serde(default, skip_serializing)]
+    pub availability_zones: Vec<String>,
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distributed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_gateway_info: Option<ExternalGateway>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub flavor_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ha: Option<bool>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub revision_number: Option<u32>,
+    #[serde(default, skip_serializing)]
+    pub routes: Vec<HostRoute>,
+    #[serde(skip_serializing)]
+    pub status: RouterStatus,
+    #[serde(default, skip_serializing)]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Default for Router {
+    fn default() -> Router {
+        Router {
+            admin_state_up: true,
+            availability_zone_hints: Vec::new(),
+            availability_zones: Vec::new(),
+            created_at: None,
+            description: None,
+            distributed: None,
+            external_gateway_info: None,
+            flavor_id: None,
+            ha: None,
+            id: String::new(),
+            name: None,
+            project_id: None,
+            revision_number: None,
+            routes: Vec::new(),
+            status: RouterStatus::Active,
+            tags: Vec::new(),
+            updated_at: None,
+        }
+    }
+}
+
+/// A router update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RouterUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distributed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_gateway_info: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ha: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes: Option<Vec<HostRoute>>,
+}
+
+/// A router.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RouterRoot {
+    pub router: Router,
+}
+
+/// A router update.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouterUpdateRoot {
+    pub router: RouterUpdate,
+}
+
+/// A list of routers.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RoutersRoot {
+    pub routers: Vec<Router>,
+}
+
+/// A request to add or remove a router interface.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RouterInterfaceRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subnet_id: Option<String>,
+}
+
+/// Extra routes to add or remove.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouterRoutes {
+    pub routes: Vec<HostRoute>,
+}
+
+/// Extra routes to add or remove.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouterRoutesRoot {
+    pub router: RouterRoutes,
+}
+
+/// A security group rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityGroupRule {
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    pub direction: SecurityGroupRuleDirection,
+    pub ethertype: EtherType,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_range_max: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port_range_min: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_group_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_ip_prefix: Option<ipnet::IpNet>,
+    pub security_group_id: String,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A security group rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityGroupRuleRoot {
+    pub security_group_rule: SecurityGroupRule,
+}
+
+/// A list of security group rules.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityGroupRulesRoot {
+    pub security_group_rules: Vec<SecurityGroupRule>,
+}
+
+/// A security group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityGroup {
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub security_group_rules: Vec<SecurityGroupRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stateful: Option<bool>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A security group update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SecurityGroupUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stateful: Option<bool>,
+}
+
+/// A security group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SecurityGroupRoot {
+    pub security_group: SecurityGroup,
+}
+
+/// A security group update.
+#[derive(Debug, Clone, Serialize)]
+pub struct SecurityGroupUpdateRoot {
+    pub security_group: SecurityGroupUpdate,
+}
+
+/// A list of security groups.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityGroupsRoot {
+    pub security_groups: Vec<SecurityGroup>,
+}
+
+/// A sub-port of a trunk.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrunkSubPort {
+    /// ID of the port.
+    pub port_id: String,
+    /// Segmentation ID (e.g. VLAN ID).
+    pub segmentation_id: u32,
+    /// Segmentation type.
+    pub segmentation_type: SegmentationType,
+}
+
+/// A trunk.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Trunk {
+    pub admin_state_up: bool,
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    pub port_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub status: TrunkStatus,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_ports: Vec<TrunkSubPort>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A trunk update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TrunkUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A trunk.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TrunkRoot {
+    pub trunk: Trunk,
+}
+
+/// A trunk update.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrunkUpdateRoot {
+    pub trunk: TrunkUpdate,
+}
+
+/// A list of trunks.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TrunksRoot {
+    pub trunks: Vec<Trunk>,
+}
+
+/// Sub-ports to add to a trunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrunkSubPortsRoot {
+    pub sub_ports: Vec<TrunkSubPort>,
+}
+
+/// A reference to a sub-port to remove from a trunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrunkSubPortRemoval {
+    pub port_id: String,
+}
+
+/// Sub-ports to remove from a trunk.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrunkSubPortRemovalsRoot {
+    pub sub_ports: Vec<TrunkSubPortRemoval>,
+}
+
+/// A bandwidth limit QoS rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QosBandwidthLimitRule {
+    /// Direction of the traffic (egress if not set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<QosRuleDirection>,
+    /// Unique ID (empty for new rules).
+    #[serde(default, skip_serializing)]
+    pub id: String,
+    /// Maximum burst size in kbps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_burst_kbps: Option<u32>,
+    /// Maximum bandwidth in kbps.
+    pub max_kbps: u32,
+}
+
+impl QosBandwidthLimitRule {
+    /// Create a new bandwidth limit rule.
+    pub fn new(max_kbps: u32) -> QosBandwidthLimitRule {
+        QosBandwidthLimitRule {
+            direction: None,
+            id: String::new(),
+            max_burst_kbps: None,
+            max_kbps,
+        }
+    }
+}
+
+/// A DSCP marking QoS rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QosDscpMarkingRule {
+    /// DSCP mark value.
+    pub dscp_mark: u8,
+    /// Unique ID (empty for new rules).
+    #[serde(default, skip_serializing)]
+    pub id: String,
+}
+
+impl QosDscpMarkingRule {
+    /// Create a new DSCP marking rule.
+    pub fn new(dscp_mark: u8) -> QosDscpMarkingRule {
+        QosDscpMarkingRule {
+            dscp_mark,
+            id: String::new(),
+        }
+    }
+}
+
+/// A minimum bandwidth QoS rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QosMinimumBandwidthRule {
+    /// Direction of the traffic (egress if not set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<QosRuleDirection>,
+    /// Unique ID (empty for new rules).
+    #[serde(default, skip_serializing)]
+    pub id: String,
+    /// Minimum bandwidth in kbps.
+    pub min_kbps: u32,
+}
+
+impl QosMinimumBandwidthRule {
+    /// Create a new minimum bandwidth rule.
+    pub fn new(min_kbps: u32) -> QosMinimumBandwidthRule {
+        QosMinimumBandwidthRule {
+            direction: None,
+            id: String::new(),
+            min_kbps,
+        }
+    }
+}
+
+/// A QoS rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum QosRule {
+    /// Limit bandwidth.
+    #[serde(rename = "bandwidth_limit")]
+    BandwidthLimit(QosBandwidthLimitRule),
+    /// Mark traffic with a DSCP value.
+    #[serde(rename = "dscp_marking")]
+    DscpMarking(QosDscpMarkingRule),
+    /// Guarantee minimum bandwidth.
+    #[serde(rename = "minimum_bandwidth")]
+    MinimumBandwidth(QosMinimumBandwidthRule),
+    #[doc(hidden)]
+    #[serde(other)]
+    __Nonexhaustive,
+}
+
+impl QosRule {
+    /// Unique ID of the rule (empty for new rules).
+    pub fn id(&self) -> &str {
+        match *self {
+            QosRule::BandwidthLimit(ref rule) => &rule.id,
+            QosRule::DscpMarking(ref rule) => &rule.id,
+            QosRule::MinimumBandwidth(ref rule) => &rule.id,
+            QosRule::__Nonexhaustive => "",
+        }
+    }
+}
+
+impl From<QosBandwidthLimitRule> for QosRule {
+    fn from(value: QosBandwidthLimitRule) -> QosRule {
+        QosRule::BandwidthLimit(value)
+    }
+}
+
+impl From<QosDscpMarkingRule> for QosRule {
+    fn from(value: QosDscpMarkingRule) -> QosRule {
+        QosRule::DscpMarking(value)
+    }
+}
+
+impl From<QosMinimumBandwidthRule> for QosRule {
+    fn from(value: QosMinimumBandwidthRule) -> QosRule {
+        QosRule::MinimumBandwidth(value)
+    }
+}
+
+/// A QoS policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QosPolicy {
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub rules: Vec<QosRule>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub shared: bool,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A QoS policy update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QosPolicyUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// A QoS policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct QosPolicyRoot {
+    pub policy: QosPolicy,
+}
+
+/// A QoS policy update.
+#[derive(Debug, Clone, Serialize)]
+pub struct QosPolicyUpdateRoot {
+    pub policy: QosPolicyUpdate,
+}
+
+/// A list of QoS policies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QosPoliciesRoot {
+    pub policies: Vec<QosPolicy>,
+}
+
+/// An address scope.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AddressScope {
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub ip_version: IpVersion,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub shared: bool,
+}
+
+/// An address scope update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AddressScopeUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// An address scope.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AddressScopeRoot {
+    pub address_scope: AddressScope,
+}
+
+/// An address scope update.
+#[derive(Debug, Clone, Serialize)]
+pub struct AddressScopeUpdateRoot {
+    pub address_scope: AddressScopeUpdate,
+}
+
+/// A list of address scopes.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AddressScopesRoot {
+    pub address_scopes: Vec<AddressScope>,
+}
+
+/// A subnet pool.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubnetPool {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_scope_id: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        rename = "default_prefixlen",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub default_prefix_length: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_quota: Option<u32>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(skip_serializing)]
+    pub ip_version: IpVersion,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub is_default: bool,
+    #[serde(
+        rename = "max_prefixlen",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_prefix_length: Option<u8>,
+    #[serde(
+        rename = "min_prefixlen",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_prefix_length: Option<u8>,
+    pub name: String,
+    pub prefixes: Vec<ipnet::IpNet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub shared: bool,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A subnet pool update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SubnetPoolUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_scope_id: Option<Option<String>>,
+    #[serde(rename = "default_prefixlen", skip_serializing_if = "Option::is_none")]
+    pub default_prefix_length: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_quota: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+    #[serde(rename = "max_prefixlen", skip_serializing_if = "Option::is_none")]
+    pub max_prefix_length: Option<u8>,
+    #[serde(rename = "min_prefixlen", skip_serializing_if = "Option::is_none")]
+    pub min_prefix_length: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefixes: Option<Vec<ipnet::IpNet>>,
+}
+
+/// A subnet pool.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SubnetPoolRoot {
+    pub subnetpool: SubnetPool,
+}
+
+/// A subnet pool update.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubnetPoolUpdateRoot {
+    pub subnetpool: SubnetPoolUpdate,
+}
+
+/// A list of subnet pools.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubnetPoolsRoot {
+    pub subnetpools: Vec<SubnetPool>,
+}
+
+/// A network segment.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Segment {
+    #[serde(default, skip_serializing)]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    pub network_id: String,
+    pub network_type: NetworkType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physical_network: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub segmentation_id: Option<u32>,
+    #[serde(default, skip_serializing)]
+    pub updated_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A network segment update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SegmentUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A network segment.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SegmentRoot {
+    pub segment: Segment,
+}
+
+/// A network segment update.
+#[derive(Debug, Clone, Serialize)]
+pub struct SegmentUpdateRoot {
+    pub segment: SegmentUpdate,
+}
+
+/// A list of network segments.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SegmentsRoot {
+    pub segments: Vec<Segment>,
+}
+
+/// A list of resource tags.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TagsRoot {
+    pub tags: Vec<String>,
+}
+
+protocol_enum! {
+    #[doc = "Action granted by an RBAC policy."]
+    enum RbacPolicyAction {
+        AccessAsExternal = "access_as_external",
+        AccessAsShared = "access_as_shared";
+        #[doc = "An action not known to this library."]
+        Other(String)
+    }
+}
+
+protocol_enum! {
+    #[doc = "Type of an object an RBAC policy applies to."]
+    enum RbacPolicyObjectType {
+        AddressScope = "address_scope",
+        Network = "network",
+        QosPolicy = "qos_policy",
+        SecurityGroup = "security_group",
+        SubnetPool = "subnetpool";
+        #[doc = "An object type not known to this library."]
+        Other(String)
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum RbacPolicySortKey {
+        Action = "action",
+        Id = "id",
+        ObjectId = "object_id",
+        ObjectType = "object_type",
+        ProjectId = "project_id",
+        TargetProjectId = "target_tenant"
+    }
+}
+
+/// An RBAC policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RbacPolicy {
+    pub action: RbacPolicyAction,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub object_id: String,
+    pub object_type: RbacPolicyObjectType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(rename = "target_tenant")]
+    pub target_project_id: String,
+}
+
+/// An RBAC policy update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RbacPolicyUpdate {
+    #[serde(rename = "target_tenant", skip_serializing_if = "Option::is_none")]
+    pub target_project_id: Option<String>,
+}
+
+/// An RBAC policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RbacPolicyRoot {
+    pub rbac_policy: RbacPolicy,
+}
+
+/// An RBAC policy update.
+#[derive(Debug, Clone, Serialize)]
+pub struct RbacPolicyUpdateRoot {
+    pub rbac_policy: RbacPolicyUpdate,
+}
+
+/// A list of RBAC policies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RbacPoliciesRoot {
+    pub rbac_policies: Vec<RbacPolicy>,
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum AgentSortKey {
+        AgentType = "agent_type",
+        Binary = "binary",
+        Host = "host",
+        Id = "id",
+        Topic = "topic"
+    }
+}
+
+/// A network agent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Agent {
+    pub admin_state_up: bool,
+    pub agent_type: String,
+    pub alive: bool,
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub availability_zone: Option<String>,
+    pub binary: String,
+    #[serde(default)]
+    pub configurations: HashMap<String, Value>,
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub created_at: Option<DateTime<FixedOffset>>,
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub description: Option<String>,
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub heartbeat_timestamp: Option<DateTime<FixedOffset>>,
+    pub host: String,
+    pub id: String,
+    #[serde(
+        deserialize_with = "common::protocol::deser_opt_datetime_assume_utc",
+        default
+    )]
+    pub started_at: Option<DateTime<FixedOffset>>,
+    #[serde(deserialize_with = "empty_as_default", default)]
+    pub topic: Option<String>,
+}
+
+/// A network agent update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AgentUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A network agent.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentRoot {
+    pub agent: Agent,
+}
+
+/// A network agent update.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentUpdateRoot {
+    pub agent: AgentUpdate,
+}
+
+/// A list of network agents.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentsRoot {
+    pub agents: Vec<Agent>,
+}
+
+/// A request to schedule a network to a DHCP agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct DhcpAgentNetwork {
+    pub network_id: String,
+}
+
+/// A request to schedule a router to an L3 agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct L3AgentRouter {
+    pub router_id: String,
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum MeteringLabelSortKey {
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// A metering label.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MeteringLabel {
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// A metering label.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MeteringLabelRoot {
+    pub metering_label: MeteringLabel,
+}
+
+/// A list of metering labels.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeteringLabelsRoot {
+    pub metering_labels: Vec<MeteringLabel>,
+}
+
+protocol_enum! {
+    #[doc = "Direction of a metering label rule."]
+    enum MeteringLabelRuleDirection {
+        Egress = "egress",
+        Ingress = "ingress"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum MeteringLabelRuleSortKey {
+        Direction = "direction",
+        Id = "id"
+    }
+}
+
+/// A metering label rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MeteringLabelRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_ip_prefix: Option<ipnet::IpNet>,
+    pub direction: MeteringLabelRuleDirection,
+    #[serde(default)]
+    pub excluded: bool,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub metering_label_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote_ip_prefix: Option<ipnet::IpNet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_ip_prefix: Option<ipnet::IpNet>,
+}
+
+/// A metering label rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MeteringLabelRuleRoot {
+    pub metering_label_rule: MeteringLabelRule,
+}
+
+/// A list of metering label rules.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeteringLabelRulesRoot {
+    pub metering_label_rules: Vec<MeteringLabelRule>,
+}
+
+protocol_enum! {
+    #[doc = "Possible firewall group statuses."]
+    enum FirewallGroupStatus {
+        Active = "ACTIVE",
+        Down = "DOWN",
+        Error = "ERROR",
+        Inactive = "INACTIVE",
+        PendingCreate = "PENDING_CREATE",
+        PendingDelete = "PENDING_DELETE",
+        PendingUpdate = "PENDING_UPDATE"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum FirewallGroupSortKey {
+        Id = "id",
+        Name = "name",
+        Status = "status"
+    }
+}
+
+/// A firewall group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallGroup {
+    pub admin_state_up: bool,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress_firewall_policy_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingress_firewall_policy_id: Option<String>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(skip_serializing)]
+    pub status: FirewallGroupStatus,
+}
+
+/// A firewall group update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FirewallGroupUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub egress_firewall_policy_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress_firewall_policy_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ports: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// A firewall group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallGroupRoot {
+    pub firewall_group: FirewallGroup,
+}
+
+/// A firewall group update.
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallGroupUpdateRoot {
+    pub firewall_group: FirewallGroupUpdate,
+}
+
+/// A list of firewall groups.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirewallGroupsRoot {
+    pub firewall_groups: Vec<FirewallGroup>,
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum FirewallPolicySortKey {
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// A firewall policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallPolicy {
+    #[serde(default)]
+    pub audited: bool,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub firewall_rules: Vec<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub shared: bool,
+}
+
+/// A firewall policy update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FirewallPolicyUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audited: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+}
+
+/// A firewall policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallPolicyRoot {
+    pub firewall_policy: FirewallPolicy,
+}
+
+/// A firewall policy update.
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallPolicyUpdateRoot {
+    pub firewall_policy: FirewallPolicyUpdate,
+}
+
+/// A list of firewall policies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirewallPoliciesRoot {
+    pub firewall_policies: Vec<FirewallPolicy>,
+}
+
+/// A request to insert a rule into or remove a rule from a firewall policy.
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallPolicyRuleRequest {
+    pub firewall_rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_after: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insert_before: Option<String>,
+}
+
+protocol_enum! {
+    #[doc = "Action of a firewall rule."]
+    enum FirewallRuleAction {
+        Allow = "allow",
+        Deny = "deny",
+        Reject = "reject"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum FirewallRuleSortKey {
+        Action = "action",
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// A firewall rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallRule {
+    pub action: FirewallRuleAction,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_ip_address: Option<ipnet::IpNet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_port: Option<String>,
+    pub enabled: bool,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub ip_version: IpVersion,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(default)]
+    pub shared: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_ip_address: Option<ipnet::IpNet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_port: Option<String>,
+}
+
+/// A firewall rule update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FirewallRuleUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action: Option<FirewallRuleAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_ip_address: Option<Option<ipnet::IpNet>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_port: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_ip_address: Option<Option<ipnet::IpNet>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_port: Option<Option<String>>,
+}
+
+/// A firewall rule.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FirewallRuleRoot {
+    pub firewall_rule: FirewallRule,
+}
+
+/// A firewall rule update.
+#[derive(Debug, Clone, Serialize)]
+pub struct FirewallRuleUpdateRoot {
+    pub firewall_rule: FirewallRuleUpdate,
+}
+
+/// A list of firewall rules.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FirewallRulesRoot {
+    pub firewall_rules: Vec<FirewallRule>,
+}
+
+protocol_enum! {
+    #[doc = "Possible statuses of VPN services and connections."]
+    enum VpnStatus {
+        Active = "ACTIVE",
+        Building = "BUILD",
+        Down = "DOWN",
+        Error = "ERROR",
+        PendingCreate = "PENDING_CREATE",
+        PendingDelete = "PENDING_DELETE",
+        PendingUpdate = "PENDING_UPDATE"
+    }
+}
+
+/// Lifetime of a security association.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VpnLifetime {
+    /// Units of the lifetime (only `seconds` is supported).
+    pub units: String,
+    /// Value of the lifetime.
+    pub value: u32,
+}
+
+impl VpnLifetime {
+    /// Create a lifetime in seconds.
+    pub fn seconds(value: u32) -> VpnLifetime {
+        VpnLifetime {
+            units: String::from("seconds"),
+            value,
+        }
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum VpnServiceSortKey {
+        Id = "id",
+        Name = "name",
+        Status = "status"
+    }
+}
+
+/// A VPN service.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VpnService {
+    pub admin_state_up: bool,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(deserialize_with = "empty_as_default", default, skip_serializing)]
+    pub external_v4_ip: Option<net::Ipv4Addr>,
+    #[serde(deserialize_with = "empty_as_default", default, skip_serializing)]
+    pub external_v6_ip: Option<net::Ipv6Addr>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub flavor_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub router_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subnet_id: Option<String>,
+    #[serde(skip_serializing)]
+    pub status: VpnStatus,
+}
+
+/// A VPN service update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VpnServiceUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A VPN service.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VpnServiceRoot {
+    pub vpnservice: VpnService,
+}
+
+/// A VPN service update.
+#[derive(Debug, Clone, Serialize)]
+pub struct VpnServiceUpdateRoot {
+    pub vpnservice: VpnServiceUpdate,
+}
+
+/// A list of VPN services.
+#[derive(Debug, Clone, Deserialize)]
+pub struct VpnServicesRoot {
+    pub vpnservices: Vec<VpnService>,
+}
+
+protocol_enum! {
+    #[doc = "Version of the IKE protocol."]
+    enum IkeVersion {
+        V1 = "v1",
+        V2 = "v2"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum IkePolicySortKey {
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// An IKE policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IkePolicy {
+    pub auth_algorithm: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    pub encryption_algorithm: String,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub ike_version: IkeVersion,
+    pub lifetime: VpnLifetime,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    pub pfs: String,
+    pub phase1_negotiation_mode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+}
+
+/// An IKE policy update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IkePolicyUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ike_version: Option<IkeVersion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifetime: Option<VpnLifetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pfs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase1_negotiation_mode: Option<String>,
+}
+
+/// An IKE policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IkePolicyRoot {
+    pub ikepolicy: IkePolicy,
+}
+
+/// An IKE policy update.
+#[derive(Debug, Clone, Serialize)]
+pub struct IkePolicyUpdateRoot {
+    pub ikepolicy: IkePolicyUpdate,
+}
+
+/// A list of IKE policies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IkePoliciesRoot {
+    pub ikepolicies: Vec<IkePolicy>,
+}
+
+protocol_enum! {
+    #[doc = "Encapsulation mode of an IPsec policy."]
+    enum IpsecEncapsulationMode {
+        Transport = "transport",
+        Tunnel = "tunnel"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Transform protocol of an IPsec policy."]
+    enum IpsecTransformProtocol {
+        Ah = "ah",
+        AhEsp = "ah-esp",
+        Esp = "esp"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum IpsecPolicySortKey {
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// An IPsec policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IpsecPolicy {
+    pub auth_algorithm: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    pub encapsulation_mode: IpsecEncapsulationMode,
+    pub encryption_algorithm: String,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub lifetime: VpnLifetime,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    pub pfs: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub transform_protocol: IpsecTransformProtocol,
+}
+
+/// An IPsec policy update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IpsecPolicyUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encapsulation_mode: Option<IpsecEncapsulationMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption_algorithm: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifetime: Option<VpnLifetime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pfs: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transform_protocol: Option<IpsecTransformProtocol>,
+}
+
+/// An IPsec policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IpsecPolicyRoot {
+    pub ipsecpolicy: IpsecPolicy,
+}
+
+/// An IPsec policy update.
+#[derive(Debug, Clone, Serialize)]
+pub struct IpsecPolicyUpdateRoot {
+    pub ipsecpolicy: IpsecPolicyUpdate,
+}
+
+/// A list of IPsec policies.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpsecPoliciesRoot {
+    pub ipsecpolicies: Vec<IpsecPolicy>,
+}
+
+protocol_enum! {
+    #[doc = "Type of the endpoints in an endpoint group."]
+    enum EndpointGroupType {
+        Cidr = "cidr",
+        Subnet = "subnet"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum EndpointGroupSortKey {
+        Id = "id",
+        Name = "name"
+    }
+}
+
+/// A VPN endpoint group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EndpointGroup {
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    pub endpoints: Vec<String>,
+    #[serde(rename = "type")]
+    pub endpoint_type: EndpointGroupType,
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+}
+
+/// A VPN endpoint group update.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EndpointGroupUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A VPN endpoint group.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EndpointGroupRoot {
+    pub endpoint_group: EndpointGroup,
+}
+
+/// A VPN endpoint group update.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointGroupUpdateRoot {
+    pub endpoint_group: EndpointGroupUpdate,
+}
+
+/// A list of VPN endpoint groups.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EndpointGroupsRoot {
+    pub endpoint_groups: Vec<EndpointGroup>,
+}
+
+protocol_enum! {
+    #[doc = "Action taken when a dead peer is detected."]
+    enum DeadPeerDetectionAction {
+        Clear = "clear",
+        Disabled = "disabled",
+        Hold = "hold",
+        Restart = "restart",
+        RestartByPeer = "restart-by-peer"
+    }
+}
+
+/// Dead peer detection settings of an IPsec site connection.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct DeadPeerDetection {
+    /// Action taken when a dead peer is detected.
+    pub action: DeadPeerDetectionAction,
+    /// Interval between checks in seconds.
+    pub interval: u32,
+    /// Time after which the peer is declared dead in seconds.
+    pub timeout: u32,
+}
+
+protocol_enum! {
+    #[doc = "Whether an IPsec site connection initiates the negotiation."]
+    enum IpsecInitiator {
+        BiDirectional = "bi-directional",
+        ResponseOnly = "response-only"
+    }
+}
+
+protocol_enum! {
+    #[doc = "Available sort keys."]
+    enum IpsecSiteConnectionSortKey {
+        Id = "id",
+        Name = "name",
+        Status = "status"
+    }
+}
+
+/// An IPsec site connection.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct IpsecSiteConnection {
+    pub admin_state_up: bool,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dpd: Option<DeadPeerDetection>,
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub ikepolicy_id: String,
+    pub initiator: IpsecInitiator,
+    pub ipsecpolicy_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_ep_group_id: Option<String>,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub local_id: Option<String>,
+    pub mtu: u32,
+    #[serde(
+        deserialize_with = "empty_as_default",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    pub peer_address: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub peer_cidrs: Vec<ipnet::IpNet>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer_ep_group_id: Option<String>,
+    pub peer_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    pub psk: String,
+    #[serde(skip_serializing)]
+    pub status: VpnStatus,
+    pub vpnservice_id: String,
+}
+
+impl fmt::Debug for IpsecSiteConnection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The pre-shared key must not end up in the logs.
+        f.debug_struct("IpsecSiteConnection")
+            .field("admin_state_up", &self.admin_state_up)
+            .field("description", &self.description)
+            .field("dpd", &self.dpd)
+            .field("id", &self.id)
+            .field("ikepolicy_id", &self.ikepolicy_id)
+            .field("initiator", &self.initiator)
+            .field("ipsecpolicy_id", &self.ipsecpolicy_id)
+            .field("local_ep_group_id", &self.local_ep_group_id)
+            .field("local_id", &self.local_id)
+            .field("mtu", &self.mtu)
+            .field("name", &self.name)
+            .field("peer_address", &self.peer_address)
+            .field("peer_cidrs", &self.peer_cidrs)
+            .field("peer_ep_group_id", &self.peer_ep_group_id)
+            .field("peer_id", &self.peer_id)
+            .field("project_id", &self.project_id)
+            .field("psk", &"***")
+            .field("status", &self.status)
+            .field("vpnservice_id", &self.vpnservice_id)
+            .finish()
+    }
+}
+
+/// An IPsec site connection update.
+#[derive(Clone, Default, Serialize)]
+pub struct IpsecSiteConnectionUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_state_up: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dpd: Option<DeadPeerDetection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initiator: Option<IpsecInitiator>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psk: Option<String>,
+}
+
+impl fmt::Debug for IpsecSiteConnectionUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The pre-shared key must not end up in the logs.
+        f.debug_struct("IpsecSiteConnectionUpdate")
+            .field("admin_state_up", &self.admin_state_up)
+            .field("description", &self.description)
+            .field("dpd", &self.dpd)
+            .field("initiator", &self.initiator)
+            .field("mtu", &self.mtu)
+            .field("name", &self.name)
+            .field("peer_address", &self.peer_address)
+            .field("peer_id", &self.peer_id)
+            .field("psk", &self.psk.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+/// An IPsec site connection.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IpsecSiteConnectionRoot {
+    pub ipsec_site_connection: IpsecSiteConnection,
+}
+
+/// An IPsec site connection update.
+#[derive(Debug, Clone, Serialize)]
+pub struct IpsecSiteConnectionUpdateRoot {
+    pub ipsec_site_connection: IpsecSiteConnectionUpdate,
+}
+
+/// A list of IPsec site connections.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpsecSiteConnectionsRoot {
+    pub ipsec_site_connections: Vec<IpsecSiteConnection>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::NetworkIpAvailability;
+
+    #[test]
+    fn test_ip_count_precision() {
+        let availability: NetworkIpAvailability = serde_json::from_str(
+            r#"{
+                "network_id": "net-id",
+                "total_ips": 18446744073709551621,
+                "used_ips": 3
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(availability.total_ips, 18_446_744_073_709_551_621);
+        assert_eq!(availability.used_ips, 3);
+    }
+}
@@ -0,0 +1,452 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! QoS policies management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, QosPolicyRef, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol};
+
+/// A query to QoS policy list.
+#[derive(Clone, Debug)]
+pub struct QosPolicyQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single QoS policy.
+#[derive(Clone, Debug)]
+pub struct QosPolicy {
+    session: Rc<Session>,
+    inner: protocol::QosPolicy,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a QoS policy.
+#[derive(Clone, Debug)]
+pub struct NewQosPolicy {
+    session: Rc<Session>,
+    inner: protocol::QosPolicy,
+}
+
+fn unsupported_rule() -> Error {
+    Error::new(ErrorKind::InvalidInput, "Unsupported QoS rule type")
+}
+
+impl QosPolicy {
+    /// Create a QoS policy object.
+    fn new(session: Rc<Session>, inner: protocol::QosPolicy) -> QosPolicy {
+        QosPolicy {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a QosPolicy object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<QosPolicy> {
+        let inner = api::get_qos_policy(&session, id)?;
+        Ok(QosPolicy::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "QoS policy description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Whether the QoS policy is the default for its project (if available)."]
+        is_default: Option<bool>
+    }
+
+    update_field! {
+        #[doc = "Configure whether the QoS policy is the default for its project."]
+        set_default, with_default -> is_default: optional bool
+    }
+
+    transparent_property! {
+        #[doc = "QoS policy name."]
+        name: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the QoS policy (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Rules of the QoS policy."]
+        rules: ref Vec<protocol::QosRule>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the QoS policy is shared with other projects."]
+        shared: bool
+    }
+
+    update_field! {
+        #[doc = "Configure whether the QoS policy is shared with other projects."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Add a rule to the QoS policy.
+    ///
+    /// Returns the created rule with its ID populated.
+    pub fn add_rule<R>(&mut self, rule: R) -> Result<protocol::QosRule>
+    where
+        R: Into<protocol::QosRule>,
+    {
+        let id = &self.inner.id;
+        let result = match rule.into() {
+            protocol::QosRule::BandwidthLimit(rule) => protocol::QosRule::BandwidthLimit(
+                api::create_qos_rule(&self.session, id, "bandwidth_limit", rule)?,
+            ),
+            protocol::QosRule::DscpMarking(rule) => protocol::QosRule::DscpMarking(
+                api::create_qos_rule(&self.session, id, "dscp_marking", rule)?,
+            ),
+            protocol::QosRule::MinimumBandwidth(rule) => protocol::QosRule::MinimumBandwidth(
+                api::create_qos_rule(&self.session, id, "minimum_bandwidth", rule)?,
+            ),
+            protocol::QosRule::__Nonexhaustive => return Err(unsupported_rule()),
+        };
+        self.refresh_rules()?;
+        Ok(result)
+    }
+
+    /// Update a rule of the QoS policy.
+    ///
+    /// The rule is identified by its `id`, all other fields are updated
+    /// from the provided value.
+    pub fn update_rule<R>(&mut self, rule: R) -> Result<protocol::QosRule>
+    where
+        R: Into<protocol::QosRule>,
+    {
+        let rule = rule.into();
+        if rule.id().is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "QoS rule ID is required for updating",
+            ));
+        }
+
+        let id = &self.inner.id;
+        let result = match rule {
+            protocol::QosRule::BandwidthLimit(rule) => protocol::QosRule::BandwidthLimit(
+                api::update_qos_rule(&self.session, id, "bandwidth_limit", rule.id.clone(), rule)?,
+            ),
+            protocol::QosRule::DscpMarking(rule) => protocol::QosRule::DscpMarking(
+                api::update_qos_rule(&self.session, id, "dscp_marking", rule.id.clone(), rule)?,
+            ),
+            protocol::QosRule::MinimumBandwidth(rule) => {
+                protocol::QosRule::MinimumBandwidth(api::update_qos_rule(
+                    &self.session,
+                    id,
+                    "minimum_bandwidth",
+                    rule.id.clone(),
+                    rule,
+                )?)
+            }
+            protocol::QosRule::__Nonexhaustive => return Err(unsupported_rule()),
+        };
+        self.refresh_rules()?;
+        Ok(result)
+    }
+
+    /// Remove a rule from the QoS policy.
+    pub fn remove_rule(&mut self, rule: &protocol::QosRule) -> Result<()> {
+        let kind = match *rule {
+            protocol::QosRule::BandwidthLimit(..) => "bandwidth_limit",
+            protocol::QosRule::DscpMarking(..) => "dscp_marking",
+            protocol::QosRule::MinimumBandwidth(..) => "minimum_bandwidth",
+            protocol::QosRule::__Nonexhaustive => return Err(unsupported_rule()),
+        };
+        api::delete_qos_rule(&self.session, &self.inner.id, kind, rule.id())?;
+        self.refresh_rules()
+    }
+
+    /// Delete the QoS policy.
+    pub fn delete(self) -> Result<DeletionWaiter<QosPolicy>> {
+        api::delete_qos_policy(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the QoS policy is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the QoS policy.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::QosPolicyUpdate::default();
+        save_fields! {
+            self -> update: name shared
+        };
+        save_option_fields! {
+            self -> update: description is_default
+        };
+        let inner = api::update_qos_policy(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Refresh only the rules, keeping other local changes.
+    fn refresh_rules(&mut self) -> Result<()> {
+        self.inner.rules = api::get_qos_policy_by_id(&self.session, &self.inner.id)?.rules;
+        Ok(())
+    }
+}
+
+impl Refresh for QosPolicy {
+    /// Refresh the QoS policy.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_qos_policy_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl QosPolicyQuery {
+    pub(crate) fn new(session: Rc<Session>) -> QosPolicyQuery {
+        QosPolicyQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::QosPolicySortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the QoS policy is default."]
+        set_default, with_default -> is_default: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by description."]
+        set_description, with_description -> description
+    }
+
+    query_filter! {
+        #[doc = "Filter by QoS policy name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the QoS policy is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<QosPolicyQuery> {
+        debug!("Fetching QoS policies with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<QosPolicy>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<QosPolicy> {
+        debug!("Fetching one QoS policy with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for QosPolicyQuery {
+    type Item = QosPolicy;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_qos_policies(&self.session, &query)?
+            .into_iter()
+            .map(|item| QosPolicy::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(QosPolicyQuery: ["qos", "policies"] -> "policies", Qos: "QoS");
+
+impl NewQosPolicy {
+    /// Start creating a QoS policy.
+    pub(crate) fn new(session: Rc<Session>, name: String) -> NewQosPolicy {
+        NewQosPolicy {
+            session,
+            inner: protocol::QosPolicy {
+                created_at: None,
+                description: None,
+                id: String::new(),
+                is_default: None,
+                name,
+                project_id: None,
+                rules: Vec::new(),
+                shared: false,
+                updated_at: None,
+            },
+        }
+    }
+
+    /// Request creation of a QoS policy.
+    pub fn create(self) -> Result<QosPolicy> {
+        let inner = api::create_qos_policy(&self.session, self.inner)?;
+        Ok(QosPolicy::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the QoS policy is the default for its project."]
+        set_default, with_default -> is_default: optional bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the QoS policy."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the QoS policy."]
+        set_name, with_name -> name
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the QoS policy is shared with other projects."]
+        set_shared, with_shared -> shared: bool
+    }
+}
+
+impl IntoFallibleIterator for QosPolicyQuery {
+    type Item = QosPolicy;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<QosPolicyQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<QosPolicy> for QosPolicyRef {
+    fn from(value: QosPolicy) -> QosPolicyRef {
+        QosPolicyRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for QosPolicyRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<QosPolicyRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            QosPolicyRef::new_verified(api::get_qos_policy(session, &self.value)?.id)
+        })
+    }
+}
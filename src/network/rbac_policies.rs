@@ -0,0 +1,316 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RBAC policies management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, ProjectRef, Refresh, ResourceIterator, ResourceQuery,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol};
+
+/// A query to RBAC policy list.
+#[derive(Clone, Debug)]
+pub struct RbacPolicyQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single RBAC policy.
+#[derive(Clone, Debug)]
+pub struct RbacPolicy {
+    session: Rc<Session>,
+    inner: protocol::RbacPolicy,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create an RBAC policy.
+#[derive(Clone, Debug)]
+pub struct NewRbacPolicy {
+    session: Rc<Session>,
+    inner: protocol::RbacPolicy,
+    target_project: ProjectRef,
+}
+
+impl RbacPolicy {
+    /// Create an RBAC policy object.
+    fn new(session: Rc<Session>, inner: protocol::RbacPolicy) -> RbacPolicy {
+        RbacPolicy {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load an RbacPolicy object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<RbacPolicy> {
+        let inner = api::get_rbac_policy(&session, id)?;
+        Ok(RbacPolicy::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Action granted by the policy."]
+        action: ref protocol::RbacPolicyAction
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the object the policy applies to."]
+        object_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Type of the object the policy applies to."]
+        object_type: ref protocol::RbacPolicyObjectType
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the policy (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project the access is granted to (`*` for all projects)."]
+        target_project_id: ref String
+    }
+
+    /// Update the project the access is granted to.
+    #[allow(unused_results)]
+    pub fn set_target_project<P: Into<ProjectRef>>(&mut self, value: P) {
+        self.inner.target_project_id = value.into().into();
+        self.dirty.insert("target_project_id");
+    }
+
+    /// Update the project the access is granted to.
+    #[inline]
+    pub fn with_target_project<P: Into<ProjectRef>>(mut self, value: P) -> Self {
+        self.set_target_project(value);
+        self
+    }
+
+    /// Delete the RBAC policy.
+    pub fn delete(self) -> Result<DeletionWaiter<RbacPolicy>> {
+        api::delete_rbac_policy(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the RBAC policy is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the RBAC policy.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::RbacPolicyUpdate::default();
+        save_fields! {
+            self -> update: target_project_id
+        };
+        let inner = api::update_rbac_policy(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for RbacPolicy {
+    /// Refresh the RBAC policy.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_rbac_policy(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl RbacPolicyQuery {
+    pub(crate) fn new(session: Rc<Session>) -> RbacPolicyQuery {
+        RbacPolicyQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::RbacPolicySortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by action."]
+        set_action, with_action -> action: protocol::RbacPolicyAction
+    }
+
+    query_filter! {
+        #[doc = "Filter by object ID."]
+        set_object_id, with_object_id -> object_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by object type."]
+        set_object_type, with_object_type -> object_type: protocol::RbacPolicyObjectType
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by the project the access is granted to."]
+        set_target_project, with_target_project -> target_tenant: ProjectRef
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<RbacPolicyQuery> {
+        debug!("Fetching RBAC policies with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<RbacPolicy>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<RbacPolicy> {
+        debug!("Fetching one RBAC policy with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for RbacPolicyQuery {
+    type Item = RbacPolicy;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_rbac_policies(&self.session, &query)?
+            .into_iter()
+            .map(|item| RbacPolicy::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(RbacPolicyQuery: ["rbac-policies"] -> "rbac_policies", Rbac: "RBAC policies");
+
+impl NewRbacPolicy {
+    /// Start creating an RBAC policy.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        object_type: protocol::RbacPolicyObjectType,
+        object_id: String,
+        action: protocol::RbacPolicyAction,
+        target_project: ProjectRef,
+    ) -> NewRbacPolicy {
+        NewRbacPolicy {
+            session,
+            inner: protocol::RbacPolicy {
+                action,
+                id: String::new(),
+                object_id,
+                object_type,
+                project_id: None,
+                // Will be replaced in create()
+                target_project_id: String::new(),
+            },
+            target_project,
+        }
+    }
+
+    /// Request creation of the RBAC policy.
+    pub fn create(mut self) -> Result<RbacPolicy> {
+        self.inner.target_project_id = self.target_project.into_verified(&self.session)?.into();
+        let inner = api::create_rbac_policy(&self.session, self.inner)?;
+        Ok(RbacPolicy::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the project owning the policy (requires administrative privileges)."]
+        set_project_id, with_project_id -> project_id: optional String
+    }
+}
+
+impl IntoFallibleIterator for RbacPolicyQuery {
+    type Item = RbacPolicy;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<RbacPolicyQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
@@ -0,0 +1,811 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Router management via Network API.
+
+use std::collections::HashSet;
+use std::mem;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use serde_json;
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, NetworkRef, PortRef, Refresh, ResourceIterator, ResourceQuery,
+    RouterRef, SubnetRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, Network, Port, PortIpRequest, Subnet};
+
+/// A query to router list.
+#[derive(Clone, Debug)]
+pub struct RouterQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    l3_agent: Option<String>,
+}
+
+/// Structure representing a single router.
+#[derive(Clone, Debug)]
+pub struct Router {
+    session: Rc<Session>,
+    inner: protocol::Router,
+    dirty: HashSet<&'static str>,
+}
+
+/// An interface of a router.
+#[derive(Clone, Debug)]
+pub enum RouterInterface {
+    /// Create a new port on the given subnet using its gateway IP.
+    Subnet(SubnetRef),
+    /// Use an existing port.
+    Port(PortRef),
+}
+
+/// A request to create a router.
+#[derive(Clone, Debug)]
+pub struct NewRouter {
+    session: Rc<Session>,
+    inner: protocol::Router,
+    external_network: Option<NetworkRef>,
+    external_fixed_ips: Vec<PortIpRequest>,
+    enable_snat: Option<bool>,
+}
+
+fn external_gateway(
+    session: &Session,
+    network: NetworkRef,
+    enable_snat: Option<bool>,
+    fixed_ips: Vec<PortIpRequest>,
+) -> Result<protocol::ExternalGateway> {
+    let mut external_fixed_ips = Vec::with_capacity(fixed_ips.len());
+    for request in fixed_ips {
+        external_fixed_ips.push(request.into_fixed_ip(session)?);
+    }
+
+    Ok(protocol::ExternalGateway {
+        enable_snat,
+        external_fixed_ips,
+        network_id: network.into_verified(session)?.into(),
+    })
+}
+
+impl Router {
+    /// Create a router object.
+    fn new(session: Rc<Session>, inner: protocol::Router) -> Router {
+        Router {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a Router object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Router> {
+        let inner = api::get_router(&session, id)?;
+        Ok(Router::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "The administrative state of the router."]
+        admin_state_up: bool
+    }
+
+    update_field! {
+        #[doc = "Set the administrative state of the router."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    transparent_property! {
+        #[doc = "Availability zone candidates for the router."]
+        availability_zone_hints: ref Vec<String>
+    }
+
+    transparent_property! {
+        #[doc = "Availability zones for the router (if available)."]
+        availability_zones: ref Vec<String>
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Router description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    /// Remove the description from the router.
+    #[allow(unused_results)]
+    pub fn clear_description(&mut self) {
+        self.inner.description = None;
+        self.dirty.insert("description");
+    }
+
+    transparent_property! {
+        #[doc = "Whether the router is distributed (if available)."]
+        distributed: Option<bool>
+    }
+
+    update_field! {
+        #[doc = "Configure whether the router is distributed (admin-only)."]
+        set_distributed, with_distributed -> distributed: optional bool
+    }
+
+    /// External gateway of the router (if any).
+    #[inline]
+    pub fn external_gateway(&self) -> Option<&protocol::ExternalGateway> {
+        self.inner.external_gateway_info.as_ref()
+    }
+
+    /// Get the external network of the router.
+    ///
+    /// Fails with `ResourceNotFound` if the router has no external gateway.
+    pub fn external_network(&self) -> Result<Network> {
+        match self.inner.external_gateway_info {
+            Some(ref gateway) => Network::load(self.session.clone(), &gateway.network_id),
+            None => Err(Error::new(
+                ErrorKind::ResourceNotFound,
+                "Router has no external gateway",
+            )),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "Flavor of the router (if any)."]
+        flavor_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the router is highly available (if available)."]
+        ha: Option<bool>
+    }
+
+    update_field! {
+        #[doc = "Configure whether the router is highly available (admin-only)."]
+        set_ha, with_ha -> ha: optional bool
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Router name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    /// Remove the name from the router.
+    #[allow(unused_results)]
+    pub fn clear_name(&mut self) {
+        self.inner.name = None;
+        self.dirty.insert("name");
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the router (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Revision number of the router (if available)."]
+        revision_number: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "Static routes of the router."]
+        routes: ref Vec<protocol::HostRoute>
+    }
+
+    update_field! {
+        #[doc = "Replace static routes of the router (requires the `extraroute` extension)."]
+        set_routes, with_routes -> routes: Vec<protocol::HostRoute>
+    }
+
+    transparent_property! {
+        #[doc = "Status of the router."]
+        status: protocol::RouterStatus
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Add an interface to the router.
+    pub fn add_interface<I>(&self, interface: I) -> Result<()>
+    where
+        I: Into<RouterInterface>,
+    {
+        let request = interface.into().into_request(&self.session)?;
+        api::router_interface_action(
+            &self.session,
+            &self.inner.id,
+            "add_router_interface",
+            request,
+        )
+    }
+
+    /// Add static routes to the router.
+    ///
+    /// Unlike `set_routes`, this call takes effect immediately and does not
+    /// override routes added concurrently. Requires the `extraroute-atomic`
+    /// extension.
+    pub fn add_routes<I>(&mut self, routes: I) -> Result<()>
+    where
+        I: IntoIterator<Item = protocol::HostRoute>,
+    {
+        let routes = routes.into_iter().collect();
+        let inner =
+            api::router_routes_action(&self.session, &self.inner.id, "add_extraroutes", routes)?;
+        self.update_routes(inner);
+        Ok(())
+    }
+
+    /// Remove the external gateway of the router.
+    pub fn clear_external_gateway(&mut self) -> Result<()> {
+        let value = serde_json::Value::Object(Default::default());
+        self.update_gateway(value)
+    }
+
+    /// Delete the router.
+    pub fn delete(self) -> Result<DeletionWaiter<Router>> {
+        api::delete_router(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the router is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Remove an interface from the router.
+    pub fn remove_interface<I>(&self, interface: I) -> Result<()>
+    where
+        I: Into<RouterInterface>,
+    {
+        let request = interface.into().into_request(&self.session)?;
+        api::router_interface_action(
+            &self.session,
+            &self.inner.id,
+            "remove_router_interface",
+            request,
+        )
+    }
+
+    /// Remove static routes from the router.
+    ///
+    /// Unlike `set_routes`, this call takes effect immediately and does not
+    /// override routes added concurrently. Requires the `extraroute-atomic`
+    /// extension.
+    pub fn remove_routes<I>(&mut self, routes: I) -> Result<()>
+    where
+        I: IntoIterator<Item = protocol::HostRoute>,
+    {
+        let routes = routes.into_iter().collect();
+        let inner =
+            api::router_routes_action(&self.session, &self.inner.id, "remove_extraroutes", routes)?;
+        self.update_routes(inner);
+        Ok(())
+    }
+
+    /// Save the changes to the router.
+    pub fn save(&mut self) -> Result<()> {
+        self.save_revision(None)
+    }
+
+    fn save_revision(&mut self, revision: Option<u32>) -> Result<()> {
+        let mut update = protocol::RouterUpdate::default();
+        save_fields! {
+            self -> update: admin_state_up routes
+        };
+        save_option_fields! {
+            self -> update: description distributed ha name
+        };
+        // Neutron does not accept null, an empty string clears these fields.
+        if self.dirty.contains("description") && update.description.is_none() {
+            update.description = Some(String::new());
+        }
+        if self.dirty.contains("name") && update.name.is_none() {
+            update.name = Some(String::new());
+        }
+        let inner = api::update_router(&self.session, self.id(), update, revision)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Set the external gateway of the router.
+    ///
+    /// Optionally enable or disable source NAT and request specific IP
+    /// addresses on the external network.
+    pub fn set_external_gateway<N>(
+        &mut self,
+        network: N,
+        enable_snat: Option<bool>,
+        fixed_ips: Vec<PortIpRequest>,
+    ) -> Result<()>
+    where
+        N: Into<NetworkRef>,
+    {
+        let gateway = external_gateway(&self.session, network.into(), enable_snat, fixed_ips)?;
+        let value = serde_json::to_value(gateway).map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Cannot serialize the external gateway: {}", err),
+            )
+        })?;
+        self.update_gateway(value)
+    }
+
+    fn update_gateway(&mut self, value: serde_json::Value) -> Result<()> {
+        let update = protocol::RouterUpdate {
+            external_gateway_info: Some(value),
+            ..Default::default()
+        };
+        let inner = api::update_router(&self.session, self.id(), update, None)?;
+        self.merge(inner);
+        Ok(())
+    }
+
+    /// Apply the result of a routes call, keeping other local changes.
+    fn update_routes(&mut self, inner: protocol::Router) {
+        let _ = self.dirty.remove("routes");
+        self.merge(inner);
+    }
+
+    /// Apply a router received from the server, keeping local changes.
+    fn merge(&mut self, mut inner: protocol::Router) {
+        if self.dirty.contains("admin_state_up") {
+            inner.admin_state_up = self.inner.admin_state_up;
+        }
+        if self.dirty.contains("description") {
+            inner.description = self.inner.description.take();
+        }
+        if self.dirty.contains("distributed") {
+            inner.distributed = self.inner.distributed;
+        }
+        if self.dirty.contains("ha") {
+            inner.ha = self.inner.ha;
+        }
+        if self.dirty.contains("name") {
+            inner.name = self.inner.name.take();
+        }
+        if self.dirty.contains("routes") {
+            inner.routes = mem::take(&mut self.inner.routes);
+        }
+        self.inner = inner;
+    }
+}
+
+taggable_resource!(Router -> "routers");
+
+revisioned_resource!(Router -> "router");
+
+impl Refresh for Router {
+    /// Refresh the router.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_router_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl RouterInterface {
+    fn into_request(self, session: &Session) -> Result<protocol::RouterInterfaceRequest> {
+        Ok(match self {
+            RouterInterface::Subnet(subnet) => protocol::RouterInterfaceRequest {
+                subnet_id: Some(subnet.into_verified(session)?.into()),
+                port_id: None,
+            },
+            RouterInterface::Port(port) => protocol::RouterInterfaceRequest {
+                subnet_id: None,
+                port_id: Some(port.into_verified(session)?.into()),
+            },
+        })
+    }
+}
+
+impl From<SubnetRef> for RouterInterface {
+    fn from(value: SubnetRef) -> RouterInterface {
+        RouterInterface::Subnet(value)
+    }
+}
+
+impl From<Subnet> for RouterInterface {
+    fn from(value: Subnet) -> RouterInterface {
+        RouterInterface::Subnet(value.into())
+    }
+}
+
+impl From<PortRef> for RouterInterface {
+    fn from(value: PortRef) -> RouterInterface {
+        RouterInterface::Port(value)
+    }
+}
+
+impl From<Port> for RouterInterface {
+    fn from(value: Port) -> RouterInterface {
+        RouterInterface::Port(value.into())
+    }
+}
+
+impl RouterQuery {
+    pub(crate) fn new(session: Rc<Session>) -> RouterQuery {
+        RouterQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            l3_agent: None,
+        }
+    }
+
+    /// Only list routers hosted by the given L3 agent.
+    pub(crate) fn with_l3_agent(mut self, agent_id: String) -> Self {
+        self.l3_agent = Some(agent_id);
+        self
+    }
+
+    /// Path to list routers at.
+    fn list_path(&self) -> Vec<&str> {
+        match self.l3_agent {
+            Some(ref agent_id) => vec!["agents", agent_id, "l3-routers"],
+            None => vec!["routers"],
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::RouterSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by description."]
+        set_description, with_description -> description
+    }
+
+    query_filter! {
+        #[doc = "Filter by router name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by status."]
+        set_status, with_status -> status: protocol::RouterStatus
+    }
+
+    tag_query_filters!();
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<RouterQuery> {
+        debug!("Fetching routers with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Router>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<Router> {
+        debug!("Fetching one router with {:?}", self.query);
+        if self.can_paginate()? {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for RouterQuery {
+    type Item = Router;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        // Agent scheduler API does not support pagination.
+        Ok(self.can_paginate && self.l3_agent.is_none())
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        let items = match self.l3_agent {
+            Some(ref agent_id) => api::list_l3_agent_routers(&self.session, agent_id, &query)?,
+            None => api::list_routers(&self.session, &query)?,
+        };
+        Ok(items
+            .into_iter()
+            .map(|item| Router::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(RouterQuery: list_path() -> "routers", Router: "Routers");
+
+impl NewRouter {
+    /// Start creating a router.
+    pub(crate) fn new(session: Rc<Session>) -> NewRouter {
+        NewRouter {
+            session,
+            inner: protocol::Router::default(),
+            external_network: None,
+            external_fixed_ips: Vec::new(),
+            enable_snat: None,
+        }
+    }
+
+    /// Request creation of a router.
+    pub fn create(mut self) -> Result<Router> {
+        if let Some(network) = self.external_network {
+            self.inner.external_gateway_info = Some(external_gateway(
+                &self.session,
+                network,
+                self.enable_snat,
+                self.external_fixed_ips,
+            )?);
+        } else if self.enable_snat.is_some() || !self.external_fixed_ips.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "SNAT and external fixed IPs require an external network",
+            ));
+        }
+
+        let inner = api::create_router(&self.session, self.inner)?;
+        Ok(Router::new(self.session, inner))
+    }
+
+    /// Add a requested IP address on the external network.
+    ///
+    /// Requires an external network to be set.
+    pub fn add_external_fixed_ip(&mut self, request: PortIpRequest) {
+        self.external_fixed_ips.push(request);
+    }
+
+    /// Add a requested IP address on the external network.
+    ///
+    /// Requires an external network to be set.
+    pub fn with_external_fixed_ip(mut self, request: PortIpRequest) -> Self {
+        self.add_external_fixed_ip(request);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set administrative status for the router."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    creation_inner_vec! {
+        #[doc = "Add an availability zone candidate for the router."]
+        add_availability_zone_hint, with_availability_zone_hint -> availability_zone_hints
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the router."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the router is distributed (admin-only)."]
+        set_distributed, with_distributed -> distributed: optional bool
+    }
+
+    /// Configure whether source NAT is enabled on the external gateway.
+    ///
+    /// Requires an external network to be set.
+    pub fn set_enable_snat(&mut self, value: bool) {
+        self.enable_snat = Some(value);
+    }
+
+    /// Configure whether source NAT is enabled on the external gateway.
+    ///
+    /// Requires an external network to be set.
+    pub fn with_enable_snat(mut self, value: bool) -> Self {
+        self.set_enable_snat(value);
+        self
+    }
+
+    /// Set the external network to use as a gateway.
+    pub fn set_external_network<N: Into<NetworkRef>>(&mut self, value: N) {
+        self.external_network = Some(value.into());
+    }
+
+    /// Set the external network to use as a gateway.
+    pub fn with_external_network<N: Into<NetworkRef>>(mut self, value: N) -> Self {
+        self.set_external_network(value);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the router is highly available (admin-only)."]
+        set_ha, with_ha -> ha: optional bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the router."]
+        set_name, with_name -> name: optional String
+    }
+}
+
+impl IntoFallibleIterator for RouterQuery {
+    type Item = Router;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<RouterQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<Router> for RouterRef {
+    fn from(value: Router) -> RouterRef {
+        RouterRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for RouterRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<RouterRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            RouterRef::new_verified(api::get_router(session, &self.value)?.id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::utils::Session;
+    use super::super::api::test::mock_network_session;
+    use super::super::protocol::HostRoute;
+    use super::Router;
+
+    const EXTENSIONS: &[&str] = &["router", "extraroute", "extraroute-atomic"];
+
+    fn route(destination: &str, next_hop: &str) -> HostRoute {
+        HostRoute {
+            destination: destination.parse().unwrap(),
+            next_hop: next_hop.parse().unwrap(),
+        }
+    }
+
+    fn router_json(routes: Value) -> Value {
+        json!({
+            "admin_state_up": true,
+            "description": "old description",
+            "id": "router-id",
+            "name": "old",
+            "routes": routes,
+            "status": "ACTIVE",
+        })
+    }
+
+    fn router(session: Session) -> Router {
+        let inner = serde_json::from_value(router_json(json!([]))).unwrap();
+        Router::new(Rc::new(session), inner)
+    }
+
+    #[test]
+    fn test_update_routes_keeps_changes() {
+        let (session, _) = mock_network_session(EXTENSIONS, |_| {
+            let routes = json!([{"destination": "10.1.0.0/24", "nexthop": "10.0.0.2"}]);
+            (200, json!({ "router": router_json(routes) }))
+        });
+        let mut router = router(session);
+        router.set_name("new");
+        router.set_routes(vec![route("10.2.0.0/24", "10.0.0.3")]);
+        router
+            .add_routes(vec![route("10.1.0.0/24", "10.0.0.2")])
+            .unwrap();
+
+        assert_eq!(router.name().as_ref().unwrap(), "new");
+        assert_eq!(router.description().as_ref().unwrap(), "old description");
+        assert_eq!(router.routes().len(), 1);
+        assert_eq!(router.routes()[0].destination.to_string(), "10.1.0.0/24");
+        assert!(router.is_dirty());
+        assert!(router.dirty.contains("name"));
+        assert!(!router.dirty.contains("routes"));
+    }
+
+    #[test]
+    fn test_update_gateway_keeps_changes() {
+        let (session, requests) = mock_network_session(EXTENSIONS, |_| {
+            (200, json!({ "router": router_json(json!([])) }))
+        });
+        let mut router = router(session);
+        router.set_name("new");
+        router.set_routes(vec![route("10.2.0.0/24", "10.0.0.3")]);
+        router.clear_external_gateway().unwrap();
+
+        assert_eq!(router.name().as_ref().unwrap(), "new");
+        assert_eq!(router.routes().len(), 1);
+        assert!(router.dirty.contains("name"));
+        assert!(router.dirty.contains("routes"));
+
+        let requests = requests.lock().unwrap();
+        let update = requests.iter().find(|r| r.method == "PUT").unwrap();
+        let body: Value = serde_json::from_str(&update.body).unwrap();
+        assert_eq!(body, json!({ "router": { "external_gateway_info": {} } }));
+    }
+}
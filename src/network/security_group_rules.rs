@@ -0,0 +1,517 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Security group rules management via Network API.
+
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use ipnet;
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, Refresh, ResourceIterator, ResourceQuery, SecurityGroupRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result};
+use super::{api, protocol, SecurityGroup};
+
+/// Protocol names and numbers of ICMP and ICMPv6.
+const ICMP_PROTOCOLS: &[&str] = &["icmp", "icmpv6", "ipv6-icmp", "1", "58"];
+
+/// A query to security group rule list.
+#[derive(Clone, Debug)]
+pub struct SecurityGroupRuleQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    security_group: Option<SecurityGroupRef>,
+    remote_group: Option<SecurityGroupRef>,
+}
+
+/// Structure representing a single security group rule.
+#[derive(Clone, Debug)]
+pub struct SecurityGroupRule {
+    session: Rc<Session>,
+    inner: protocol::SecurityGroupRule,
+}
+
+/// A request to create a security group rule.
+#[derive(Clone, Debug)]
+pub struct NewSecurityGroupRule {
+    session: Rc<Session>,
+    inner: protocol::SecurityGroupRule,
+    security_group: SecurityGroupRef,
+    ethertype: Option<protocol::EtherType>,
+    remote_group: Option<SecurityGroupRef>,
+}
+
+impl SecurityGroupRule {
+    /// Create a security group rule object.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        inner: protocol::SecurityGroupRule,
+    ) -> SecurityGroupRule {
+        SecurityGroupRule { session, inner }
+    }
+
+    /// Load a SecurityGroupRule object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<SecurityGroupRule> {
+        let inner = api::get_security_group_rule(&session, id)?;
+        Ok(SecurityGroupRule::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Rule description."]
+        description: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Direction of the traffic the rule applies to."]
+        direction: protocol::SecurityGroupRuleDirection
+    }
+
+    transparent_property! {
+        #[doc = "Ethernet type of the traffic the rule applies to."]
+        ethertype: protocol::EtherType
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Maximum port number in the range (if any)."]
+        port_range_max: Option<u16>
+    }
+
+    transparent_property! {
+        #[doc = "Minimum port number in the range (if any)."]
+        port_range_min: Option<u16>
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the rule (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "IP protocol name or number (if any)."]
+        protocol: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "ID of the remote security group (if any)."]
+        remote_group_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Remote IP prefix (if any)."]
+        remote_ip_prefix: Option<ipnet::IpNet>
+    }
+
+    /// Get the security group this rule belongs to.
+    pub fn security_group(&self) -> Result<SecurityGroup> {
+        SecurityGroup::load(self.session.clone(), &self.inner.security_group_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the security group this rule belongs to."]
+        security_group_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Delete the security group rule.
+    pub fn delete(self) -> Result<DeletionWaiter<SecurityGroupRule>> {
+        api::delete_security_group_rule(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+}
+
+impl Refresh for SecurityGroupRule {
+    /// Refresh the security group rule.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_security_group_rule(&self.session, &self.inner.id)?;
+        Ok(())
+    }
+}
+
+impl SecurityGroupRuleQuery {
+    pub(crate) fn new(session: Rc<Session>) -> SecurityGroupRuleQuery {
+        SecurityGroupRuleQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            security_group: None,
+            remote_group: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by direction."]
+        set_direction, with_direction -> direction: protocol::SecurityGroupRuleDirection
+    }
+
+    query_filter! {
+        #[doc = "Filter by ethernet type."]
+        set_ethertype, with_ethertype -> ethertype: protocol::EtherType
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by IP protocol."]
+        set_protocol, with_protocol -> protocol
+    }
+
+    /// Filter by remote security group.
+    pub fn set_remote_group<G: Into<SecurityGroupRef>>(&mut self, value: G) {
+        self.remote_group = Some(value.into());
+    }
+
+    /// Filter by remote security group.
+    pub fn with_remote_group<G: Into<SecurityGroupRef>>(mut self, value: G) -> Self {
+        self.set_remote_group(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by remote IP prefix."]
+        set_remote_ip_prefix, with_remote_ip_prefix -> remote_ip_prefix: ipnet::IpNet
+    }
+
+    /// Filter by security group.
+    pub fn set_security_group<G: Into<SecurityGroupRef>>(&mut self, value: G) {
+        self.security_group = Some(value.into());
+    }
+
+    /// Filter by security group.
+    pub fn with_security_group<G: Into<SecurityGroupRef>>(mut self, value: G) -> Self {
+        self.set_security_group(value);
+        self
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<SecurityGroupRuleQuery> {
+        debug!("Fetching security group rules with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<SecurityGroupRule>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<SecurityGroupRule> {
+        debug!("Fetching one security group rule with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for SecurityGroupRuleQuery {
+    type Item = SecurityGroupRule;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_security_group_rules(&self.session, &query)?
+            .into_iter()
+            .map(|item| SecurityGroupRule::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(security_group) = self.security_group.take() {
+            let verified = security_group.into_verified(&self.session)?;
+            self.query.push_str("security_group_id", verified);
+        }
+        if let Some(remote_group) = self.remote_group.take() {
+            let verified = remote_group.into_verified(&self.session)?;
+            self.query.push_str("remote_group_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(SecurityGroupRuleQuery: ["security-group-rules"] -> "security_group_rules");
+
+impl NewSecurityGroupRule {
+    /// Start creating a security group rule.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        security_group: SecurityGroupRef,
+        direction: protocol::SecurityGroupRuleDirection,
+    ) -> NewSecurityGroupRule {
+        NewSecurityGroupRule {
+            session,
+            inner: protocol::SecurityGroupRule {
+                created_at: None,
+                description: None,
+                direction,
+                // Will be replaced in create()
+                ethertype: protocol::EtherType::Ipv4,
+                id: String::new(),
+                port_range_max: None,
+                port_range_min: None,
+                project_id: None,
+                protocol: None,
+                remote_group_id: None,
+                remote_ip_prefix: None,
+                // Will be replaced in create()
+                security_group_id: String::new(),
+                updated_at: None,
+            },
+            security_group,
+            ethertype: None,
+            remote_group: None,
+        }
+    }
+
+    /// Request creation of a security group rule.
+    pub fn create(mut self) -> Result<SecurityGroupRule> {
+        if self.remote_group.is_some() && self.inner.remote_ip_prefix.is_some() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "Remote group and remote IP prefix are mutually exclusive",
+            ));
+        }
+
+        // For ICMP the range is a type and a code, which are not ordered.
+        let is_icmp = match self.inner.protocol {
+            Some(ref protocol) => ICMP_PROTOCOLS.contains(&protocol.to_lowercase().as_str()),
+            None => false,
+        };
+        if let (Some(min), Some(max)) = (self.inner.port_range_min, self.inner.port_range_max) {
+            if min > max && !is_icmp {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Invalid port range {}-{}", min, max),
+                ));
+            }
+        }
+
+        self.inner.ethertype = match (self.ethertype, self.inner.remote_ip_prefix) {
+            (Some(ethertype), _) => ethertype,
+            (None, Some(ipnet::IpNet::V6(..))) => protocol::EtherType::Ipv6,
+            (None, _) => protocol::EtherType::Ipv4,
+        };
+        self.inner.security_group_id = self.security_group.into_verified(&self.session)?.into();
+        if let Some(remote_group) = self.remote_group {
+            self.inner.remote_group_id = Some(remote_group.into_verified(&self.session)?.into());
+        }
+
+        let inner = api::create_security_group_rule(&self.session, self.inner)?;
+        Ok(SecurityGroupRule::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the rule."]
+        set_description, with_description -> description: optional String
+    }
+
+    /// Set ethernet type of the rule.
+    ///
+    /// Defaults to the type of the remote IP prefix or IPv4.
+    pub fn set_ethertype(&mut self, value: protocol::EtherType) {
+        self.ethertype = Some(value);
+    }
+
+    /// Set ethernet type of the rule.
+    ///
+    /// Defaults to the type of the remote IP prefix or IPv4.
+    pub fn with_ethertype(mut self, value: protocol::EtherType) -> Self {
+        self.set_ethertype(value);
+        self
+    }
+
+    /// Limit the rule to one port.
+    pub fn set_port(&mut self, value: u16) {
+        self.set_port_range(value, value);
+    }
+
+    /// Limit the rule to one port.
+    pub fn with_port(mut self, value: u16) -> Self {
+        self.set_port(value);
+        self
+    }
+
+    /// Limit the rule to a range of ports (inclusive).
+    ///
+    /// For ICMP these values are the ICMP type and code.
+    pub fn set_port_range(&mut self, min: u16, max: u16) {
+        self.inner.port_range_min = Some(min);
+        self.inner.port_range_max = Some(max);
+    }
+
+    /// Limit the rule to a range of ports (inclusive).
+    ///
+    /// For ICMP these values are the ICMP type and code.
+    pub fn with_port_range(mut self, min: u16, max: u16) -> Self {
+        self.set_port_range(min, max);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Set IP protocol name (e.g. `tcp`) or number."]
+        set_protocol, with_protocol -> protocol: optional String
+    }
+
+    /// Limit the rule to traffic from or to the given security group.
+    pub fn set_remote_group<G: Into<SecurityGroupRef>>(&mut self, value: G) {
+        self.remote_group = Some(value.into());
+    }
+
+    /// Limit the rule to traffic from or to the given security group.
+    pub fn with_remote_group<G: Into<SecurityGroupRef>>(mut self, value: G) -> Self {
+        self.set_remote_group(value);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Limit the rule to traffic from or to the given IP prefix."]
+        set_remote_ip_prefix, with_remote_ip_prefix -> remote_ip_prefix: optional ipnet::IpNet
+    }
+}
+
+impl IntoFallibleIterator for SecurityGroupRuleQuery {
+    type Item = SecurityGroupRule;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<SecurityGroupRuleQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::common::SecurityGroupRef;
+    use super::super::super::ErrorKind;
+    use super::super::api::test::mock_network_session;
+    use super::super::protocol::SecurityGroupRuleDirection;
+    use super::NewSecurityGroupRule;
+
+    fn new_rule(protocol: &str, min: u16, max: u16) -> (NewSecurityGroupRule, impl Fn() -> usize) {
+        let (session, requests) = mock_network_session(&["security-group"], |request| {
+            let mut body: Value = serde_json::from_str(&request.body).unwrap();
+            body["security_group_rule"]["id"] = json!("rule-id");
+            (201, body)
+        });
+        let rule = NewSecurityGroupRule::new(
+            Rc::new(session),
+            SecurityGroupRef::new_verified("sg-id".to_string()),
+            SecurityGroupRuleDirection::Ingress,
+        )
+        .with_protocol(protocol)
+        .with_port_range(min, max);
+        let posts = move || {
+            requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.method == "POST")
+                .count()
+        };
+        (rule, posts)
+    }
+
+    #[test]
+    fn test_icmp_type_and_code() {
+        let (rule, posts) = new_rule("icmp", 8, 0);
+        let rule = rule.create().unwrap();
+        assert_eq!(rule.id(), "rule-id");
+        assert_eq!(rule.port_range_min(), Some(8));
+        assert_eq!(rule.port_range_max(), Some(0));
+        assert_eq!(posts(), 1);
+
+        let (rule, _) = new_rule("ipv6-icmp", 128, 0);
+        let _ = rule.create().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_port_range() {
+        let (rule, posts) = new_rule("tcp", 443, 80);
+        let err = rule.create().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert_eq!(posts(), 0);
+    }
+}
@@ -0,0 +1,359 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Security groups management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, Refresh, ResourceIterator, ResourceQuery, SecurityGroupRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, NewSecurityGroupRule, SecurityGroupRule};
+
+/// A query to security group list.
+#[derive(Clone, Debug)]
+pub struct SecurityGroupQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+}
+
+/// Structure representing a single security group.
+#[derive(Clone, Debug)]
+pub struct SecurityGroup {
+    session: Rc<Session>,
+    inner: protocol::SecurityGroup,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a security group.
+#[derive(Clone, Debug)]
+pub struct NewSecurityGroup {
+    session: Rc<Session>,
+    inner: protocol::SecurityGroup,
+}
+
+impl SecurityGroup {
+    /// Create a security group object.
+    fn new(session: Rc<Session>, inner: protocol::SecurityGroup) -> SecurityGroup {
+        SecurityGroup {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a SecurityGroup object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<SecurityGroup> {
+        let inner = api::get_security_group(&session, id)?;
+        Ok(SecurityGroup::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Security group description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Security group name."]
+        name: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the security group (if available)."]
+        project_id: ref Option<String>
+    }
+
+    /// Rules of this security group.
+    pub fn rules(&self) -> Vec<SecurityGroupRule> {
+        self.inner
+            .security_group_rules
+            .iter()
+            .map(|item| SecurityGroupRule::new(self.session.clone(), item.clone()))
+            .collect()
+    }
+
+    transparent_property! {
+        #[doc = "Whether the security group is stateful (if available)."]
+        stateful: Option<bool>
+    }
+
+    update_field! {
+        #[doc = "Configure whether the security group is stateful."]
+        set_stateful, with_stateful -> stateful: optional bool
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Delete the security group.
+    pub fn delete(self) -> Result<DeletionWaiter<SecurityGroup>> {
+        api::delete_security_group(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the security group is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Prepare a new rule for this security group.
+    ///
+    /// Use `Refresh::refresh` to update `rules` after creating it.
+    pub fn new_rule(
+        &self,
+        direction: protocol::SecurityGroupRuleDirection,
+    ) -> NewSecurityGroupRule {
+        NewSecurityGroupRule::new(
+            self.session.clone(),
+            SecurityGroupRef::new_verified(self.inner.id.clone()),
+            direction,
+        )
+    }
+
+    /// Save the changes to the security group.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::SecurityGroupUpdate::default();
+        save_fields! {
+            self -> update: name
+        };
+        save_option_fields! {
+            self -> update: description stateful
+        };
+        let inner = api::update_security_group(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for SecurityGroup {
+    /// Refresh the security group.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_security_group_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl SecurityGroupQuery {
+    pub(crate) fn new(session: Rc<Session>) -> SecurityGroupQuery {
+        SecurityGroupQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::SecurityGroupSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by description."]
+        set_description, with_description -> description
+    }
+
+    query_filter! {
+        #[doc = "Filter by security group name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<SecurityGroupQuery> {
+        debug!("Fetching security groups with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<SecurityGroup>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<SecurityGroup> {
+        debug!("Fetching one security group with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for SecurityGroupQuery {
+    type Item = SecurityGroup;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_security_groups(&self.session, &query)?
+            .into_iter()
+            .map(|item| SecurityGroup::new(self.session.clone(), item))
+            .collect())
+    }
+}
+
+projectable_query!(SecurityGroupQuery: ["security-groups"] -> "security_groups");
+
+impl NewSecurityGroup {
+    /// Start creating a security group.
+    pub(crate) fn new(session: Rc<Session>, name: String) -> NewSecurityGroup {
+        NewSecurityGroup {
+            session,
+            inner: protocol::SecurityGroup {
+                created_at: None,
+                description: None,
+                id: String::new(),
+                name,
+                project_id: None,
+                security_group_rules: Vec::new(),
+                stateful: None,
+                updated_at: None,
+            },
+        }
+    }
+
+    /// Request creation of a security group.
+    pub fn create(self) -> Result<SecurityGroup> {
+        let inner = api::create_security_group(&self.session, self.inner)?;
+        Ok(SecurityGroup::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the security group."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the security group."]
+        set_name, with_name -> name
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the security group is stateful."]
+        set_stateful, with_stateful -> stateful: optional bool
+    }
+}
+
+impl IntoFallibleIterator for SecurityGroupQuery {
+    type Item = SecurityGroup;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<SecurityGroupQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<SecurityGroup> for SecurityGroupRef {
+    fn from(value: SecurityGroup) -> SecurityGroupRef {
+        SecurityGroupRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for SecurityGroupRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<SecurityGroupRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            SecurityGroupRef::new_verified(api::get_security_group(session, &self.value)?.id)
+        })
+    }
+}
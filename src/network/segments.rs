@@ -0,0 +1,383 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Network segments management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, NetworkRef, Refresh, ResourceIterator, ResourceQuery, SegmentRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, Network};
+
+/// A query to network segment list.
+#[derive(Clone, Debug)]
+pub struct SegmentQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    network: Option<NetworkRef>,
+}
+
+/// Structure representing a segment of a routed provider network.
+#[derive(Clone, Debug)]
+pub struct Segment {
+    session: Rc<Session>,
+    inner: protocol::Segment,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a network segment.
+#[derive(Clone, Debug)]
+pub struct NewSegment {
+    session: Rc<Session>,
+    inner: protocol::Segment,
+    network: NetworkRef,
+}
+
+impl Segment {
+    /// Create a segment object.
+    fn new(session: Rc<Session>, inner: protocol::Segment) -> Segment {
+        Segment {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a Segment object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Segment> {
+        let inner = api::get_segment(&session, id)?;
+        Ok(Segment::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Segment description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Segment name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    /// Get network associated with this segment.
+    pub fn network(&self) -> Result<Network> {
+        Network::load(self.session.clone(), &self.inner.network_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the network this segment belongs to."]
+        network_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Network type (e.g. `vlan`)."]
+        network_type: ref protocol::NetworkType
+    }
+
+    transparent_property! {
+        #[doc = "Physical network this segment is mapped to (if any)."]
+        physical_network: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Segmentation ID (e.g. VLAN ID), if applicable."]
+        segmentation_id: Option<u32>
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Delete the segment.
+    pub fn delete(self) -> Result<DeletionWaiter<Segment>> {
+        api::delete_segment(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the segment is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the segment.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::SegmentUpdate::default();
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_segment(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for Segment {
+    /// Refresh the segment.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_segment_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl SegmentQuery {
+    pub(crate) fn new(session: Rc<Session>) -> SegmentQuery {
+        SegmentQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            network: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::SegmentSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by segment name."]
+        set_name, with_name -> name
+    }
+
+    /// Filter by network.
+    pub fn set_network<N: Into<NetworkRef>>(&mut self, value: N) {
+        self.network = Some(value.into());
+    }
+
+    /// Filter by network.
+    pub fn with_network<N: Into<NetworkRef>>(mut self, value: N) -> Self {
+        self.set_network(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by network type."]
+        set_network_type, with_network_type -> network_type
+    }
+
+    query_filter! {
+        #[doc = "Filter by physical network."]
+        set_physical_network, with_physical_network -> physical_network
+    }
+
+    query_filter! {
+        #[doc = "Filter by segmentation ID."]
+        set_segmentation_id, with_segmentation_id -> segmentation_id: u32
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<SegmentQuery> {
+        debug!("Fetching segments with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Segment>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<Segment> {
+        debug!("Fetching one segment with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for SegmentQuery {
+    type Item = Segment;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_segments(&self.session, &query)?
+            .into_iter()
+            .map(|item| Segment::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(network) = self.network.take() {
+            let verified = network.into_verified(&self.session)?;
+            self.query.push_str("network_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(SegmentQuery: ["segments"] -> "segments", Segment: "Network segments");
+
+impl NewSegment {
+    /// Start creating a segment.
+    pub(crate) fn new(
+        session: Rc<Session>,
+        network: NetworkRef,
+        network_type: protocol::NetworkType,
+    ) -> NewSegment {
+        NewSegment {
+            session,
+            inner: protocol::Segment {
+                created_at: None,
+                description: None,
+                id: String::new(),
+                name: None,
+                // Will be replaced in create()
+                network_id: String::new(),
+                network_type,
+                physical_network: None,
+                segmentation_id: None,
+                updated_at: None,
+            },
+            network,
+        }
+    }
+
+    /// Request creation of the segment.
+    pub fn create(mut self) -> Result<Segment> {
+        self.inner.network_id = self.network.into_verified(&self.session)?.into();
+        let inner = api::create_segment(&self.session, self.inner)?;
+        Ok(Segment::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the segment."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the segment."]
+        set_name, with_name -> name: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the physical network of the segment."]
+        set_physical_network, with_physical_network -> physical_network: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the segmentation ID (e.g. VLAN ID) of the segment."]
+        set_segmentation_id, with_segmentation_id -> segmentation_id: optional u32
+    }
+}
+
+impl IntoFallibleIterator for SegmentQuery {
+    type Item = Segment;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<SegmentQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<Segment> for SegmentRef {
+    fn from(value: Segment) -> SegmentRef {
+        SegmentRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for SegmentRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<SegmentRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            SegmentRef::new_verified(api::get_segment(session, &self.value)?.id)
+        })
+    }
+}
@@ -0,0 +1,534 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subnet pools management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+use ipnet;
+
+use super::super::common::{
+    AddressScopeRef, DeletionWaiter, IntoVerified, Refresh, ResourceIterator, ResourceQuery,
+    SubnetPoolRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, AddressScope};
+
+/// A query to subnet pool list.
+#[derive(Clone, Debug)]
+pub struct SubnetPoolQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    address_scope: Option<AddressScopeRef>,
+}
+
+/// Structure representing a subnet pool.
+#[derive(Clone, Debug)]
+pub struct SubnetPool {
+    session: Rc<Session>,
+    inner: protocol::SubnetPool,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a subnet pool.
+#[derive(Clone, Debug)]
+pub struct NewSubnetPool {
+    session: Rc<Session>,
+    inner: protocol::SubnetPool,
+    address_scope: Option<AddressScopeRef>,
+}
+
+impl SubnetPool {
+    /// Create a subnet pool object.
+    fn new(session: Rc<Session>, inner: protocol::SubnetPool) -> SubnetPool {
+        SubnetPool {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a SubnetPool object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<SubnetPool> {
+        let inner = api::get_subnet_pool(&session, id)?;
+        Ok(SubnetPool::new(session, inner))
+    }
+
+    /// Get the address scope associated with this subnet pool (if any).
+    pub fn address_scope(&self) -> Result<Option<AddressScope>> {
+        match self.inner.address_scope_id {
+            Some(ref id) => AddressScope::load(self.session.clone(), id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "ID of the address scope of this subnet pool (if any)."]
+        address_scope_id: ref Option<String>
+    }
+
+    /// Associate the subnet pool with an address scope.
+    #[allow(unused_results)]
+    pub fn set_address_scope<A>(&mut self, address_scope: A) -> Result<()>
+    where
+        A: Into<AddressScopeRef>,
+    {
+        let verified = address_scope.into().into_verified(&self.session)?;
+        self.inner.address_scope_id = Some(verified.into());
+        self.dirty.insert("address_scope_id");
+        Ok(())
+    }
+
+    /// Remove the association with an address scope.
+    #[allow(unused_results)]
+    pub fn clear_address_scope(&mut self) {
+        self.inner.address_scope_id = None;
+        self.dirty.insert("address_scope_id");
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Default prefix length for subnets allocated from this pool."]
+        default_prefix_length: Option<u8>
+    }
+
+    update_field! {
+        #[doc = "Update the default prefix length."]
+        set_default_prefix_length, with_default_prefix_length
+            -> default_prefix_length: optional u8
+    }
+
+    transparent_property! {
+        #[doc = "Number of addresses a project can allocate from this pool."]
+        default_quota: Option<u32>
+    }
+
+    update_field! {
+        #[doc = "Update the default quota."]
+        set_default_quota, with_default_quota -> default_quota: optional u32
+    }
+
+    transparent_property! {
+        #[doc = "Subnet pool description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "IP protocol version."]
+        ip_version: protocol::IpVersion
+    }
+
+    transparent_property! {
+        #[doc = "Whether this is the default subnet pool."]
+        is_default: bool
+    }
+
+    update_field! {
+        #[doc = "Configure whether this is the default subnet pool."]
+        set_default, with_default -> is_default: bool
+    }
+
+    transparent_property! {
+        #[doc = "Maximum prefix length for subnets allocated from this pool."]
+        max_prefix_length: Option<u8>
+    }
+
+    update_field! {
+        #[doc = "Update the maximum prefix length."]
+        set_max_prefix_length, with_max_prefix_length
+            -> max_prefix_length: optional u8
+    }
+
+    transparent_property! {
+        #[doc = "Minimum prefix length for subnets allocated from this pool."]
+        min_prefix_length: Option<u8>
+    }
+
+    update_field! {
+        #[doc = "Update the minimum prefix length."]
+        set_min_prefix_length, with_min_prefix_length
+            -> min_prefix_length: optional u8
+    }
+
+    transparent_property! {
+        #[doc = "Subnet pool name."]
+        name: ref String
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name
+    }
+
+    transparent_property! {
+        #[doc = "Prefixes available for allocation."]
+        prefixes: ref Vec<ipnet::IpNet>
+    }
+
+    update_field_mut! {
+        #[doc = "Update the prefixes available for allocation."]
+        prefixes_mut, set_prefixes, with_prefixes -> prefixes: Vec<ipnet::IpNet>
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the subnet pool (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Whether the subnet pool is shared with other projects."]
+        shared: bool
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Delete the subnet pool.
+    pub fn delete(self) -> Result<DeletionWaiter<SubnetPool>> {
+        api::delete_subnet_pool(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the subnet pool is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the subnet pool.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::SubnetPoolUpdate::default();
+        save_fields! {
+            self -> update: address_scope_id is_default name prefixes
+        };
+        save_option_fields! {
+            self -> update: default_prefix_length default_quota description
+                max_prefix_length min_prefix_length
+        };
+        let inner = api::update_subnet_pool(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+}
+
+impl Refresh for SubnetPool {
+    /// Refresh the subnet pool.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_subnet_pool_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl SubnetPoolQuery {
+    pub(crate) fn new(session: Rc<Session>) -> SubnetPoolQuery {
+        SubnetPoolQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            address_scope: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::SubnetPoolSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    /// Filter by address scope.
+    pub fn set_address_scope<A: Into<AddressScopeRef>>(&mut self, value: A) {
+        self.address_scope = Some(value.into());
+    }
+
+    /// Filter by address scope.
+    pub fn with_address_scope<A: Into<AddressScopeRef>>(mut self, value: A) -> Self {
+        self.set_address_scope(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the subnet pool is the default one."]
+        set_default, with_default -> is_default: bool
+    }
+
+    /// Filter by IP version.
+    pub fn set_ip_version(&mut self, value: protocol::IpVersion) {
+        self.query.push("ip_version", u8::from(value));
+    }
+
+    /// Filter by IP version.
+    pub fn with_ip_version(mut self, value: protocol::IpVersion) -> Self {
+        self.set_ip_version(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by subnet pool name."]
+        set_name, with_name -> name
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by whether the subnet pool is shared."]
+        set_shared, with_shared -> shared: bool
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<SubnetPoolQuery> {
+        debug!("Fetching subnet pools with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<SubnetPool>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<SubnetPool> {
+        debug!("Fetching one subnet pool with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for SubnetPoolQuery {
+    type Item = SubnetPool;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_subnet_pools(&self.session, &query)?
+            .into_iter()
+            .map(|item| SubnetPool::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(address_scope) = self.address_scope.take() {
+            let verified = address_scope.into_verified(&self.session)?;
+            self.query.push_str("address_scope_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(SubnetPoolQuery: ["subnetpools"] -> "subnetpools", SubnetAllocation: "Subnet pools");
+
+impl NewSubnetPool {
+    /// Start creating a subnet pool.
+    pub(crate) fn new(session: Rc<Session>, name: String) -> NewSubnetPool {
+        NewSubnetPool {
+            session,
+            inner: protocol::SubnetPool {
+                address_scope_id: None,
+                created_at: None,
+                default_prefix_length: None,
+                default_quota: None,
+                description: None,
+                id: String::new(),
+                ip_version: protocol::IpVersion::V4,
+                is_default: false,
+                max_prefix_length: None,
+                min_prefix_length: None,
+                name,
+                prefixes: Vec::new(),
+                project_id: None,
+                shared: false,
+                updated_at: None,
+            },
+            address_scope: None,
+        }
+    }
+
+    /// Request creation of the subnet pool.
+    pub fn create(mut self) -> Result<SubnetPool> {
+        if let Some(address_scope) = self.address_scope {
+            self.inner.address_scope_id = Some(address_scope.into_verified(&self.session)?.into());
+        }
+
+        let inner = api::create_subnet_pool(&self.session, self.inner)?;
+        Ok(SubnetPool::new(self.session, inner))
+    }
+
+    /// Associate the subnet pool with an address scope.
+    pub fn set_address_scope<A>(&mut self, address_scope: A)
+    where
+        A: Into<AddressScopeRef>,
+    {
+        self.address_scope = Some(address_scope.into());
+    }
+
+    /// Associate the subnet pool with an address scope.
+    pub fn with_address_scope<A>(mut self, address_scope: A) -> Self
+    where
+        A: Into<AddressScopeRef>,
+    {
+        self.set_address_scope(address_scope);
+        self
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether this is the default subnet pool."]
+        set_default, with_default -> is_default: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the default prefix length for allocated subnets."]
+        set_default_prefix_length, with_default_prefix_length
+            -> default_prefix_length: optional u8
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the number of addresses a project can allocate from the pool."]
+        set_default_quota, with_default_quota -> default_quota: optional u32
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the subnet pool."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the maximum prefix length for allocated subnets."]
+        set_max_prefix_length, with_max_prefix_length
+            -> max_prefix_length: optional u8
+    }
+
+    creation_inner_field! {
+        #[doc = "Set the minimum prefix length for allocated subnets."]
+        set_min_prefix_length, with_min_prefix_length
+            -> min_prefix_length: optional u8
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the subnet pool."]
+        set_name, with_name -> name
+    }
+
+    creation_inner_vec! {
+        #[doc = "Prefix(es) available for allocation (at least one is required)."]
+        add_prefix, with_prefix -> prefixes: ipnet::IpNet
+    }
+
+    creation_inner_field! {
+        #[doc = "Configure whether the subnet pool is shared with other projects."]
+        set_shared, with_shared -> shared: bool
+    }
+}
+
+impl IntoFallibleIterator for SubnetPoolQuery {
+    type Item = SubnetPool;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<SubnetPoolQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<SubnetPool> for SubnetPoolRef {
+    fn from(value: SubnetPool) -> SubnetPoolRef {
+        SubnetPoolRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for SubnetPoolRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<SubnetPoolRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            SubnetPoolRef::new_verified(api::get_subnet_pool(session, &self.value)?.id)
+        })
+    }
+}
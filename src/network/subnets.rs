@@ -18,18 +18,19 @@ use std::collections::HashSet;
 use std::net;
 use std::rc::Rc;
 use std::time::Duration;
+use std::vec;
 
 use chrono::{DateTime, FixedOffset};
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 use ipnet;
 
 use super::super::common::{
-    DeletionWaiter, IntoVerified, NetworkRef, Refresh, ResourceIterator, ResourceQuery, SubnetRef,
+    DeletionWaiter, IntoVerified, NetworkRef, Refresh, ResourceIterator, ResourceQuery, SegmentRef,
+    SubnetPoolRef, SubnetRef,
 };
-use super::super::session::Session;
-use super::super::utils::Query;
-use super::super::{Error, Result, Sort};
-use super::{api, protocol, Network};
+use super::super::utils::{Query, Session};
+use super::super::{Error, ErrorKind, Result, Sort};
+use super::{api, protocol, Network, PortQuery, Segment, SubnetPool};
 
 /// A query to subnet list.
 #[derive(Clone, Debug)]
@@ -48,12 +49,25 @@ pub struct Subnet {
     dirty: HashSet<&'static str>,
 }
 
+/// An iterator over free IP addresses of a subnet.
+///
+/// Created by [Subnet::free_addresses](struct.Subnet.html#method.free_addresses).
+#[derive(Clone, Debug)]
+pub struct FreeAddresses {
+    cidr: ipnet::IpNet,
+    pools: vec::IntoIter<protocol::AllocationPool>,
+    current: Option<(net::IpAddr, net::IpAddr)>,
+    used: HashSet<net::IpAddr>,
+}
+
 /// A request to create a subnet.
 #[derive(Clone, Debug)]
 pub struct NewSubnet {
     session: Rc<Session>,
     inner: protocol::Subnet,
     network: NetworkRef,
+    segment: Option<SegmentRef>,
+    subnet_pool: Option<SubnetPoolRef>,
 }
 
 impl Subnet {
@@ -83,9 +97,12 @@ impl Subnet {
             -> allocation_pools: Vec<protocol::AllocationPool>
     }
 
-    transparent_property! {
-        #[doc = "Network address of this subnet."]
-        cidr: ipnet::IpNet
+    /// Network address of this subnet.
+    #[inline]
+    pub fn cidr(&self) -> ipnet::IpNet {
+        self.inner
+            .cidr
+            .expect("Subnets received from Network API always have a CIDR")
     }
 
     transparent_property! {
@@ -124,6 +141,35 @@ impl Subnet {
             -> dns_nameservers: Vec<String>
     }
 
+    /// Find IP addresses of this subnet that are not allocated to any port.
+    ///
+    /// The addresses are taken from the allocation pools of the subnet,
+    /// skipping the gateway IP and fixed IPs of the existing ports. For IPv4
+    /// the network and broadcast addresses are never returned. The ports are
+    /// fetched once when this call is made, so the result is only a snapshot:
+    /// addresses may be allocated concurrently.
+    pub fn free_addresses(&self) -> Result<FreeAddresses> {
+        let mut used = HashSet::new();
+        let mut ports = PortQuery::new(self.session.clone())
+            .with_subnet(SubnetRef::new_verified(self.inner.id.clone()))
+            .into_iter();
+        while let Some(port) = ports.next()? {
+            used.extend(
+                port.fixed_ips()
+                    .iter()
+                    .filter(|fixed_ip| fixed_ip.subnet_id == self.inner.id)
+                    .map(|fixed_ip| fixed_ip.ip_address),
+            );
+        }
+
+        Ok(FreeAddresses::new(
+            self.cidr(),
+            self.inner.allocation_pools.clone(),
+            self.inner.gateway_ip,
+            used,
+        ))
+    }
+
     transparent_property! {
         #[doc = "Gateway IP address (if any)."]
         gateway_ip: Option<net::IpAddr>
@@ -185,6 +231,37 @@ impl Subnet {
         network_id: ref String
     }
 
+    transparent_property! {
+        #[doc = "Revision number of the subnet (if available)."]
+        revision_number: Option<u32>
+    }
+
+    /// Get the segment this subnet is associated with (if any).
+    pub fn segment(&self) -> Result<Option<Segment>> {
+        match self.inner.segment_id {
+            Some(ref id) => Segment::load(self.session.clone(), id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "ID of the segment this subnet is associated with (if any)."]
+        segment_id: ref Option<String>
+    }
+
+    /// Get the subnet pool this subnet was allocated from (if any).
+    pub fn subnet_pool(&self) -> Result<Option<SubnetPool>> {
+        match self.inner.subnetpool_id {
+            Some(ref id) => SubnetPool::load(self.session.clone(), id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    transparent_property! {
+        #[doc = "ID of the subnet pool this subnet was allocated from (if any)."]
+        subnetpool_id: ref Option<String>
+    }
+
     transparent_property! {
         #[doc = "Last update data and time (if available)."]
         updated_at: Option<DateTime<FixedOffset>>
@@ -207,6 +284,10 @@ impl Subnet {
 
     /// Save the changes to the subnet.
     pub fn save(&mut self) -> Result<()> {
+        self.save_revision(None)
+    }
+
+    fn save_revision(&mut self, revision: Option<u32>) -> Result<()> {
         let mut update = protocol::SubnetUpdate::default();
         save_fields! {
             self -> update: allocation_pools dhcp_enabled dns_nameservers
@@ -215,13 +296,17 @@ impl Subnet {
         save_option_fields! {
             self -> update: description gateway_ip name
         };
-        let inner = api::update_subnet(&self.session, self.id(), update)?;
+        let inner = api::update_subnet(&self.session, self.id(), update, revision)?;
         self.dirty.clear();
         self.inner = inner;
         Ok(())
     }
 }
 
+taggable_resource!(Subnet -> "subnets");
+
+revisioned_resource!(Subnet -> "subnet");
+
 impl Refresh for Subnet {
     /// Refresh the subnet.
     fn refresh(&mut self) -> Result<()> {
@@ -315,6 +400,13 @@ impl SubnetQuery {
         self
     }
 
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    tag_query_filters!();
+
     /// Convert this query into an iterator executing the request.
     ///
     /// Returns a `FallibleIterator`, which is an iterator with each `next`
@@ -379,26 +471,71 @@ impl ResourceQuery for SubnetQuery {
     }
 }
 
+projectable_query!(SubnetQuery: ["subnets"] -> "subnets");
+
 impl NewSubnet {
     /// Start creating a subnet.
     pub(crate) fn new(session: Rc<Session>, network: NetworkRef, cidr: ipnet::IpNet) -> NewSubnet {
         NewSubnet {
             session,
-            inner: protocol::Subnet::empty(cidr),
+            inner: protocol::Subnet::empty(Some(cidr)),
             network,
+            segment: None,
+            subnet_pool: None,
+        }
+    }
+
+    /// Start creating a subnet allocated from a subnet pool.
+    pub(crate) fn new_from_pool(
+        session: Rc<Session>,
+        network: NetworkRef,
+        subnet_pool: SubnetPoolRef,
+    ) -> NewSubnet {
+        // No CIDR means "allocate from the pool".
+        NewSubnet {
+            session,
+            inner: protocol::Subnet::empty(None),
+            network,
+            segment: None,
+            subnet_pool: Some(subnet_pool),
         }
     }
 
     /// Request creation of the subnet.
-    pub fn create(mut self) -> Result<Subnet> {
-        self.inner.network_id = self.network.into_verified(&self.session)?.into();
-        self.inner.ip_version = match self.inner.cidr {
-            ipnet::IpNet::V4(..) => protocol::IpVersion::V4,
-            ipnet::IpNet::V6(..) => protocol::IpVersion::V6,
-        };
+    pub fn create(self) -> Result<Subnet> {
+        let session = self.session.clone();
+        let subnet = api::create_subnet(&session, self.into_request()?)?;
+        Ok(Subnet::new(session, subnet))
+    }
 
-        let subnet = api::create_subnet(&self.session, self.inner)?;
-        Ok(Subnet::new(self.session, subnet))
+    /// Verify references and convert into a protocol request.
+    fn into_request(mut self) -> Result<protocol::Subnet> {
+        self.inner.network_id = self.network.into_verified(&self.session)?.into();
+        if let Some(segment) = self.segment {
+            self.inner.segment_id = Some(segment.into_verified(&self.session)?.into());
+        }
+        if let Some(cidr) = self.inner.cidr {
+            self.inner.ip_version = match cidr {
+                ipnet::IpNet::V4(..) => protocol::IpVersion::V4,
+                ipnet::IpNet::V6(..) => protocol::IpVersion::V6,
+            };
+            if let Some(subnet_pool) = self.subnet_pool {
+                self.inner.subnetpool_id = Some(subnet_pool.into_verified(&self.session)?.into());
+            }
+        } else {
+            let subnet_pool = match self.subnet_pool {
+                Some(subnet_pool) => api::get_subnet_pool(&self.session, subnet_pool)?,
+                None => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Either a CIDR or a subnet pool is required to create a subnet",
+                    ));
+                }
+            };
+            self.inner.ip_version = subnet_pool.ip_version;
+            self.inner.subnetpool_id = Some(subnet_pool.id);
+        }
+        Ok(self.inner)
     }
 
     creation_inner_vec! {
@@ -408,7 +545,7 @@ impl NewSubnet {
 
     creation_inner_field! {
         #[doc = "Set CIDR of the subnet."]
-        set_cidr, with_cidr -> cidr: ipnet::IpNet
+        set_cidr, with_cidr -> cidr: optional ipnet::IpNet
     }
 
     creation_inner_field! {
@@ -448,6 +585,11 @@ impl NewSubnet {
         set_name, with_name -> name: optional String
     }
 
+    creation_inner_field! {
+        #[doc = "Set prefix length of a subnet allocated from a subnet pool."]
+        set_prefix_length, with_prefix_length -> prefix_length: optional u8
+    }
+
     /// Set the network of the subnet.
     pub fn set_network<N>(&mut self, value: N)
     where
@@ -464,6 +606,128 @@ impl NewSubnet {
         self.set_network(value);
         self
     }
+
+    /// Associate the subnet with a segment of a routed provider network.
+    pub fn set_segment<S>(&mut self, value: S)
+    where
+        S: Into<SegmentRef>,
+    {
+        self.segment = Some(value.into());
+    }
+
+    /// Associate the subnet with a segment of a routed provider network.
+    pub fn with_segment<S>(mut self, value: S) -> Self
+    where
+        S: Into<SegmentRef>,
+    {
+        self.set_segment(value);
+        self
+    }
+
+    /// Set the subnet pool to allocate the subnet from.
+    pub fn set_subnet_pool<P>(&mut self, value: P)
+    where
+        P: Into<SubnetPoolRef>,
+    {
+        self.subnet_pool = Some(value.into());
+    }
+
+    /// Set the subnet pool to allocate the subnet from.
+    pub fn with_subnet_pool<P>(mut self, value: P) -> Self
+    where
+        P: Into<SubnetPoolRef>,
+    {
+        self.set_subnet_pool(value);
+        self
+    }
+}
+
+impl FreeAddresses {
+    fn new(
+        cidr: ipnet::IpNet,
+        pools: Vec<protocol::AllocationPool>,
+        gateway_ip: Option<net::IpAddr>,
+        mut used: HashSet<net::IpAddr>,
+    ) -> FreeAddresses {
+        used.extend(gateway_ip);
+        FreeAddresses {
+            cidr,
+            pools: pools.into_iter(),
+            current: None,
+            used,
+        }
+    }
+
+    fn is_reserved(&self, value: net::IpAddr) -> bool {
+        match self.cidr {
+            // Point-to-point networks have no network and broadcast addresses.
+            ipnet::IpNet::V4(cidr) if cidr.prefix_len() < 31 => {
+                value == net::IpAddr::V4(cidr.network())
+                    || value == net::IpAddr::V4(cidr.broadcast())
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Iterator for FreeAddresses {
+    type Item = net::IpAddr;
+
+    fn next(&mut self) -> Option<net::IpAddr> {
+        loop {
+            let (current, end) = match self.current.take() {
+                Some(range) => range,
+                None => {
+                    let pool = self.pools.next()?;
+                    (pool.start, pool.end)
+                }
+            };
+            if current > end {
+                continue;
+            }
+
+            self.current = next_address(current).map(|next| (next, end));
+            if self.cidr.contains(&current)
+                && !self.used.contains(&current)
+                && !self.is_reserved(current)
+            {
+                return Some(current);
+            }
+        }
+    }
+}
+
+fn next_address(value: net::IpAddr) -> Option<net::IpAddr> {
+    match value {
+        net::IpAddr::V4(addr) => u32::from(addr)
+            .checked_add(1)
+            .map(|next| net::Ipv4Addr::from(next).into()),
+        net::IpAddr::V6(addr) => u128::from(addr)
+            .checked_add(1)
+            .map(|next| net::Ipv6Addr::from(next).into()),
+    }
+}
+
+/// Create several subnets in one request.
+///
+/// The subnets are returned in the same order as the requests. The operation
+/// is atomic: on failure no subnets are created.
+pub(crate) fn create_subnets(
+    session: Rc<Session>,
+    requests: Vec<NewSubnet>,
+) -> Result<Vec<Subnet>> {
+    if requests.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let requests = requests
+        .into_iter()
+        .map(NewSubnet::into_request)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(api::create_subnets(&session, requests)?
+        .into_iter()
+        .map(|item| Subnet::new(session.clone(), item))
+        .collect())
 }
 
 impl IntoFallibleIterator for SubnetQuery {
@@ -495,3 +759,88 @@ impl IntoVerified for SubnetRef {
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::net;
+
+    use super::super::protocol::{AllocationPool, Subnet};
+    use super::FreeAddresses;
+
+    fn pool(start: &str, end: &str) -> AllocationPool {
+        AllocationPool {
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_free_addresses() {
+        let used: HashSet<net::IpAddr> = vec!["10.0.0.3".parse().unwrap()].into_iter().collect();
+        let pools = vec![
+            pool("10.0.0.1", "10.0.0.4"),
+            pool("10.0.0.253", "10.0.0.254"),
+        ];
+        let gateway = Some("10.0.0.1".parse().unwrap());
+        let result: Vec<String> =
+            FreeAddresses::new("10.0.0.0/24".parse().unwrap(), pools, gateway, used)
+                .map(|addr| addr.to_string())
+                .collect();
+        assert_eq!(
+            result,
+            vec!["10.0.0.2", "10.0.0.4", "10.0.0.253", "10.0.0.254"]
+        );
+    }
+
+    #[test]
+    fn test_free_addresses_ipv4_reserved() {
+        let pools = vec![pool("10.0.0.0", "10.0.0.3")];
+        let result: Vec<String> =
+            FreeAddresses::new("10.0.0.0/30".parse().unwrap(), pools, None, HashSet::new())
+                .map(|addr| addr.to_string())
+                .collect();
+        assert_eq!(result, vec!["10.0.0.1", "10.0.0.2"]);
+    }
+
+    #[test]
+    fn test_free_addresses_ipv6_end() {
+        let pools = vec![pool(
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe",
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+        )];
+        let result: Vec<net::IpAddr> =
+            FreeAddresses::new("ffff::/16".parse().unwrap(), pools, None, HashSet::new()).collect();
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn test_free_addresses_empty() {
+        let pools = vec![pool("10.0.0.5", "10.0.0.4")];
+        let mut result =
+            FreeAddresses::new("10.0.0.0/24".parse().unwrap(), pools, None, HashSet::new());
+        assert!(result.next().is_none());
+    }
+
+    #[test]
+    fn test_subnet_cidr_optional_in_requests() {
+        let request = serde_json::to_value(Subnet::empty(None)).unwrap();
+        assert!(request.get("cidr").is_none());
+
+        let cidr = "10.0.0.0/24".parse().unwrap();
+        let request = serde_json::to_value(Subnet::empty(Some(cidr))).unwrap();
+        assert_eq!(request["cidr"], "10.0.0.0/24");
+
+        let mut response = serde_json::json!({
+            "enable_dhcp": true,
+            "id": "subnet-id",
+            "ip_version": 4,
+            "name": "",
+            "network_id": "network-id",
+        });
+        assert!(serde_json::from_value::<Subnet>(response.clone()).is_err());
+        response["cidr"] = "10.0.0.0/24".into();
+        let subnet: Subnet = serde_json::from_value(response).unwrap();
+        assert_eq!(subnet.cidr, Some(cidr));
+    }
+}
@@ -0,0 +1,430 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Network topology of a project as a graph.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use serde::Serialize;
+use serde_json;
+
+use super::super::common::{IntoVerified, ProjectRef};
+#[cfg(feature = "compute")]
+use super::super::compute::Server;
+use super::super::utils::Session;
+use super::super::{Error, ErrorKind, Result};
+use super::{
+    api, FloatingIpQuery, NetworkQuery, PortQuery, Router, RouterQuery, Subnet, SubnetQuery,
+};
+
+/// Kind of a resource in a network topology.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TopologyNodeKind {
+    /// A floating IP.
+    FloatingIp,
+    /// A network.
+    Network,
+    /// A port.
+    Port,
+    /// A router.
+    Router,
+    /// A server.
+    Server,
+    /// A subnet.
+    Subnet,
+}
+
+/// A single resource in a network topology.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopologyNode {
+    /// Kind of the resource.
+    pub kind: TopologyNodeKind,
+    /// ID of the resource.
+    pub id: String,
+    /// Human-readable label: name, CIDR or IP address, falling back to the ID.
+    pub label: String,
+}
+
+/// A connection between two resources in a network topology.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopologyEdge {
+    /// ID of the first resource.
+    pub source: String,
+    /// ID of the second resource.
+    pub target: String,
+    /// Optional label, e.g. an IP address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Network topology of a project.
+///
+/// Nodes are servers, ports, subnets, networks, routers and floating IPs,
+/// edges are connections between them. Resources of other projects (e.g.
+/// shared or external networks) are included if the project's resources
+/// are connected to them.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkTopology {
+    project_id: String,
+    nodes: Vec<TopologyNode>,
+    edges: Vec<TopologyEdge>,
+    #[serde(skip)]
+    index: HashMap<String, usize>,
+}
+
+impl TopologyNodeKind {
+    fn dot_shape(self) -> &'static str {
+        match self {
+            TopologyNodeKind::FloatingIp => "hexagon",
+            TopologyNodeKind::Network => "box3d",
+            TopologyNodeKind::Port => "ellipse",
+            TopologyNodeKind::Router => "diamond",
+            TopologyNodeKind::Server => "box",
+            TopologyNodeKind::Subnet => "note",
+        }
+    }
+}
+
+impl NetworkTopology {
+    fn new(project_id: String) -> NetworkTopology {
+        NetworkTopology {
+            project_id,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// ID of the project.
+    pub fn project_id(&self) -> &String {
+        &self.project_id
+    }
+
+    /// Resources in the topology.
+    pub fn nodes(&self) -> &Vec<TopologyNode> {
+        &self.nodes
+    }
+
+    /// Connections between the resources.
+    pub fn edges(&self) -> &Vec<TopologyEdge> {
+        &self.edges
+    }
+
+    /// Find a resource by its ID.
+    pub fn node<S: AsRef<str>>(&self, id: S) -> Option<&TopologyNode> {
+        self.index.get(id.as_ref()).map(|idx| &self.nodes[*idx])
+    }
+
+    /// Export the topology in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut result = String::new();
+        let _ = writeln!(result, "graph {} {{", dot_quote(&self.project_id));
+        for node in &self.nodes {
+            let _ = writeln!(
+                result,
+                "    {} [label={}, shape={}];",
+                dot_quote(&node.id),
+                dot_quote(&node.label),
+                node.kind.dot_shape()
+            );
+        }
+        for edge in &self.edges {
+            let _ = match edge.label {
+                Some(ref label) => writeln!(
+                    result,
+                    "    {} -- {} [label={}];",
+                    dot_quote(&edge.source),
+                    dot_quote(&edge.target),
+                    dot_quote(label)
+                ),
+                None => writeln!(
+                    result,
+                    "    {} -- {};",
+                    dot_quote(&edge.source),
+                    dot_quote(&edge.target)
+                ),
+            };
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    /// Export the topology as a JSON document.
+    ///
+    /// The document is an object with `project_id`, `nodes` and `edges` keys.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Cannot serialize the network topology: {}", err),
+            )
+        })
+    }
+
+    fn add_node(&mut self, kind: TopologyNodeKind, id: &str, label: Option<String>) -> bool {
+        if self.index.contains_key(id) {
+            return false;
+        }
+
+        let _ = self.index.insert(id.to_string(), self.nodes.len());
+        self.nodes.push(TopologyNode {
+            kind,
+            id: id.to_string(),
+            label: label.unwrap_or_else(|| id.to_string()),
+        });
+        true
+    }
+
+    fn add_edge(&mut self, source: &str, target: &str, label: Option<String>) {
+        self.edges.push(TopologyEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            label,
+        });
+    }
+
+    fn ensure_network(&mut self, session: &Session, id: &str) -> Result<()> {
+        if !self.index.contains_key(id) {
+            let network = api::get_network_by_id(session, id)?;
+            let _ = self.add_node(TopologyNodeKind::Network, id, network.name);
+        }
+        Ok(())
+    }
+
+    fn ensure_subnet(&mut self, session: &Rc<Session>, id: &str) -> Result<()> {
+        if !self.index.contains_key(id) {
+            let subnet = Subnet::load(session.clone(), id)?;
+            self.add_subnet(session, &subnet)?;
+        }
+        Ok(())
+    }
+
+    fn add_subnet(&mut self, session: &Session, subnet: &Subnet) -> Result<()> {
+        let label = match subnet.name() {
+            Some(ref name) => format!("{} ({})", name, subnet.cidr()),
+            None => subnet.cidr().to_string(),
+        };
+        if self.add_node(TopologyNodeKind::Subnet, subnet.id(), Some(label)) {
+            self.ensure_network(session, subnet.network_id())?;
+            self.add_edge(subnet.id(), subnet.network_id(), None);
+        }
+        Ok(())
+    }
+
+    fn add_router(&mut self, session: &Session, router: &Router) -> Result<()> {
+        if self.add_node(TopologyNodeKind::Router, router.id(), router.name().clone()) {
+            if let Some(gateway) = router.external_gateway() {
+                self.ensure_network(session, &gateway.network_id)?;
+                self.add_edge(
+                    router.id(),
+                    &gateway.network_id,
+                    Some(String::from("gateway")),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Use server names as labels.
+    ///
+    /// Servers that no longer exist while their ports do keep their IDs.
+    #[cfg(feature = "compute")]
+    fn resolve_server_names(&mut self, session: &Rc<Session>) -> Result<()> {
+        for node in &mut self.nodes {
+            if node.kind != TopologyNodeKind::Server {
+                continue;
+            }
+
+            match Server::load(session.clone(), &node.id) {
+                Ok(server) => node.label = server.name().clone(),
+                Err(ref err) if err.kind() == ErrorKind::ResourceNotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build the network topology of a project.
+pub(crate) fn network_topology(
+    session: Rc<Session>,
+    project: ProjectRef,
+) -> Result<NetworkTopology> {
+    let project = project.into_verified(&session)?;
+    let mut topology = NetworkTopology::new(project.to_string());
+
+    let networks = NetworkQuery::new(session.clone())
+        .with_project(project.clone())
+        .all()?;
+    for network in networks {
+        let _ = topology.add_node(
+            TopologyNodeKind::Network,
+            network.id(),
+            network.name().clone(),
+        );
+    }
+
+    let subnets = SubnetQuery::new(session.clone())
+        .with_project(project.clone())
+        .all()?;
+    for subnet in subnets {
+        topology.add_subnet(&session, &subnet)?;
+    }
+
+    let routers = RouterQuery::new(session.clone())
+        .with_project(project.clone())
+        .all()?;
+    for router in routers {
+        topology.add_router(&session, &router)?;
+    }
+
+    let ports = PortQuery::new(session.clone())
+        .with_project(project.clone())
+        .all()?;
+    for port in ports {
+        let _ = topology.add_node(TopologyNodeKind::Port, port.id(), port.name().clone());
+        topology.ensure_network(&session, port.network_id())?;
+        topology.add_edge(port.id(), port.network_id(), None);
+        for fixed_ip in port.fixed_ips() {
+            topology.ensure_subnet(&session, &fixed_ip.subnet_id)?;
+            topology.add_edge(
+                port.id(),
+                &fixed_ip.subnet_id,
+                Some(fixed_ip.ip_address.to_string()),
+            );
+        }
+
+        if let Some(ref device_id) = *port.device_id() {
+            if port.attached_to_server() {
+                let _ = topology.add_node(TopologyNodeKind::Server, device_id, None);
+                topology.add_edge(device_id, port.id(), None);
+            } else if owned_by_router(port.device_owner()) {
+                // Routers of other projects are not visible, use the ID.
+                let _ = topology.add_node(TopologyNodeKind::Router, device_id, None);
+                topology.add_edge(device_id, port.id(), None);
+            }
+        }
+    }
+
+    let floating_ips = FloatingIpQuery::new(session.clone())
+        .with_project(project)
+        .all()?;
+    for floating_ip in floating_ips {
+        let _ = topology.add_node(
+            TopologyNodeKind::FloatingIp,
+            floating_ip.id(),
+            Some(floating_ip.floating_ip_address().to_string()),
+        );
+        topology.ensure_network(&session, floating_ip.floating_network_id())?;
+        topology.add_edge(floating_ip.id(), floating_ip.floating_network_id(), None);
+        if let Some(ref port_id) = *floating_ip.port_id() {
+            let _ = topology.add_node(TopologyNodeKind::Port, port_id, None);
+            topology.add_edge(
+                floating_ip.id(),
+                port_id,
+                floating_ip.fixed_ip_address().map(|ip| ip.to_string()),
+            );
+        }
+    }
+
+    #[cfg(feature = "compute")]
+    topology.resolve_server_names(&session)?;
+
+    Ok(topology)
+}
+
+/// Whether the device owner denotes a router port.
+fn owned_by_router(device_owner: &Option<String>) -> bool {
+    match *device_owner {
+        Some(ref owner) => {
+            owner.starts_with("network:router") || owner == "network:ha_router_replicated_interface"
+        }
+        None => false,
+    }
+}
+
+/// Quote a string for the DOT format.
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{owned_by_router, NetworkTopology, TopologyNodeKind};
+
+    fn topology() -> NetworkTopology {
+        let mut topology = NetworkTopology::new(String::from("project"));
+        assert!(topology.add_node(
+            TopologyNodeKind::Network,
+            "net",
+            Some(String::from("private \"net\""))
+        ));
+        assert!(topology.add_node(TopologyNodeKind::Port, "port", None));
+        assert!(!topology.add_node(TopologyNodeKind::Port, "port", None));
+        topology.add_edge("port", "net", Some(String::from("10.0.0.5")));
+        topology.add_edge("port", "net", None);
+        topology
+    }
+
+    #[test]
+    fn test_node_lookup() {
+        let topology = topology();
+        assert_eq!(topology.nodes().len(), 2);
+        assert_eq!(topology.node("port").unwrap().label, "port");
+        assert_eq!(
+            topology.node("net").unwrap().kind,
+            TopologyNodeKind::Network
+        );
+        assert!(topology.node("server").is_none());
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let expected = "graph \"project\" {
+    \"net\" [label=\"private \\\"net\\\"\", shape=box3d];
+    \"port\" [label=\"port\", shape=ellipse];
+    \"port\" -- \"net\" [label=\"10.0.0.5\"];
+    \"port\" -- \"net\";
+}
+";
+        assert_eq!(topology().to_dot(), expected);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let value: serde_json::Value =
+            serde_json::from_str(&topology().to_json().unwrap()).unwrap();
+        assert_eq!(value["project_id"], "project");
+        assert_eq!(value["nodes"][0]["kind"], "network");
+        assert_eq!(value["nodes"][1]["label"], "port");
+        assert_eq!(value["edges"][0]["label"], "10.0.0.5");
+        assert!(value["edges"][1].get("label").is_none());
+    }
+
+    #[test]
+    fn test_owned_by_router() {
+        let owner = |s: &str| Some(String::from(s));
+        assert!(owned_by_router(&owner("network:router_interface")));
+        assert!(owned_by_router(&owner("network:router_gateway")));
+        assert!(owned_by_router(&owner(
+            "network:ha_router_replicated_interface"
+        )));
+        assert!(!owned_by_router(&owner("network:dhcp")));
+        assert!(!owned_by_router(&owner("compute:nova")));
+        assert!(!owned_by_router(&None));
+    }
+}
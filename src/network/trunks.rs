@@ -0,0 +1,594 @@
+// Copyright 2019 Dmitry Tantsur <divius.inside@gmail.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trunk management via Network API.
+
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
+
+use super::super::common::{
+    DeletionWaiter, IntoVerified, PortRef, Refresh, ResourceIterator, ResourceQuery, TrunkRef,
+};
+use super::super::utils::{Query, Session};
+use super::super::{Error, Result, Sort};
+use super::{api, protocol, Port};
+
+/// A query to trunk list.
+#[derive(Clone, Debug)]
+pub struct TrunkQuery {
+    session: Rc<Session>,
+    query: Query,
+    can_paginate: bool,
+    port: Option<PortRef>,
+}
+
+/// Structure representing a single trunk.
+#[derive(Clone, Debug)]
+pub struct Trunk {
+    session: Rc<Session>,
+    inner: protocol::Trunk,
+    dirty: HashSet<&'static str>,
+}
+
+/// A request to create a trunk.
+#[derive(Clone, Debug)]
+pub struct NewTrunk {
+    session: Rc<Session>,
+    inner: protocol::Trunk,
+    port: PortRef,
+    sub_ports: Vec<(PortRef, protocol::SegmentationType, u32)>,
+}
+
+fn convert_sub_ports<I>(session: &Session, sub_ports: I) -> Result<Vec<protocol::TrunkSubPort>>
+where
+    I: IntoIterator<Item = (PortRef, protocol::SegmentationType, u32)>,
+{
+    let mut result = Vec::new();
+    for (port, segmentation_type, segmentation_id) in sub_ports {
+        result.push(protocol::TrunkSubPort {
+            port_id: port.into_verified(session)?.into(),
+            segmentation_id,
+            segmentation_type,
+        });
+    }
+    Ok(result)
+}
+
+impl Trunk {
+    /// Create a trunk object.
+    fn new(session: Rc<Session>, inner: protocol::Trunk) -> Trunk {
+        Trunk {
+            session,
+            inner,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Load a Trunk object.
+    pub(crate) fn load<Id: AsRef<str>>(session: Rc<Session>, id: Id) -> Result<Trunk> {
+        let inner = api::get_trunk(&session, id)?;
+        Ok(Trunk::new(session, inner))
+    }
+
+    transparent_property! {
+        #[doc = "The administrative state of the trunk."]
+        admin_state_up: bool
+    }
+
+    update_field! {
+        #[doc = "Set the administrative state of the trunk."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    transparent_property! {
+        #[doc = "Creation data and time (if available)."]
+        created_at: Option<DateTime<FixedOffset>>
+    }
+
+    transparent_property! {
+        #[doc = "Trunk description."]
+        description: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the description."]
+        set_description, with_description -> description: optional String
+    }
+
+    transparent_property! {
+        #[doc = "Unique ID."]
+        id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "Trunk name."]
+        name: ref Option<String>
+    }
+
+    update_field! {
+        #[doc = "Update the name."]
+        set_name, with_name -> name: optional String
+    }
+
+    /// Fetch the parent port of the trunk.
+    pub fn port(&self) -> Result<Port> {
+        Port::load(self.session.clone(), &self.inner.port_id)
+    }
+
+    transparent_property! {
+        #[doc = "ID of the parent port of the trunk."]
+        port_id: ref String
+    }
+
+    transparent_property! {
+        #[doc = "ID of the project owning the trunk (if available)."]
+        project_id: ref Option<String>
+    }
+
+    transparent_property! {
+        #[doc = "Trunk status."]
+        status: protocol::TrunkStatus
+    }
+
+    transparent_property! {
+        #[doc = "Sub-ports of the trunk."]
+        sub_ports: ref Vec<protocol::TrunkSubPort>
+    }
+
+    transparent_property! {
+        #[doc = "Last update data and time (if available)."]
+        updated_at: Option<DateTime<FixedOffset>>
+    }
+
+    /// Add a sub-port to the trunk.
+    pub fn add_sub_port<P>(
+        &mut self,
+        port: P,
+        segmentation_type: protocol::SegmentationType,
+        segmentation_id: u32,
+    ) -> Result<()>
+    where
+        P: Into<PortRef>,
+    {
+        self.add_sub_ports(vec![(port.into(), segmentation_type, segmentation_id)])
+    }
+
+    /// Add several sub-ports to the trunk.
+    ///
+    /// Each item is a port, a segmentation type and a segmentation ID.
+    pub fn add_sub_ports<I>(&mut self, sub_ports: I) -> Result<()>
+    where
+        I: IntoIterator<Item = (PortRef, protocol::SegmentationType, u32)>,
+    {
+        let request = protocol::TrunkSubPortsRoot {
+            sub_ports: convert_sub_ports(&self.session, sub_ports)?,
+        };
+        let inner =
+            api::trunk_sub_ports_action(&self.session, &self.inner.id, "add_subports", request)?;
+        self.update_sub_ports(inner);
+        Ok(())
+    }
+
+    /// Remove a sub-port from the trunk.
+    pub fn remove_sub_port<P>(&mut self, port: P) -> Result<()>
+    where
+        P: Into<PortRef>,
+    {
+        self.remove_sub_ports(vec![port.into()])
+    }
+
+    /// Remove several sub-ports from the trunk.
+    pub fn remove_sub_ports<I>(&mut self, ports: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<PortRef>,
+    {
+        let mut sub_ports = Vec::new();
+        for port in ports {
+            sub_ports.push(protocol::TrunkSubPortRemoval {
+                port_id: port.into().into_verified(&self.session)?.into(),
+            });
+        }
+        let request = protocol::TrunkSubPortRemovalsRoot { sub_ports };
+        let inner =
+            api::trunk_sub_ports_action(&self.session, &self.inner.id, "remove_subports", request)?;
+        self.update_sub_ports(inner);
+        Ok(())
+    }
+
+    /// Delete the trunk.
+    pub fn delete(self) -> Result<DeletionWaiter<Trunk>> {
+        api::delete_trunk(&self.session, &self.inner.id)?;
+        Ok(DeletionWaiter::new(
+            self,
+            Duration::new(60, 0),
+            Duration::new(1, 0),
+        ))
+    }
+
+    /// Whether the trunk is modified.
+    pub fn is_dirty(&self) -> bool {
+        !self.dirty.is_empty()
+    }
+
+    /// Save the changes to the trunk.
+    pub fn save(&mut self) -> Result<()> {
+        let mut update = protocol::TrunkUpdate::default();
+        save_fields! {
+            self -> update: admin_state_up
+        };
+        save_option_fields! {
+            self -> update: description name
+        };
+        let inner = api::update_trunk(&self.session, self.id(), update)?;
+        self.dirty.clear();
+        self.inner = inner;
+        Ok(())
+    }
+
+    /// Apply the result of a sub-ports call, keeping other local changes.
+    fn update_sub_ports(&mut self, mut inner: protocol::Trunk) {
+        if self.dirty.contains("admin_state_up") {
+            inner.admin_state_up = self.inner.admin_state_up;
+        }
+        if self.dirty.contains("description") {
+            inner.description = self.inner.description.take();
+        }
+        if self.dirty.contains("name") {
+            inner.name = self.inner.name.take();
+        }
+        self.inner = inner;
+    }
+}
+
+impl Refresh for Trunk {
+    /// Refresh the trunk.
+    fn refresh(&mut self) -> Result<()> {
+        self.inner = api::get_trunk_by_id(&self.session, &self.inner.id)?;
+        self.dirty.clear();
+        Ok(())
+    }
+}
+
+impl TrunkQuery {
+    pub(crate) fn new(session: Rc<Session>) -> TrunkQuery {
+        TrunkQuery {
+            session,
+            query: Query::new(),
+            can_paginate: true,
+            port: None,
+        }
+    }
+
+    /// Add marker to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_marker<T: Into<String>>(mut self, marker: T) -> Self {
+        self.can_paginate = false;
+        self.query.push_str("marker", marker);
+        self
+    }
+
+    /// Add limit to the request.
+    ///
+    /// Using this disables automatic pagination.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.can_paginate = false;
+        self.query.push("limit", limit);
+        self
+    }
+
+    /// Add sorting to the request.
+    pub fn sort_by(mut self, sort: Sort<protocol::TrunkSortKey>) -> Self {
+        let (field, direction) = sort.into();
+        self.query.push_str("sort_key", field);
+        self.query.push("sort_dir", direction);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by administrative state."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    query_filter! {
+        #[doc = "Filter by description."]
+        set_description, with_description -> description
+    }
+
+    query_filter! {
+        #[doc = "Filter by trunk name."]
+        set_name, with_name -> name
+    }
+
+    /// Filter by parent port.
+    pub fn set_port<P: Into<PortRef>>(&mut self, value: P) {
+        self.port = Some(value.into());
+    }
+
+    /// Filter by parent port.
+    pub fn with_port<P: Into<PortRef>>(mut self, value: P) -> Self {
+        self.set_port(value);
+        self
+    }
+
+    query_filter! {
+        #[doc = "Filter by project (also commonly known as tenant)."]
+        set_project, with_project -> project_id
+    }
+
+    query_filter! {
+        #[doc = "Filter by trunk status."]
+        set_status, with_status -> status: protocol::TrunkStatus
+    }
+
+    /// Convert this query into an iterator executing the request.
+    ///
+    /// Returns a `FallibleIterator`, which is an iterator with each `next`
+    /// call returning a `Result`.
+    ///
+    /// Note that no requests are done until you start iterating.
+    pub fn into_iter(self) -> ResourceIterator<TrunkQuery> {
+        debug!("Fetching trunks with {:?}", self.query);
+        ResourceIterator::new(self)
+    }
+
+    /// Execute this request and return all results.
+    ///
+    /// A convenience shortcut for `self.into_iter().collect()`.
+    pub fn all(self) -> Result<Vec<Trunk>> {
+        self.into_iter().collect()
+    }
+
+    /// Return one and exactly one result.
+    ///
+    /// Fails with `ResourceNotFound` if the query produces no results and
+    /// with `TooManyItems` if the query produces more than one result.
+    pub fn one(mut self) -> Result<Trunk> {
+        debug!("Fetching one trunk with {:?}", self.query);
+        if self.can_paginate {
+            // We need only one result. We fetch maximum two to be able
+            // to check if the query yieled more than one result.
+            self.query.push("limit", 2);
+        }
+
+        self.into_iter().one()
+    }
+}
+
+impl ResourceQuery for TrunkQuery {
+    type Item = Trunk;
+
+    const DEFAULT_LIMIT: usize = 50;
+
+    fn can_paginate(&self) -> Result<bool> {
+        Ok(self.can_paginate)
+    }
+
+    fn extract_marker(&self, resource: &Self::Item) -> String {
+        resource.id().clone()
+    }
+
+    fn fetch_chunk(&self, limit: Option<usize>, marker: Option<String>) -> Result<Vec<Self::Item>> {
+        let query = self.query.with_marker_and_limit(limit, marker);
+        Ok(api::list_trunks(&self.session, &query)?
+            .into_iter()
+            .map(|item| Trunk::new(self.session.clone(), item))
+            .collect())
+    }
+
+    fn validate(&mut self) -> Result<()> {
+        if let Some(port) = self.port.take() {
+            let verified = port.into_verified(&self.session)?;
+            self.query.push_str("port_id", verified);
+        }
+        Ok(())
+    }
+}
+
+projectable_query!(TrunkQuery: ["trunks"] -> "trunks", Trunk: "Trunks");
+
+impl NewTrunk {
+    /// Start creating a trunk.
+    pub(crate) fn new(session: Rc<Session>, port: PortRef) -> NewTrunk {
+        NewTrunk {
+            session,
+            inner: protocol::Trunk {
+                admin_state_up: true,
+                created_at: None,
+                description: None,
+                id: String::new(),
+                name: None,
+                // Will be replaced in create()
+                port_id: String::new(),
+                project_id: None,
+                // Dummy value, not used when serializing
+                status: protocol::TrunkStatus::Active,
+                sub_ports: Vec::new(),
+                updated_at: None,
+            },
+            port,
+            sub_ports: Vec::new(),
+        }
+    }
+
+    /// Request creation of a trunk.
+    pub fn create(mut self) -> Result<Trunk> {
+        self.inner.port_id = self.port.into_verified(&self.session)?.into();
+        self.inner.sub_ports = convert_sub_ports(&self.session, self.sub_ports)?;
+
+        let inner = api::create_trunk(&self.session, self.inner)?;
+        Ok(Trunk::new(self.session, inner))
+    }
+
+    creation_inner_field! {
+        #[doc = "Set administrative status for the trunk."]
+        set_admin_state_up, with_admin_state_up -> admin_state_up: bool
+    }
+
+    creation_inner_field! {
+        #[doc = "Set description of the trunk."]
+        set_description, with_description -> description: optional String
+    }
+
+    creation_inner_field! {
+        #[doc = "Set a name for the trunk."]
+        set_name, with_name -> name: optional String
+    }
+
+    /// Add a sub-port to the trunk.
+    pub fn add_sub_port<P>(
+        &mut self,
+        port: P,
+        segmentation_type: protocol::SegmentationType,
+        segmentation_id: u32,
+    ) where
+        P: Into<PortRef>,
+    {
+        self.sub_ports
+            .push((port.into(), segmentation_type, segmentation_id));
+    }
+
+    /// Add a sub-port to the trunk.
+    pub fn with_sub_port<P>(
+        mut self,
+        port: P,
+        segmentation_type: protocol::SegmentationType,
+        segmentation_id: u32,
+    ) -> Self
+    where
+        P: Into<PortRef>,
+    {
+        self.add_sub_port(port, segmentation_type, segmentation_id);
+        self
+    }
+}
+
+impl IntoFallibleIterator for TrunkQuery {
+    type Item = Trunk;
+
+    type Error = Error;
+
+    type IntoFallibleIter = ResourceIterator<TrunkQuery>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        self.into_iter()
+    }
+}
+
+impl From<Trunk> for TrunkRef {
+    fn from(value: Trunk) -> TrunkRef {
+        TrunkRef::new_verified(value.inner.id)
+    }
+}
+
+#[cfg(feature = "network")]
+impl IntoVerified for TrunkRef {
+    /// Verify this reference and convert to an ID, if possible.
+    fn into_verified(self, session: &Session) -> Result<TrunkRef> {
+        Ok(if self.verified {
+            self
+        } else {
+            TrunkRef::new_verified(api::get_trunk(session, &self.value)?.id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::rc::Rc;
+
+    use serde_json::{json, Value};
+
+    use super::super::super::common::PortRef;
+    use super::super::api::test::mock_network_session;
+    use super::super::protocol::SegmentationType;
+    use super::Trunk;
+
+    fn trunk_json(sub_ports: Value) -> Value {
+        json!({
+            "admin_state_up": true,
+            "description": "old description",
+            "id": "trunk-id",
+            "name": "old",
+            "port_id": "parent-id",
+            "status": "ACTIVE",
+            "sub_ports": sub_ports,
+        })
+    }
+
+    #[test]
+    fn test_update_sub_ports_keeps_changes() {
+        let (session, requests) = mock_network_session(&["trunk"], |request| {
+            if request.path.ends_with("/add_subports") {
+                let sub_ports = json!([{
+                    "port_id": "child-id",
+                    "segmentation_id": 42,
+                    "segmentation_type": "vlan",
+                }]);
+                (200, trunk_json(sub_ports))
+            } else {
+                (200, trunk_json(json!([])))
+            }
+        });
+        let inner = serde_json::from_value(trunk_json(json!([]))).unwrap();
+        let mut trunk = Trunk::new(Rc::new(session), inner);
+        trunk.set_name("new");
+        trunk.set_admin_state_up(false);
+
+        trunk
+            .add_sub_port(
+                PortRef::new_verified("child-id".to_string()),
+                SegmentationType::Vlan,
+                42,
+            )
+            .unwrap();
+        assert_eq!(trunk.sub_ports().len(), 1);
+        assert_eq!(trunk.sub_ports()[0].port_id, "child-id");
+        assert_eq!(trunk.sub_ports()[0].segmentation_id, 42);
+        assert_eq!(trunk.name().as_ref().unwrap(), "new");
+        assert!(!trunk.admin_state_up());
+        assert_eq!(trunk.description().as_ref().unwrap(), "old description");
+        assert!(trunk.is_dirty());
+
+        trunk
+            .remove_sub_port(PortRef::new_verified("child-id".to_string()))
+            .unwrap();
+        assert!(trunk.sub_ports().is_empty());
+        assert_eq!(trunk.name().as_ref().unwrap(), "new");
+        assert!(trunk.is_dirty());
+
+        let requests = requests.lock().unwrap();
+        let bodies: Vec<Value> = requests
+            .iter()
+            .filter(|r| r.method == "PUT")
+            .map(|r| serde_json::from_str(&r.body).unwrap())
+            .collect();
+        assert_eq!(
+            bodies,
+            vec![
+                json!({ "sub_ports": [{
+                    "port_id": "child-id",
+                    "segmentation_id": 42,
+                    "segmentation_type": "vlan",
+                }] }),
+                json!({ "sub_ports": [{ "port_id": "child-id" }] }),
+            ]
+        );
+    }
+}
@@ -20,9 +20,13 @@ use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
+use std::ops::Deref;
 
+use osauth::sync::SyncSession;
 use serde::{Serialize, Serializer};
 
+#[cfg(feature = "network")]
+use super::network::NetworkExtension;
 use super::{Error, ErrorKind, Result};
 
 /// Type of query parameters.
@@ -274,3 +278,221 @@ pub mod url {
         url
     }
 }
+
+/// Synchronous session used by resources and API calls.
+///
+/// Dereferences to the `osauth` synchronous session and additionally caches
+/// information about the cloud that `osauth` does not know about.
+#[derive(Debug, Clone)]
+pub struct Session {
+    inner: SyncSession,
+    #[cfg(feature = "network")]
+    network_extensions: ValueCache<Vec<NetworkExtension>>,
+}
+
+impl Session {
+    /// Create a new session.
+    pub fn new(session: SyncSession) -> Session {
+        Session {
+            inner: session,
+            #[cfg(feature = "network")]
+            network_extensions: ValueCache::new(None),
+        }
+    }
+
+    /// Set a new endpoint interface to use.
+    ///
+    /// Clears the cached information.
+    pub fn set_endpoint_interface<S>(&mut self, endpoint_interface: S)
+    where
+        S: Into<String>,
+    {
+        self.inner.set_endpoint_interface(endpoint_interface);
+        self.reset_cache();
+    }
+
+    /// Refresh the session (renew token, refetch service catalog, etc).
+    ///
+    /// Clears the cached information.
+    pub fn refresh(&mut self) -> Result<()> {
+        self.inner.refresh()?;
+        self.reset_cache();
+        Ok(())
+    }
+
+    /// Cache of the Network API extensions.
+    #[cfg(feature = "network")]
+    pub fn network_extensions(&self) -> &ValueCache<Vec<NetworkExtension>> {
+        &self.network_extensions
+    }
+
+    fn reset_cache(&mut self) {
+        #[cfg(feature = "network")]
+        {
+            self.network_extensions = ValueCache::new(None);
+        }
+    }
+}
+
+impl Deref for Session {
+    type Target = SyncSession;
+
+    fn deref(&self) -> &SyncSession {
+        &self.inner
+    }
+}
+
+impl From<SyncSession> for Session {
+    fn from(value: SyncSession) -> Session {
+        Session::new(value)
+    }
+}
+
+impl From<osauth::Session> for Session {
+    fn from(value: osauth::Session) -> Session {
+        Session::new(SyncSession::new(value))
+    }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use osauth::NoAuth;
+    use serde_json::Value;
+
+    use super::Session;
+
+    /// A request received by the mock server.
+    #[derive(Clone, Debug)]
+    pub struct MockRequest {
+        pub method: String,
+        pub path: String,
+        /// Headers with lower-case names.
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl MockRequest {
+        /// Get a header value by its lower-case name.
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// A session for tests that never talk to a real cloud.
+    pub fn fake_session() -> Session {
+        let auth = NoAuth::new("http://127.0.0.1:5000/v2").expect("Invalid URL");
+        Session::from(osauth::Session::new(auth))
+    }
+
+    /// A session talking to a local server that only serves version discovery.
+    ///
+    /// The server reports the given maximum microversion for any service.
+    pub fn versioned_session(max_version: &str) -> Session {
+        mock_session(max_version, |_| (404, Value::Null)).0
+    }
+
+    /// A session talking to a local mock server.
+    ///
+    /// Version discovery reports the given maximum microversion for any service, all other
+    /// requests are answered by the handler with a status code and a JSON body (`null` for
+    /// an empty body). Received requests are recorded, except for version discovery.
+    pub fn mock_session<F>(max_version: &str, handler: F) -> (Session, Arc<Mutex<Vec<MockRequest>>>)
+    where
+        F: Fn(&MockRequest) -> (u16, Value) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Cannot bind a local port");
+        let root = "/v2.1/";
+        let endpoint = format!("http://{}{}", listener.local_addr().unwrap(), root);
+        let version = serde_json::json!({
+            "version": {
+                "id": "v2.1",
+                "status": "CURRENT",
+                "version": max_version,
+                "min_version": "2.1",
+                "links": [{"rel": "self", "href": endpoint}],
+            }
+        });
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        let _ = thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let request = match read_request(&stream) {
+                    Some(request) => request,
+                    None => continue,
+                };
+                let (status, body) = if request.method == "GET" && request.path == root {
+                    (200, version.clone())
+                } else {
+                    recorded.lock().unwrap().push(request.clone());
+                    handler(&request)
+                };
+                let body = if body.is_null() {
+                    String::new()
+                } else {
+                    body.to_string()
+                };
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let auth = NoAuth::new(endpoint.as_str()).expect("Invalid URL");
+        (Session::from(osauth::Session::new(auth)), requests)
+    }
+
+    fn read_request(stream: &TcpStream) -> Option<MockRequest> {
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        let _ = reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let path = parts.next()?.to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            line.clear();
+            let _ = reader.read_line(&mut line).ok()?;
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            let mut parts = header.splitn(2, ':');
+            let name = parts.next()?.trim().to_lowercase();
+            let value = parts.next().unwrap_or_default().trim().to_string();
+            headers.push((name, value));
+        }
+
+        let length = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).ok()?;
+        Some(MockRequest {
+            method,
+            path,
+            headers,
+            body: String::from_utf8(body).ok()?,
+        })
+    }
+}
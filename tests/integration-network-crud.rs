@@ -25,6 +25,7 @@ use std::sync::{Once, ONCE_INIT};
 use fallible_iterator::FallibleIterator;
 use waiter::Waiter;
 
+use openstack::network::NetworkExtension;
 use openstack::Refresh;
 
 static INIT: Once = ONCE_INIT;
@@ -38,6 +39,20 @@ fn set_up() -> openstack::Cloud {
         .expect("Failed to create an identity provider from the environment")
 }
 
+/// Whether the Network API supports the extension.
+///
+/// Tests of optional Network API features are skipped if it does not.
+fn supports_extension(os: &openstack::Cloud, extension: NetworkExtension) -> bool {
+    let supported = os
+        .network_extensions()
+        .expect("Cannot list network extensions")
+        .contains(&extension);
+    if !supported {
+        println!("Skipping, the {} extension is not supported", extension);
+    }
+    supported
+}
+
 #[test]
 fn test_port_create_update_delete() {
     let os = set_up();
@@ -376,3 +391,701 @@ fn test_floating_ip_create_delete() {
         .wait()
         .expect("Floating IP was not deleted");
 }
+
+#[test]
+fn test_router_create_update_delete() {
+    let os = set_up();
+
+    let network = os.new_network().create().expect("Could not create network");
+    let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
+        .unwrap()
+        .into();
+    let subnet = os
+        .new_subnet(network.clone(), cidr)
+        .create()
+        .expect("Could not create subnet");
+
+    let mut router = os
+        .new_router()
+        .with_name("rust-openstack-integration")
+        .create()
+        .expect("Could not create router");
+    assert_eq!(
+        router.name().as_ref().unwrap(),
+        "rust-openstack-integration"
+    );
+    assert!(router.admin_state_up());
+    assert!(router.external_gateway().is_none());
+    assert!(!router.is_dirty());
+
+    router
+        .add_interface(subnet.clone())
+        .expect("Cannot add an interface");
+
+    router.set_name("rust-openstack-integration-2");
+    assert!(router.is_dirty());
+    router.save().expect("Cannot update router");
+    assert!(!router.is_dirty());
+
+    let router_found = os
+        .find_routers()
+        .with_name("rust-openstack-integration-2")
+        .one()
+        .expect("Cannot find router by name");
+    assert_eq!(router_found.id(), router.id());
+
+    router
+        .remove_interface(subnet.clone())
+        .expect("Cannot remove an interface");
+
+    router
+        .delete()
+        .expect("Cannot request router deletion")
+        .wait()
+        .expect("Router was not deleted");
+
+    os.get_router("rust-openstack-integration-2")
+        .err()
+        .expect("Router is still present");
+
+    subnet
+        .delete()
+        .expect("Cannot request subnet deletion")
+        .wait()
+        .expect("Subnet was not deleted");
+
+    network
+        .delete()
+        .expect("Cannot request network deletion")
+        .wait()
+        .expect("Network was not deleted");
+}
+
+#[test]
+fn test_security_group_create_delete() {
+    let os = set_up();
+
+    let mut group = os
+        .new_security_group("rust-openstack-integration")
+        .with_description("Security group for testing")
+        .create()
+        .expect("Could not create security group");
+    assert_eq!(group.name(), "rust-openstack-integration");
+    assert_eq!(
+        group.description().as_ref().unwrap(),
+        "Security group for testing"
+    );
+    // Egress rules are created by default.
+    let default_rules = group.rules().len();
+    assert!(default_rules > 0);
+
+    let rule = group
+        .new_rule(openstack::network::SecurityGroupRuleDirection::Ingress)
+        .with_protocol("tcp")
+        .with_port(80)
+        .with_remote_ip_prefix("192.168.0.0/16".parse().unwrap())
+        .create()
+        .expect("Could not create security group rule");
+    assert_eq!(rule.security_group_id(), group.id());
+    assert_eq!(rule.port_range_min(), Some(80));
+    assert_eq!(rule.port_range_max(), Some(80));
+    assert_eq!(rule.ethertype(), openstack::network::EtherType::Ipv4);
+
+    // ICMP echo request: the type is greater than the code.
+    let icmp_rule = os
+        .new_security_group_rule(
+            group.clone(),
+            openstack::network::SecurityGroupRuleDirection::Ingress,
+        )
+        .with_protocol("icmp")
+        .with_port_range(8, 0)
+        .create()
+        .expect("Could not create ICMP security group rule");
+    assert_eq!(icmp_rule.port_range_min(), Some(8));
+    assert_eq!(icmp_rule.port_range_max(), Some(0));
+
+    let rules = os
+        .find_security_group_rules()
+        .with_security_group("rust-openstack-integration")
+        .all()
+        .expect("Cannot list security group rules");
+    assert_eq!(rules.len(), default_rules + 2);
+
+    group.refresh().expect("Cannot refresh security group");
+    assert_eq!(group.rules().len(), default_rules + 2);
+
+    let group_found = os
+        .find_security_groups()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find security group by name");
+    assert_eq!(group_found.id(), group.id());
+
+    rule.delete()
+        .expect("Cannot request security group rule deletion")
+        .wait()
+        .expect("Security group rule was not deleted");
+    icmp_rule
+        .delete()
+        .expect("Cannot request security group rule deletion")
+        .wait()
+        .expect("Security group rule was not deleted");
+
+    group
+        .delete()
+        .expect("Cannot request security group deletion")
+        .wait()
+        .expect("Security group was not deleted");
+
+    os.get_security_group("rust-openstack-integration")
+        .err()
+        .expect("Security group is still present");
+}
+
+#[test]
+fn test_trunk_create_delete() {
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Trunk) {
+        return;
+    }
+
+    let network = os.new_network().create().expect("Could not create network");
+    let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
+        .unwrap()
+        .into();
+    let subnet = os
+        .new_subnet(network.clone(), cidr)
+        .create()
+        .expect("Could not create subnet");
+    let parent = os
+        .new_port(network.clone())
+        .create()
+        .expect("Could not create parent port");
+    let child = os
+        .new_port(network.clone())
+        .create()
+        .expect("Could not create child port");
+
+    let mut trunk = os
+        .new_trunk(parent.clone())
+        .with_name("rust-openstack-integration")
+        .create()
+        .expect("Could not create trunk");
+    assert_eq!(trunk.name().as_ref().unwrap(), "rust-openstack-integration");
+    assert_eq!(trunk.port_id(), parent.id());
+    assert!(trunk.sub_ports().is_empty());
+
+    trunk.set_description("Trunk for testing");
+    trunk
+        .add_sub_port(
+            child.clone(),
+            openstack::network::SegmentationType::Vlan,
+            100,
+        )
+        .expect("Cannot add a sub-port");
+    assert_eq!(trunk.sub_ports().len(), 1);
+    assert_eq!(&trunk.sub_ports()[0].port_id, child.id());
+    assert_eq!(trunk.sub_ports()[0].segmentation_id, 100);
+    // The unsaved description is kept.
+    assert!(trunk.is_dirty());
+    trunk.save().expect("Cannot update trunk");
+    assert_eq!(trunk.description().as_ref().unwrap(), "Trunk for testing");
+
+    let trunk_found = os
+        .find_trunks()
+        .with_port(parent.clone())
+        .one()
+        .expect("Cannot find trunk by port");
+    assert_eq!(trunk_found.id(), trunk.id());
+    assert_eq!(trunk_found.sub_ports().len(), 1);
+
+    trunk
+        .remove_sub_port(child.clone())
+        .expect("Cannot remove a sub-port");
+    assert!(trunk.sub_ports().is_empty());
+
+    trunk
+        .delete()
+        .expect("Cannot request trunk deletion")
+        .wait()
+        .expect("Trunk was not deleted");
+
+    child
+        .delete()
+        .expect("Cannot request port deletion")
+        .wait()
+        .expect("Port was not deleted");
+    parent
+        .delete()
+        .expect("Cannot request port deletion")
+        .wait()
+        .expect("Port was not deleted");
+
+    subnet
+        .delete()
+        .expect("Cannot request subnet deletion")
+        .wait()
+        .expect("Subnet was not deleted");
+
+    network
+        .delete()
+        .expect("Cannot request network deletion")
+        .wait()
+        .expect("Network was not deleted");
+}
+
+#[test]
+fn test_qos_policy_create_delete() {
+    use openstack::network::{QosBandwidthLimitRule, QosRule};
+
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Qos) {
+        return;
+    }
+
+    let mut policy = os
+        .new_qos_policy("rust-openstack-integration")
+        .with_description("QoS policy for testing")
+        .create()
+        .expect("Could not create QoS policy");
+    assert_eq!(policy.name(), "rust-openstack-integration");
+    assert!(policy.rules().is_empty());
+    assert!(!policy.shared());
+
+    let rule = policy
+        .add_rule(QosBandwidthLimitRule::new(10000))
+        .expect("Cannot add a QoS rule");
+    let mut rule = match rule {
+        QosRule::BandwidthLimit(rule) => rule,
+        other => panic!("Unexpected rule {:?}", other),
+    };
+    assert!(!rule.id.is_empty());
+    assert_eq!(rule.max_kbps, 10000);
+    assert_eq!(policy.rules().len(), 1);
+
+    rule.max_kbps = 20000;
+    let rule = policy.update_rule(rule).expect("Cannot update a QoS rule");
+    match policy.rules()[0] {
+        QosRule::BandwidthLimit(ref rule) => assert_eq!(rule.max_kbps, 20000),
+        ref other => panic!("Unexpected rule {:?}", other),
+    }
+
+    let policy_found = os
+        .find_qos_policies()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find QoS policy by name");
+    assert_eq!(policy_found.id(), policy.id());
+    assert_eq!(policy_found.rules().len(), 1);
+
+    policy.remove_rule(&rule).expect("Cannot remove a QoS rule");
+    assert!(policy.rules().is_empty());
+
+    policy
+        .delete()
+        .expect("Cannot request QoS policy deletion")
+        .wait()
+        .expect("QoS policy was not deleted");
+
+    os.get_qos_policy("rust-openstack-integration")
+        .err()
+        .expect("QoS policy is still present");
+}
+
+#[test]
+fn test_network_share_unshare() {
+    use openstack::network::{RbacPolicyAction, RbacPolicyObjectType};
+
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Rbac) {
+        return;
+    }
+
+    let project = "0123456789abcdef0123456789abcdef";
+    let network = os
+        .new_network()
+        .with_name("rust-openstack-integration-rbac")
+        .create()
+        .expect("Could not create network");
+
+    let policy = network
+        .share_with(project)
+        .expect("Cannot share the network");
+    assert!(!policy.id().is_empty());
+    assert_eq!(*policy.action(), RbacPolicyAction::AccessAsShared);
+    assert_eq!(policy.object_id(), network.id());
+    assert_eq!(*policy.object_type(), RbacPolicyObjectType::Network);
+    assert_eq!(policy.target_project_id(), project);
+
+    let policy_found = os
+        .find_rbac_policies()
+        .with_object_id(network.id().clone())
+        .one()
+        .expect("Cannot find RBAC policy by object ID");
+    assert_eq!(policy_found.id(), policy.id());
+
+    network
+        .unshare_from(project)
+        .expect("Cannot unshare the network");
+    os.get_rbac_policy(policy.id())
+        .err()
+        .expect("RBAC policy is still present");
+    network
+        .unshare_from(project)
+        .err()
+        .expect("Unsharing a network twice succeeded");
+
+    network
+        .delete()
+        .expect("Cannot request network deletion")
+        .wait()
+        .expect("Network was not deleted");
+}
+
+#[test]
+fn test_agent_list_update() {
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Agent) {
+        return;
+    }
+
+    let agents = match os.find_agents().all() {
+        Ok(agents) => agents,
+        Err(ref err) if err.kind() == openstack::ErrorKind::AccessDenied => {
+            println!("Skipping, listing agents requires admin privileges");
+            return;
+        }
+        Err(err) => panic!("Cannot list agents: {}", err),
+    };
+    let agent = match agents.into_iter().next() {
+        Some(agent) => agent,
+        None => {
+            println!("Skipping, no agents found");
+            return;
+        }
+    };
+    assert!(!agent.id().is_empty());
+    assert!(!agent.host().is_empty());
+
+    let found = os
+        .find_agents()
+        .with_host(agent.host().clone())
+        .with_agent_type(agent.agent_type().clone())
+        .all()
+        .expect("Cannot find agents by host and type");
+    assert!(found.iter().any(|item| item.id() == agent.id()));
+
+    let mut agent = os.get_agent(agent.id()).expect("Cannot get an agent");
+    let description = agent.description().clone();
+    agent.set_description("rust-openstack-integration");
+    agent.save().expect("Cannot update agent");
+    assert_eq!(
+        agent.description().as_ref().map(String::as_str),
+        Some("rust-openstack-integration")
+    );
+
+    agent.set_description(description.unwrap_or_default());
+    agent.save().expect("Cannot restore agent description");
+}
+
+#[test]
+fn test_metering_label_create_delete() {
+    use openstack::network::MeteringLabelRuleDirection;
+
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Metering) {
+        return;
+    }
+
+    let label = match os
+        .new_metering_label("rust-openstack-integration")
+        .with_description("Metering label for testing")
+        .create()
+    {
+        Ok(label) => label,
+        Err(ref err) if err.kind() == openstack::ErrorKind::AccessDenied => {
+            println!("Skipping, creating metering labels requires admin privileges");
+            return;
+        }
+        Err(err) => panic!("Could not create metering label: {}", err),
+    };
+    assert!(!label.id().is_empty());
+    assert_eq!(label.name(), "rust-openstack-integration");
+
+    let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
+        .unwrap()
+        .into();
+    let rule = os
+        .new_metering_label_rule(label.id().clone(), MeteringLabelRuleDirection::Egress)
+        .with_destination_ip_prefix(cidr)
+        .create()
+        .expect("Could not create metering label rule");
+    assert_eq!(rule.destination_ip_prefix(), Some(cidr));
+    assert_eq!(rule.direction(), MeteringLabelRuleDirection::Egress);
+    assert_eq!(rule.metering_label_id(), label.id());
+
+    let label_found = os
+        .find_metering_labels()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find metering label by name");
+    assert_eq!(label_found.id(), label.id());
+
+    let rules = label
+        .rules()
+        .all()
+        .expect("Cannot list metering label rules");
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].id(), rule.id());
+
+    rule.delete()
+        .expect("Cannot request metering label rule deletion")
+        .wait()
+        .expect("Metering label rule was not deleted");
+
+    label
+        .delete()
+        .expect("Cannot request metering label deletion")
+        .wait()
+        .expect("Metering label was not deleted");
+
+    os.get_metering_label("rust-openstack-integration")
+        .err()
+        .expect("Metering label is still present");
+}
+
+#[test]
+fn test_firewall_create_delete() {
+    use openstack::network::FirewallRuleAction;
+
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Fwaas) {
+        return;
+    }
+
+    let allow = os
+        .new_firewall_rule(FirewallRuleAction::Allow)
+        .with_name("rust-openstack-integration-allow")
+        .with_protocol("tcp")
+        .with_destination_port("22")
+        .create()
+        .expect("Could not create firewall rule");
+    assert!(!allow.id().is_empty());
+    assert_eq!(allow.action(), FirewallRuleAction::Allow);
+    let deny = os
+        .new_firewall_rule(FirewallRuleAction::Deny)
+        .with_name("rust-openstack-integration-deny")
+        .create()
+        .expect("Could not create firewall rule");
+
+    let mut policy = os
+        .new_firewall_policy()
+        .with_name("rust-openstack-integration")
+        .with_firewall_rule(deny.id().clone())
+        .create()
+        .expect("Could not create firewall policy");
+    policy
+        .insert_rule(allow.id().clone(), 0)
+        .expect("Cannot insert a firewall rule");
+    let rules = policy.rules().expect("Cannot fetch firewall rules");
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].id(), allow.id());
+    assert_eq!(rules[1].id(), deny.id());
+
+    let group = os
+        .new_firewall_group()
+        .with_name("rust-openstack-integration")
+        .with_ingress_firewall_policy(policy.id().clone())
+        .create()
+        .expect("Could not create firewall group");
+    let ingress = group
+        .ingress_firewall_policy()
+        .expect("Cannot fetch ingress firewall policy");
+    assert_eq!(ingress.id(), policy.id());
+
+    let group_found = os
+        .find_firewall_groups()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find firewall group by name");
+    assert_eq!(group_found.id(), group.id());
+    let policy_found = os
+        .find_firewall_policies()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find firewall policy by name");
+    assert_eq!(policy_found.id(), policy.id());
+
+    group
+        .delete()
+        .expect("Cannot request firewall group deletion")
+        .wait()
+        .expect("Firewall group was not deleted");
+
+    policy
+        .remove_rule(deny.id().clone())
+        .expect("Cannot remove a firewall rule");
+    assert_eq!(
+        policy.rules().expect("Cannot fetch firewall rules").len(),
+        1
+    );
+    policy
+        .delete()
+        .expect("Cannot request firewall policy deletion")
+        .wait()
+        .expect("Firewall policy was not deleted");
+
+    allow
+        .delete()
+        .expect("Cannot request firewall rule deletion")
+        .wait()
+        .expect("Firewall rule was not deleted");
+    deny.delete()
+        .expect("Cannot request firewall rule deletion")
+        .wait()
+        .expect("Firewall rule was not deleted");
+
+    os.get_firewall_policy("rust-openstack-integration")
+        .err()
+        .expect("Firewall policy is still present");
+}
+
+#[test]
+fn test_vpn_create_delete() {
+    use openstack::network::{EndpointGroupType, IkeVersion};
+
+    let os = set_up();
+    if !supports_extension(&os, NetworkExtension::Vpnaas) {
+        return;
+    }
+    let floating_network_id = env::var("RUST_OPENSTACK_FLOATING_NETWORK")
+        .expect("Missing RUST_OPENSTACK_FLOATING_NETWORK");
+
+    let network = os.new_network().create().expect("Could not create network");
+    let cidr = ipnet::Ipv4Net::new(net::Ipv4Addr::new(192, 168, 1, 0), 24)
+        .unwrap()
+        .into();
+    let subnet = os
+        .new_subnet(network.clone(), cidr)
+        .create()
+        .expect("Could not create subnet");
+    let mut router = os
+        .new_router()
+        .with_name("rust-openstack-integration-vpn")
+        .create()
+        .expect("Could not create router");
+    router
+        .set_external_gateway(floating_network_id, None, Vec::new())
+        .expect("Cannot set an external gateway");
+    router
+        .add_interface(subnet.clone())
+        .expect("Cannot add an interface");
+
+    let vpn_service = os
+        .new_vpn_service(router.id().clone())
+        .with_name("rust-openstack-integration")
+        .create()
+        .expect("Could not create VPN service");
+    assert_eq!(vpn_service.router_id(), router.id());
+
+    let ike_policy = os
+        .new_ike_policy()
+        .with_name("rust-openstack-integration")
+        .with_ike_version(IkeVersion::V2)
+        .create()
+        .expect("Could not create IKE policy");
+    assert_eq!(ike_policy.ike_version(), IkeVersion::V2);
+    let ipsec_policy = os
+        .new_ipsec_policy()
+        .with_name("rust-openstack-integration")
+        .create()
+        .expect("Could not create IPsec policy");
+
+    let local_group = os
+        .new_endpoint_group(EndpointGroupType::Subnet)
+        .with_name("rust-openstack-integration-local")
+        .with_endpoint(subnet.id().clone())
+        .create()
+        .expect("Could not create local endpoint group");
+    let peer_group = os
+        .new_endpoint_group(EndpointGroupType::Cidr)
+        .with_name("rust-openstack-integration-peer")
+        .with_endpoint("10.42.0.0/24")
+        .create()
+        .expect("Could not create peer endpoint group");
+    assert_eq!(peer_group.endpoints(), &vec!["10.42.0.0/24".to_string()]);
+
+    let vpn_service_found = os
+        .find_vpn_services()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find VPN service by name");
+    assert_eq!(vpn_service_found.id(), vpn_service.id());
+    let ike_policy_found = os
+        .find_ike_policies()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find IKE policy by name");
+    assert_eq!(ike_policy_found.id(), ike_policy.id());
+    let ipsec_policy_found = os
+        .find_ipsec_policies()
+        .with_name("rust-openstack-integration")
+        .one()
+        .expect("Cannot find IPsec policy by name");
+    assert_eq!(ipsec_policy_found.id(), ipsec_policy.id());
+    let groups = os
+        .find_endpoint_groups()
+        .with_endpoint_type(EndpointGroupType::Cidr)
+        .all()
+        .expect("Cannot list endpoint groups");
+    assert!(groups.iter().any(|group| group.id() == peer_group.id()));
+
+    peer_group
+        .delete()
+        .expect("Cannot request endpoint group deletion")
+        .wait()
+        .expect("Endpoint group was not deleted");
+    local_group
+        .delete()
+        .expect("Cannot request endpoint group deletion")
+        .wait()
+        .expect("Endpoint group was not deleted");
+    ipsec_policy
+        .delete()
+        .expect("Cannot request IPsec policy deletion")
+        .wait()
+        .expect("IPsec policy was not deleted");
+    ike_policy
+        .delete()
+        .expect("Cannot request IKE policy deletion")
+        .wait()
+        .expect("IKE policy was not deleted");
+    vpn_service
+        .delete()
+        .expect("Cannot request VPN service deletion")
+        .wait()
+        .expect("VPN service was not deleted");
+
+    os.get_vpn_service("rust-openstack-integration")
+        .err()
+        .expect("VPN service is still present");
+
+    router
+        .remove_interface(subnet.clone())
+        .expect("Cannot remove an interface");
+    router
+        .delete()
+        .expect("Cannot request router deletion")
+        .wait()
+        .expect("Router was not deleted");
+    subnet
+        .delete()
+        .expect("Cannot request subnet deletion")
+        .wait()
+        .expect("Subnet was not deleted");
+    network
+        .delete()
+        .expect("Cannot request network deletion")
+        .wait()
+        .expect("Network was not deleted");
+}